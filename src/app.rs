@@ -7,10 +7,18 @@ pub struct App {
     // === UI ===
     /// The current select screen (might be overlayed by a display screen and thus not rendered).
     select: ui::screen::SelectScreen,
+    /// The task screen, which keeps its filter while it is not shown.
+    tasks: ui::screen::TaskScreen,
+    /// Whether the task screen is shown instead of the select screen.
+    show_tasks: bool,
     /// The top of the display stack, if present.
     display: Option<ui::screen::DisplayScreen>,
     /// The ids of note on the display stack
     display_stack: Vec<String>,
+    /// The quick switcher to jump to any note, shown on top of all screens while it is open.
+    switcher: Option<ui::Palette<String>>,
+    /// The ids of the notes shown during this session, most recent first.
+    recent: Vec<String>,
 
     // === DATA ===
     /// Index note data
@@ -23,8 +31,12 @@ pub struct App {
     builder: io::HtmlBuilder,
     /// The styles used by this app's screens.
     styles: ui::UiStyles,
-    /// Decides wether the index is cached when the program is quit.
+    /// The keys bound to the actions of this app's screens.
+    keys: ui::KeyBindings,
+    /// Decides wether the index is cached when the program is quit, also when quitting by a panic or a signal.
     cache_index: bool,
+    /// Whether display screens show the preview of their note, kept when navigating between notes.
+    display_preview: bool,
 }
 
 impl App {
@@ -66,6 +78,15 @@ impl App {
             }
         };
 
+        // Parse the keys given in the config
+        let keys = match ui::KeyBindings::new(&config) {
+            Ok(keys) => keys,
+            Err(e) => {
+                errors.push(e);
+                Default::default()
+            }
+        };
+
         // Use the config file to create managers & trackers
         loading_screen_callback("Creating managers & trackers...");
 
@@ -94,21 +115,28 @@ impl App {
         };
 
         // Print error message based on current directory
-        let mut msg = "Indexing...";
+        let mut warning = "";
         if let Some(user_dirs) = directories::UserDirs::new() {
             if config
                 .vault_path
                 .as_ref()
                 .is_some_and(|path| path == directories::UserDirs::home_dir(&user_dirs))
             {
-                msg = "Indexing...\n\nYou are running rucola in your home directory. This might take a while.\nConsider running in your notes directory instead.";
+                warning = "\n\nYou are running rucola in your home directory. This might take a while.\nConsider running in your notes directory instead.";
             }
         }
 
-        loading_screen_callback(msg);
+        loading_screen_callback(&format!("Indexing...{}", warning));
 
-        // Index all files in path
-        let (index, index_errors) = data::NoteIndex::new(tracker, builder.clone(), &config);
+        // Index all files in path, showing the progress below the current step
+        let (index, index_errors) = data::NoteIndex::new_with_progress(
+            tracker,
+            builder.clone(),
+            &config,
+            |step, done, total| {
+                loading_screen_callback(&format!("{} {} / {}{}", step, done, total, warning))
+            },
+        );
         errors.extend(index_errors);
 
         let index = std::rc::Rc::new(std::cell::RefCell::new(index));
@@ -116,6 +144,14 @@ impl App {
         // Use the config file to create managers & trackers
         loading_screen_callback("Initiliazing app state...");
 
+        let filter_history = match io::FilterHistory::new(&config) {
+            Ok(history) => history,
+            Err(e) => {
+                errors.push(e);
+                Default::default()
+            }
+        };
+
         // Initialize app state
         (
             Self {
@@ -124,16 +160,23 @@ impl App {
                     manager.clone(),
                     git_manager,
                     builder.clone(),
+                    filter_history,
                     styles,
                     &config,
                 ),
+                tasks: ui::screen::TaskScreen::new(index.clone(), manager.clone(), styles, &config),
+                show_tasks: false,
                 display: None,
                 display_stack: Vec::new(),
+                switcher: None,
+                recent: Vec::new(),
                 index,
                 styles,
+                keys,
                 manager,
                 builder,
                 cache_index: config.cache_index,
+                display_preview: config.display_preview,
             },
             errors,
         )
@@ -142,6 +185,15 @@ impl App {
     /// Reads the top of the display stack, creates a new display screen from it and sets that as the currently active display screen.
     /// If the display stack is empty, clears the display screen.
     fn set_display_to_top(&mut self) -> error::Result<()> {
+        // keep showing or hiding the preview like the previous display screen
+        if let Some(display) = &self.display {
+            self.display_preview = display.shows_preview();
+        }
+        // remember the shown note for the quick switcher
+        if let Some(id) = self.display_stack.last() {
+            self.recent.retain(|recent| recent != id);
+            self.recent.insert(0, id.clone());
+        }
         self.display = match self.display_stack.last() {
             Some(id) => Some(ui::screen::DisplayScreen::new(
                 id,
@@ -149,6 +201,8 @@ impl App {
                 self.manager.clone(),
                 self.builder.clone(),
                 self.styles,
+                self.keys.clone(),
+                self.display_preview,
             )?),
             None => None,
         };
@@ -179,6 +233,7 @@ impl App {
         if modifications {
             // if anything happened in the file system, better refresh the filters
             self.select.refresh_env_stats();
+            self.tasks.refresh_tasks();
             // also refresh the display by setting it to none
             self.set_display_to_top()?;
         }
@@ -189,9 +244,23 @@ impl App {
             return Ok(ui::TerminalMessage::None);
         };
 
-        // Update appropriate screen
-        let msg = if let Some(display) = &mut self.display {
+        // Update appropriate screen, or the quick switcher on top of it
+        let msg = if let Some(switcher) = &mut self.switcher {
+            match switcher.update(key) {
+                ui::PaletteChoice::Pending => Ok(ui::Message::None),
+                ui::PaletteChoice::Cancelled => {
+                    self.switcher = None;
+                    Ok(ui::Message::None)
+                }
+                ui::PaletteChoice::Chosen(id) => {
+                    self.switcher = None;
+                    Ok(ui::Message::DisplayStackPush(id))
+                }
+            }
+        } else if let Some(display) = &mut self.display {
             display.update(key)
+        } else if self.show_tasks {
+            self.tasks.update(key)
         } else {
             self.select.update(key)
         };
@@ -200,13 +269,9 @@ impl App {
 
         // Act on the potentially returned message.
         match &msg {
-            // Message that do not modify the app potentially trigger a save, but no other immediate effect and are later passed up.
-            ui::Message::Quit => {
-                if self.cache_index {
-                    self.index.borrow().save()
-                }
-            }
-            ui::Message::None | ui::Message::OpenExternalCommand(_) => {}
+            // Message that do not modify the app have no immediate effect and are later passed up.
+            // The index is saved when the app is dropped after quitting.
+            ui::Message::Quit | ui::Message::None | ui::Message::OpenExternalCommand(_) => {}
             ui::Message::DisplayStackClear => {
                 // Clear the display stack and remove the current display screen, if there is one.
                 self.display_stack.clear();
@@ -222,6 +287,20 @@ impl App {
                 self.display_stack.push(new_id.clone());
                 self.set_display_to_top()?;
             }
+            ui::Message::TaskScreenOpen => {
+                self.tasks.refresh_tasks();
+                self.show_tasks = true;
+            }
+            ui::Message::TaskScreenClose => {
+                self.show_tasks = false;
+            }
+            ui::Message::QuickSwitcherOpen => {
+                self.switcher = Some(ui::Palette::notes(
+                    &self.index.borrow(),
+                    &self.recent,
+                    self.styles,
+                ));
+            }
         }
 
         Ok(msg.into())
@@ -230,8 +309,27 @@ impl App {
     pub fn draw(&self, area: Rect, buf: &mut Buffer) {
         if let Some(display) = &self.display {
             display.draw(area, buf);
+        } else if self.show_tasks {
+            self.tasks.draw(area, buf);
         } else {
             self.select.draw(area, buf);
         }
+        if let Some(switcher) = &self.switcher {
+            switcher.draw(area, buf);
+        }
+    }
+}
+
+impl Drop for App {
+    /// Saves the index cache, if enabled.
+    /// As the app is dropped both when quitting regularly and when unwinding from a panic, the cache is always kept up to date.
+    fn drop(&mut self) {
+        if self.cache_index {
+            // The index might still be borrowed when panicking, in which case it is not saved.
+            // Errors are ignored, as a missing cache only leads to re-indexing on the next launch.
+            if let Ok(index) = self.index.try_borrow() {
+                let _ = index.save();
+            }
+        }
     }
 }
@@ -1,4 +1,7 @@
-use std::{collections::HashMap, path};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path,
+};
 
 use crate::{data, error, ui};
 
@@ -26,6 +29,8 @@ pub struct Config {
     pub(crate) default_extension: String,
     /// Whether to create a cache file of the index to reload on program start.
     pub(crate) cache_index: bool,
+    /// Whether to save the full-text index alongside the index cache.
+    pub(crate) cache_full_text: bool,
     /// When set to true, information about the git status of the repository is shown on the select screen.
     pub(crate) enable_git: bool,
     /// Selected theme
@@ -36,10 +41,20 @@ pub struct Config {
     pub(crate) select_columns: Vec<(String, data::NoteColumn)>,
     /// Whether tags have to match exactly or only by prefix when filtering.
     pub(crate) tag_match: data::TagMatch,
-    /// Default column to sort notes by.
-    pub(crate) default_sorting: data::NoteColumn,
-    /// Default sorting direction (true for ascending, false for descending).
+    /// Default column or columns to sort notes by.
+    pub(crate) default_sorting: Sorting,
+    /// Default sorting direction (true for ascending, false for descending), if only a single column is given.
     pub(crate) default_sorting_asc: bool,
+    /// Named filters that can be applied from a list on the select screen.
+    pub(crate) saved_filters: BTreeMap<String, SavedFilter>,
+    /// Whether to show the saved filters and their number of matching notes next to the note list.
+    pub(crate) show_saved_filters: bool,
+    /// Whether to show the rendered content of the note on the display screen.
+    pub(crate) display_preview: bool,
+    /// Whether to show the rendered content of the selected note next to the note list on the select screen.
+    pub(crate) select_preview: bool,
+    /// How many applied filters to remember in the user data directory to recall them later. Zero turns the filter history off.
+    pub(crate) filter_history_length: usize,
     /// The editor to use for notes.
     pub(crate) editor: Option<Vec<String>>,
     /// Main viewer to inspect rendered notes.
@@ -62,6 +77,30 @@ pub struct Config {
     pub(crate) math_replacements: HashMap<String, String>,
     /// Configuration section for the diary feature
     pub(crate) diary: DiaryConfig,
+    /// Keys to use for actions instead of their default keys.
+    pub(crate) keys: BTreeMap<ui::Action, Vec<String>>,
+}
+
+/// How to sort the notes on the select screen.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum Sorting {
+    /// A single column, sorted in the direction given by `default_sorting_asc`.
+    Column(data::NoteColumn),
+    /// Multiple columns with their directions, where later columns only decide between notes that are equal in all earlier ones.
+    Keys(Vec<data::SortKey>),
+}
+
+/// A filter saved under a name in the config, together with how to sort its results.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SavedFilter {
+    /// The filter string, just as it would be typed into the filter area.
+    pub filter: String,
+    /// The column to sort the matching notes by. If none is given, they are sorted by score.
+    pub sorting: Option<data::NoteColumn>,
+    /// The sorting direction (true for ascending, false for descending).
+    #[serde(default)]
+    pub sorting_asc: bool,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default)]
@@ -80,12 +119,14 @@ impl Default for Config {
             vault_path: None,
             file_types: vec![String::from("markdown")],
             default_extension: String::from("md"),
-            cache_index: false,
+            cache_index: true,
+            cache_full_text: false,
             enable_git: true,
             theme: "default_dark".to_string(),
             stats_show: ui::screen::StatsShow::Both,
             select_columns: vec![
                 ("Name".to_string(), data::NoteColumn::Name),
+                ("Folder".to_string(), data::NoteColumn::Folder),
                 ("   Words".to_string(), data::NoteColumn::Words),
                 ("   Chars".to_string(), data::NoteColumn::Chars),
                 ("GlobalOut".to_string(), data::NoteColumn::GlobalOutLinks),
@@ -94,8 +135,13 @@ impl Default for Config {
                 ("LocalIn".to_string(), data::NoteColumn::LocalInLinks),
             ],
             tag_match: data::TagMatch::Exact,
-            default_sorting: data::NoteColumn::Name,
+            default_sorting: Sorting::Column(data::NoteColumn::Name),
             default_sorting_asc: true,
+            saved_filters: BTreeMap::new(),
+            show_saved_filters: false,
+            display_preview: true,
+            select_preview: false,
+            filter_history_length: 100,
             editor: None,
             viewer_type: Some(ViewerType::Html),
             viewer: Some(vec![String::from("firefox"), String::from("%p")]),
@@ -110,11 +156,20 @@ impl Default for Config {
                 "\\mathbb".to_string(),
             )]),
             diary: DiaryConfig::default(),
+            keys: BTreeMap::new(),
         }
     }
 }
 
 impl Config {
+    /// Returns the columns to sort notes by by default, together with their directions.
+    pub fn default_sort_keys(&self) -> Vec<data::SortKey> {
+        match &self.default_sorting {
+            Sorting::Column(column) => vec![data::SortKey::new(*column, self.default_sorting_asc)],
+            Sorting::Keys(keys) => keys.clone(),
+        }
+    }
+
     /// Ensures the vault path in this configuration struct is set and not `None`.
     /// If a vault path is given in a command line argument, that one takes precedent over one given in the configuration file.
     /// If neither the configuration file nor the command line arguments specify a vault path, the current directory is used instead.
@@ -163,3 +218,31 @@ impl Config {
             .unwrap_or_else(|| pwd.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_default_sorting() {
+        let config: Config =
+            toml::from_str("default_sorting = \"Words\"\ndefault_sorting_asc = false").unwrap();
+        assert_eq!(
+            config.default_sort_keys(),
+            vec![data::SortKey::new(data::NoteColumn::Words, false)]
+        );
+
+        let config: Config = toml::from_str(
+            "default_sorting = [{ column = \"LocalInLinks\" }, { column = \"Name\", ascending = true }]",
+        )
+        .unwrap();
+        assert_eq!(
+            config.default_sort_keys(),
+            vec![
+                data::SortKey::new(data::NoteColumn::LocalInLinks, false),
+                data::SortKey::new(data::NoteColumn::Name, true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use itertools::Itertools;
+
+/// Returns all words the given word typed into the filter area can be completed to.
+///  - Words starting with `#` complete to tags, the most common ones first.
+///  - Words starting with `>` or `<` complete to notes, written like their id without folders where that is unique.
+///  - All other words complete to the keys of frontmatter properties, followed by `:`.
+///
+/// A leading `!` is kept, so negated conditions can be completed as well.
+pub fn filter_completions(word: &str, index: &super::NoteIndex) -> Vec<String> {
+    let (negation, word) = match word.strip_prefix('!') {
+        Some(word) => ("!", word),
+        None => ("", word),
+    };
+    let lowercase = word.to_lowercase();
+
+    let completions = match word.chars().next() {
+        None => Vec::new(),
+        Some('#') => index
+            .tags_vec()
+            .into_iter()
+            .map(|(tag, _count)| tag)
+            .filter(|tag| tag.to_lowercase().starts_with(&lowercase))
+            .collect(),
+        Some(prefix @ ('>' | '<')) => {
+            let typed = super::link_to_id(&word[1..]);
+            let names = index
+                .inner
+                .keys()
+                .map(|id| id.rsplit('/').next().unwrap_or(id))
+                .counts();
+            index
+                .inner
+                .keys()
+                .map(|id| {
+                    let name = id.rsplit('/').next().unwrap_or(id);
+                    // qualify names that several notes share by their folders
+                    if names.get(name).is_some_and(|count| *count > 1) {
+                        id.as_str()
+                    } else {
+                        name
+                    }
+                })
+                .filter(|name| name.starts_with(&typed))
+                .sorted()
+                .map(|name| format!("{prefix}{name}"))
+                .collect()
+        }
+        Some(_) => index
+            .inner
+            .values()
+            .flat_map(|note| note.properties.keys())
+            .map(|key| key.to_lowercase())
+            .filter(|key| key.starts_with(&lowercase))
+            .sorted()
+            .dedup()
+            .map(|key| format!("{key}:"))
+            .collect(),
+    };
+
+    completions
+        .into_iter()
+        .map(|completion| format!("{negation}{completion}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data, io};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_filter_completions() {
+        let tmp = testdir::testdir!();
+        std::fs::create_dir_all(tmp.join("work")).unwrap();
+        std::fs::write(
+            tmp.join("Lie Group.md"),
+            "---\nstatus: draft\nStage: 2\n---\n#math #manifold",
+        )
+        .unwrap();
+        std::fs::write(tmp.join("Linux.md"), "---\nstatus: done\n---\n#math #os").unwrap();
+        std::fs::write(tmp.join("Index.md"), "#math").unwrap();
+        std::fs::write(tmp.join("work/Index.md"), "").unwrap();
+
+        let config = crate::Config {
+            vault_path: Some(tmp),
+            ..Default::default()
+        };
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let index = data::NoteIndex::new(tracker, builder, &config).0;
+
+        assert_eq!(filter_completions("#m", &index), vec!["#math", "#manifold"]);
+        assert_eq!(filter_completions("!#O", &index), vec!["!#os"]);
+        assert_eq!(
+            filter_completions(">li", &index),
+            vec![">lie-group", ">linux"]
+        );
+        assert_eq!(filter_completions("<Lie-G", &index), vec!["<lie-group"]);
+        assert_eq!(filter_completions(">in", &index), vec![">index"]);
+        assert_eq!(filter_completions(">work/", &index), vec![">work/index"]);
+        assert_eq!(filter_completions("st", &index), vec!["stage:", "status:"]);
+        assert_eq!(filter_completions("", &index), Vec::<String>::new());
+        assert_eq!(filter_completions("#nothing", &index), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,135 @@
+use std::ops::{Bound, RangeBounds};
+
+use crate::error;
+
+/// A statistic of a note a count condition refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountField {
+    /// The number of words.
+    Words,
+    /// The number of characters.
+    Chars,
+    /// The number of links without a valid target anywhere.
+    Broken,
+    /// The number of links from other notes to the note.
+    InLinks,
+    /// The number of links from the note to other notes, not counting broken ones.
+    OutLinks,
+}
+
+impl CountField {
+    /// Returns the field belonging to the given lowercase filter key, if there is one.
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "words" => Some(Self::Words),
+            "chars" => Some(Self::Chars),
+            "broken" => Some(Self::Broken),
+            "inlinks" => Some(Self::InLinks),
+            "outlinks" => Some(Self::OutLinks),
+            _ => None,
+        }
+    }
+}
+
+/// A condition on a statistic of a note, as typed into the filter area.
+/// Numbers can be compared with `>`, `>=`, `<`, `<=` or matched exactly, e.g. `words:>500`, `chars:<100` or `inlinks:0` for notes no other note links to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CountCondition {
+    /// The statistic that is checked.
+    field: CountField,
+    /// The lower and upper bound of all values of the statistic that match.
+    range: (Bound<usize>, Bound<usize>),
+}
+
+impl CountCondition {
+    /// Parses the value of a count condition, given the key it was typed with for error messages.
+    pub fn parse(field: CountField, key: &str, value: &str) -> error::Result<Self> {
+        let (comparison, number) = value
+            .find(|c: char| !matches!(c, '<' | '>' | '='))
+            .map(|position| value.split_at(position))
+            .unwrap_or((value, ""));
+
+        let number = number.parse::<usize>().map_err(|_| {
+            error::RucolaError::FilterParseError(format!(
+                "Expected a number in '{key}:{value}', e.g. {key}:>10 or {key}:0."
+            ))
+        })?;
+
+        let range = match comparison {
+            "" | "=" => (Bound::Included(number), Bound::Included(number)),
+            ">" => (Bound::Excluded(number), Bound::Unbounded),
+            ">=" => (Bound::Included(number), Bound::Unbounded),
+            "<" => (Bound::Unbounded, Bound::Excluded(number)),
+            "<=" => (Bound::Unbounded, Bound::Included(number)),
+            _ => {
+                return Err(error::RucolaError::FilterParseError(format!(
+                    "Unknown comparison '{comparison}', use <, <=, >, >= or none."
+                )))
+            }
+        };
+
+        Ok(Self { field, range })
+    }
+
+    /// Checks if the statistic of the note lies in the range of this condition.
+    pub fn matches(&self, note: &super::Note, index: &super::NoteIndex) -> bool {
+        let count = match self.field {
+            CountField::Words => note.words,
+            CountField::Chars => note.characters,
+            CountField::Broken => index.broken_links(&index.path_to_id(&note.path)),
+            CountField::InLinks => index.backlinks(&index.path_to_id(&note.path)).len(),
+            CountField::OutLinks => index.outlinks(&index.path_to_id(&note.path)).len(),
+        };
+        self.range.contains(&count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse() {
+        let range = |value: &str| {
+            CountCondition::parse(CountField::Words, "words", value)
+                .unwrap()
+                .range
+        };
+
+        assert!(range("500").contains(&500));
+        assert!(!range("500").contains(&501));
+        assert_eq!(range("=500"), range("500"));
+        assert!(range(">500").contains(&501));
+        assert!(!range(">500").contains(&500));
+        assert!(range(">=500").contains(&500));
+        assert!(range("<100").contains(&99));
+        assert!(!range("<100").contains(&100));
+        assert!(range("<=100").contains(&100));
+        assert!(!range("<0").contains(&0));
+
+        for (value, message) in [
+            (
+                ">",
+                "Expected a number in 'words:>', e.g. words:>10 or words:0.",
+            ),
+            (
+                "",
+                "Expected a number in 'words:', e.g. words:>10 or words:0.",
+            ),
+            (
+                "many",
+                "Expected a number in 'words:many', e.g. words:>10 or words:0.",
+            ),
+            ("<>5", "Unknown comparison '<>', use <, <=, >, >= or none."),
+        ] {
+            assert_eq!(
+                CountCondition::parse(CountField::Words, "words", value)
+                    .unwrap_err()
+                    .to_string(),
+                format!("Invalid filter: {message}"),
+                "{value}"
+            );
+        }
+    }
+}
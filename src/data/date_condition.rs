@@ -0,0 +1,211 @@
+use std::time::SystemTime;
+
+use chrono::{DateTime, Local, Months, NaiveDate, TimeDelta};
+
+use crate::error;
+
+/// The point in time of a note a date condition refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateField {
+    /// The last modification of the note file.
+    Modified,
+    /// The creation of the note, see [`super::Note::created`].
+    Created,
+}
+
+impl DateField {
+    /// Returns the field belonging to the given lowercase filter key, if it is `modified` or `created`.
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "modified" => Some(Self::Modified),
+            "created" => Some(Self::Created),
+            _ => None,
+        }
+    }
+}
+
+/// A condition on the modification or creation time of a note, as typed into the filter area.
+///  - `modified:2025-03` holds for notes modified in March 2025, just like `modified:2025` and `modified:2025-03-14` for a year and a day.
+///  - `modified:>2025-03` holds for notes modified after March 2025, `modified:<2025-03` for notes modified before it.
+///  - `modified:7d` and `modified:<7d` hold for notes modified less than 7 days ago, `modified:>7d` for notes modified earlier.
+///    Instead of days, ages can be given in hours (`h`), weeks (`w`), months (`m`) and years (`y`).
+///  - `modified:` without a value holds for notes with a known modification time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateCondition {
+    /// The point in time that is checked.
+    field: DateField,
+    /// The earliest matching point in time, if any.
+    start: Option<SystemTime>,
+    /// The first point in time after all matching ones, if any.
+    end: Option<SystemTime>,
+}
+
+impl DateCondition {
+    /// Parses the value of a date condition, computing ages relative to the given current time.
+    pub fn parse(field: DateField, value: &str, now: DateTime<Local>) -> error::Result<Self> {
+        let (comparison, date) = match value.chars().next() {
+            Some(comparison @ ('<' | '>')) => (Some(comparison), &value[1..]),
+            _ => (None, value),
+        };
+
+        let (start, end) = if date.is_empty() {
+            if let Some(comparison) = comparison {
+                return Err(error::RucolaError::FilterParseError(format!(
+                    "Expected a date after '{comparison}'."
+                )));
+            }
+            (None, None)
+        } else if let Some(time) = parse_age(date, now) {
+            // younger than the age means after the resulting point in time
+            match comparison {
+                Some('>') => (None, Some(time)),
+                _ => (Some(time), None),
+            }
+        } else if let Some((start, end)) = parse_period(date) {
+            match comparison {
+                Some('>') => (Some(end), None),
+                Some('<') => (None, Some(start)),
+                _ => (Some(start), Some(end)),
+            }
+        } else {
+            return Err(error::RucolaError::FilterParseError(format!(
+                "Invalid date '{date}', use e.g. 2025-03-14, 2025-03, 2025 or an age like 7d."
+            )));
+        };
+
+        Ok(Self { field, start, end })
+    }
+
+    /// Checks if the note's point in time is known and lies in the range of this condition.
+    pub fn matches(&self, note: &super::Note) -> bool {
+        let time = match self.field {
+            DateField::Modified => note.last_modification,
+            DateField::Created => note.created,
+        };
+        time.is_some_and(|time| {
+            self.start.is_none_or(|start| time >= start) && self.end.is_none_or(|end| time < end)
+        })
+    }
+}
+
+/// Parses an age like `12h`, `7d`, `2w`, `3m` or `1y` into the point in time this long before now.
+fn parse_age(age: &str, now: DateTime<Local>) -> Option<SystemTime> {
+    let unit = age.chars().last()?;
+    let amount = age[..age.len() - unit.len_utf8()].parse::<u32>().ok()?;
+    let time = match unit {
+        'h' => now.checked_sub_signed(TimeDelta::hours(amount.into())),
+        'd' => now.checked_sub_signed(TimeDelta::days(amount.into())),
+        'w' => now.checked_sub_signed(TimeDelta::weeks(amount.into())),
+        'm' => now.checked_sub_months(Months::new(amount)),
+        'y' => now.checked_sub_months(Months::new(amount.checked_mul(12)?)),
+        _ => None,
+    }?;
+    Some(time.into())
+}
+
+/// Parses a day (`2025-03-14`), month (`2025-03`) or year (`2025`) into the local start of it and the start of the next one.
+fn parse_period(date: &str) -> Option<(SystemTime, SystemTime)> {
+    let parts = date
+        .split('-')
+        .map(|part| part.parse::<u32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    let (start, end) = match parts[..] {
+        [year] if date.len() == 4 => {
+            let start = NaiveDate::from_ymd_opt(year.try_into().ok()?, 1, 1)?;
+            (start, start.checked_add_months(Months::new(12))?)
+        }
+        [year, month] => {
+            let start = NaiveDate::from_ymd_opt(year.try_into().ok()?, month, 1)?;
+            (start, start.checked_add_months(Months::new(1))?)
+        }
+        [year, month, day] => {
+            let start = NaiveDate::from_ymd_opt(year.try_into().ok()?, month, day)?;
+            (start, start.succ_opt()?)
+        }
+        _ => return None,
+    };
+
+    Some((start_of_day(start)?, start_of_day(end)?))
+}
+
+/// Returns the first point in time of the given day in the local time zone.
+fn start_of_day(date: NaiveDate) -> Option<SystemTime> {
+    date.and_hms_opt(0, 0, 0)?
+        .and_local_timezone(Local)
+        .earliest()
+        .map(SystemTime::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_date_conditions() {
+        let now = Local.with_ymd_and_hms(2025, 3, 14, 12, 0, 0).unwrap();
+        let at = |year, month, day| {
+            let time: SystemTime = Local
+                .with_ymd_and_hms(year, month, day, 12, 0, 0)
+                .unwrap()
+                .into();
+            super::super::Note {
+                last_modification: Some(time),
+                ..Default::default()
+            }
+        };
+        let matches = |value: &str, note: &super::super::Note| {
+            DateCondition::parse(DateField::Modified, value, now)
+                .unwrap()
+                .matches(note)
+        };
+
+        assert!(matches("2025-03-14", &at(2025, 3, 14)));
+        assert!(!matches("2025-03-14", &at(2025, 3, 15)));
+        assert!(matches("2025-03", &at(2025, 3, 1)));
+        assert!(!matches("2025-03", &at(2025, 4, 1)));
+        assert!(matches("2025", &at(2025, 12, 31)));
+        assert!(matches(">2025-01-01", &at(2025, 1, 2)));
+        assert!(!matches(">2025-01-01", &at(2025, 1, 1)));
+        assert!(matches("<2025-03", &at(2025, 2, 28)));
+        assert!(!matches("<2025-03", &at(2025, 3, 1)));
+
+        assert!(matches("<7d", &at(2025, 3, 8)));
+        assert!(matches("7d", &at(2025, 3, 8)));
+        assert!(!matches("<7d", &at(2025, 3, 6)));
+        assert!(matches(">7d", &at(2025, 3, 6)));
+        assert!(matches("<2w", &at(2025, 3, 1)));
+        assert!(matches(">1m", &at(2025, 2, 13)));
+        assert!(!matches(">1m", &at(2025, 2, 15)));
+        assert!(matches("<1y", &at(2024, 3, 15)));
+        assert!(matches(">12h", &at(2025, 3, 13)));
+
+        assert!(matches("", &at(2025, 3, 14)));
+        assert!(!matches("", &super::super::Note::default()));
+        assert!(!DateCondition::parse(DateField::Created, "2025", now)
+            .unwrap()
+            .matches(&at(2025, 3, 14)));
+
+        for (value, message) in [
+            (">", "Expected a date after '>'."),
+            (
+                "2025-13",
+                "Invalid date '2025-13', use e.g. 2025-03-14, 2025-03, 2025 or an age like 7d.",
+            ),
+            (
+                "<soon",
+                "Invalid date 'soon', use e.g. 2025-03-14, 2025-03, 2025 or an age like 7d.",
+            ),
+        ] {
+            assert_eq!(
+                DateCondition::parse(DateField::Modified, value, now)
+                    .unwrap_err()
+                    .to_string(),
+                format!("Invalid filter: {message}"),
+                "{value}"
+            );
+        }
+    }
+}
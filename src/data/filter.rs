@@ -1,5 +1,10 @@
 use fuzzy_matcher::FuzzyMatcher;
 
+use super::{CountCondition, CountField, DateCondition, DateField, FullTextQuery, Query};
+
+/// The factor the relevance of a note for a full-text query is multiplied with before it is added to its fuzzy match score.
+const RELEVANCE_SCALE: f64 = 10.0;
+
 /// Describes how to match tags when filtering
 #[derive(Debug, Default, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub enum TagMatch {
@@ -19,6 +24,15 @@ impl TagMatch {
     }
 }
 
+/// The parts of a note matching the conditions of a filter, used to show why the note is part of the results.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Highlights {
+    /// The sorted positions of the characters of the display name matched by the title words.
+    pub name: Vec<usize>,
+    /// The tags of the note and the notes it links to or is linked from that were searched for, e.g. `#topology`, `>Atlas` or `<Atlas`.
+    pub conditions: Vec<String>,
+}
+
 /// Describes a way to filter notes by their contained tags and/or title
 #[derive(Debug, Default, Clone)]
 pub struct Filter {
@@ -32,24 +46,70 @@ pub struct Filter {
     pub links: Vec<(String, bool)>,
     /// The backlinks to look for or exclude, already converted to ids.
     pub blinks: Vec<(String, bool)>,
+    /// The frontmatter properties to look for or exclude, as pairs of lowercase key and value.
+    /// An empty value, written as `*`, matches any note that has the property at all.
+    pub properties: Vec<(String, String, bool)>,
+    /// The ranges the modification or creation time has to lie in or must not lie in.
+    pub dates: Vec<(DateCondition, bool)>,
+    /// The ranges statistics like the number of words have to lie in or must not lie in.
+    pub counts: Vec<(CountCondition, bool)>,
+    /// The folders to look in or exclude, already converted to the beginning of ids.
+    pub folders: Vec<(String, bool)>,
     /// The words to search the note title for. Will be fuzzy matched with the note title.
     pub title: String,
-    /// Everything to be searched for in the full text of the notes, in lowercase.
-    pub full_text: Option<String>,
+    /// Everything to be searched for in the full text of the notes.
+    pub full_text: Option<FullTextQuery>,
+    /// The boolean combination of conditions, if the filter string uses `AND`, `OR`, `NOT` or parentheses.
+    /// In this case, it replaces all of the conditions above and the any/all mode.
+    pub query: Option<Query>,
+    /// The error message if the filter string could not be parsed, e.g. because of unbalanced parentheses, an invalid date or an invalid regular expression.
+    pub error: Option<String>,
 }
 
 impl Filter {
     pub fn new(filter_string: &str, any: bool, tag_match: TagMatch) -> Self {
+        // filters with operators or parentheses are parsed as a boolean query
+        if Query::is_query(filter_string) {
+            let (query, error) = match Query::parse(filter_string) {
+                Ok(query) => (Some(query), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            return Self {
+                any,
+                tag_match,
+                query,
+                error,
+                ..Default::default()
+            };
+        }
+
         let mut tags = Vec::new();
         let mut links = Vec::new();
         let mut blinks = Vec::new();
+        let mut properties = Vec::new();
+        let mut dates = Vec::new();
+        let mut counts = Vec::new();
+        let mut folders = Vec::new();
         let mut title = String::new();
 
+        // invalid conditions leave an empty filter with only the error message
+        let parse_error = |e: crate::error::RucolaError| Self {
+            any,
+            tag_match,
+            error: Some(e.to_string()),
+            ..Default::default()
+        };
+
         let (filters, full_text) = filter_string
             .split_once('|')
-            .map(|(filters, rest)| (filters, Some(rest.to_lowercase())))
+            .map(|(filters, rest)| (filters, Some(rest)))
             .unwrap_or((filter_string, None));
 
+        let full_text = match full_text.map(FullTextQuery::parse).transpose() {
+            Ok(full_text) => full_text,
+            Err(e) => return parse_error(e),
+        };
+
         // Go through words
         for word in filters.split_whitespace() {
             if word.starts_with("!#") {
@@ -62,32 +122,55 @@ impl Filter {
             }
             if word.starts_with("!>") {
                 links.push((
-                    super::name_to_id(word.trim_start_matches("!>")).to_string(),
+                    super::link_to_id(word.trim_start_matches("!>")).to_string(),
                     false,
                 ));
                 continue;
             }
             if word.starts_with('>') {
                 links.push((
-                    super::name_to_id(word.trim_start_matches('>')).to_string(),
+                    super::link_to_id(word.trim_start_matches('>')).to_string(),
                     true,
                 ));
                 continue;
             }
             if word.starts_with("!<") {
                 blinks.push((
-                    super::name_to_id(word.trim_start_matches("!<")).to_string(),
+                    super::link_to_id(word.trim_start_matches("!<")).to_string(),
                     false,
                 ));
                 continue;
             }
             if word.starts_with('<') {
                 blinks.push((
-                    super::name_to_id(word.trim_start_matches('<')).to_string(),
+                    super::link_to_id(word.trim_start_matches('<')).to_string(),
                     true,
                 ));
                 continue;
             }
+            let (unnegated, included) = match word.strip_prefix('!') {
+                Some(unnegated) => (unnegated, false),
+                None => (word, true),
+            };
+            if let Some((key, value)) = split_condition(unnegated) {
+                let lowercase_key = key.to_lowercase();
+                if let Some(field) = DateField::from_key(&lowercase_key) {
+                    match DateCondition::parse(field, value, chrono::Local::now()) {
+                        Ok(date) => dates.push((date, included)),
+                        Err(e) => return parse_error(e),
+                    }
+                } else if let Some(field) = CountField::from_key(&lowercase_key) {
+                    match CountCondition::parse(field, key, value) {
+                        Ok(count) => counts.push((count, included)),
+                        Err(e) => return parse_error(e),
+                    }
+                } else if lowercase_key == "in" {
+                    folders.push((super::folder_to_id(value), included));
+                } else {
+                    properties.push((lowercase_key, property_value(value), included));
+                }
+                continue;
+            }
             // if nothing else fits
             title.push_str(word);
         }
@@ -99,44 +182,31 @@ impl Filter {
             tags,
             links,
             blinks,
+            properties,
+            dates,
+            counts,
+            folders,
             title,
             full_text,
+            query: None,
+            error: None,
         }
     }
 
     pub fn apply(&self, note: &super::Note, index: &super::NoteIndex) -> Option<i64> {
+        if let Some(query) = &self.query {
+            return self.evaluate(query, note, index, &note.display_name);
+        }
+
         // === === TAGS === ===
 
         let mut any = false;
         let mut all = true;
         for (tag, included) in self.tags.iter() {
-            if note
-                // go over all tags
-                .tags
-                .iter()
-                // split each tag into..
-                .flat_map(|tag| {
-                    // an iterator of substring starting at 0 and going to every appearance of /
-                    tag.match_indices('/')
-                        .map(|(index, _match)| &tag[0..index])
-                        // and appended just a substring that is the whole tag
-                        .chain(std::iter::once(tag.as_str()))
-                        // flatten this so we have just an iterator over (sub)strs
-                })
-                // check if any of these substring is the searched tag or, in case of a multi-word tag, the tag with appropriate replacements.
-                .any(|subtag|
-                    match self.tag_match {
-                        // tags needs to be matched exactly
-                        TagMatch::Exact => tag.replace('-', " ") == subtag.replace('-', " "),
-                        // it is enough if the typed tag is a prefix of the found tag
-                        TagMatch::Prefix => subtag.replace('-', " ").starts_with(&tag.replace('-', " ")),
-                    }
-                )
             // now compare this to our expectation
-            //  - inclusion: We _want_ one of them to be equal
-            //  - exclusion: We _dont_ want one of them to be equal
-             == *included
-            {
+            //  - inclusion: We _want_ the note to have the tag
+            //  - exclusion: We _dont_ want the note to have the tag
+            if self.has_tag(note, tag) == *included {
                 // this did match our expectation (one of them is equal in case of inclusion or none of them is equal in case of exclusion)
                 // so at least one condition (this one) is true
                 any = true;
@@ -152,7 +222,7 @@ impl Filter {
         // go through all links
         for (link, included) in self.links.iter() {
             // if the links is contained and we want it to be contained or not contained and we want it to be not contained
-            if note.links.contains(link) == *included {
+            if Self::links_to(note, link, index) == *included {
                 // at least one condition (this one) is true
                 any = true;
             } else {
@@ -163,26 +233,59 @@ impl Filter {
 
         // go through all backlinks
         for (blink, included) in self.blinks.iter() {
-            // check if the note with the blink-ID links to the main one passed to this function
-            let exists_and_contains = if let Some(other_note) = index.inner.get(blink) {
-                other_note.links.contains(&super::name_to_id(&note.name))
+            // if the backlink exists and we want that, set any/all as above
+            if Self::linked_from(note, blink, index) == *included {
+                any = true;
             } else {
-                false
-            };
+                all = false;
+            }
+        }
 
-            // if the backlink exists and we want that, set any/all as above
-            if exists_and_contains == *included {
+        // === === PROPERTIES === ===
+
+        // go through all properties
+        for (key, value, included) in self.properties.iter() {
+            if Self::has_property(note, key, value) == *included {
                 any = true;
             } else {
                 all = false;
             }
         }
 
+        // === === DATES === ===
+
+        for (date, included) in self.dates.iter() {
+            if date.matches(note) == *included {
+                any = true;
+            } else {
+                all = false;
+            }
+        }
+
+        // === === STATISTICS === ===
+
+        for (count, included) in self.counts.iter() {
+            if count.matches(note, index) == *included {
+                any = true;
+            } else {
+                all = false;
+            }
+        }
+
+        // === === FOLDERS === ===
+
+        for (folder, included) in self.folders.iter() {
+            if Self::in_folder(note, folder, index) == *included {
+                any = true;
+            } else {
+                all = false;
+            }
+        }
+
+        let mut relevance = 0;
         if let Some(text) = &self.full_text {
-            if std::fs::read_to_string(&note.path)
-                .map(|content| content.to_lowercase().contains(text))
-                .unwrap_or(false)
-            {
+            if let Some(score) = Self::full_text_score(note, text, index) {
+                relevance = score;
                 any = true;
             } else {
                 all = false;
@@ -202,21 +305,290 @@ impl Filter {
             fuzzy_match
         };
         // if all conditions are empty, return match score (only title search)
-        if self.tags.is_empty() && self.links.is_empty() && self.blinks.is_empty() && self.full_text.is_none() && self.title.is_empty()  ||
+        if !self.has_note_conditions() && self.title.is_empty()  ||
             // also return match score if the required amount of conditions are fulfilled
             (!self.any && all || self.any && any)
         {
-            fuz_match.or(Some(0))
+            Some(fuz_match.unwrap_or_default() + relevance)
         } else {
             // else, an exclusion criterion was triggered
             None
         }
     }
+
+    /// Evaluates a boolean query for the given note, fuzzy matching title words with the given text.
+    /// Returns the sum (for `AND`) or the maximum (for `OR`) of the fuzzy match scores of the matched title words and the relevance for full-text queries, or None if the query does not hold.
+    fn evaluate(
+        &self,
+        query: &Query,
+        note: &super::Note,
+        index: &super::NoteIndex,
+        title: &str,
+    ) -> Option<i64> {
+        match query {
+            Query::Tag(tag) => self.has_tag(note, tag).then_some(0),
+            Query::Link(link) => Self::links_to(note, link, index).then_some(0),
+            Query::Backlink(blink) => Self::linked_from(note, blink, index).then_some(0),
+            Query::Property(key, value) => Self::has_property(note, key, value).then_some(0),
+            Query::Date(date) => date.matches(note).then_some(0),
+            Query::Count(count) => count.matches(note, index).then_some(0),
+            Query::Folder(folder) => Self::in_folder(note, folder, index).then_some(0),
+            Query::FullText(text) => Self::full_text_score(note, text, index),
+            Query::Title(words) => {
+                fuzzy_matcher::skim::SkimMatcherV2::default().fuzzy_match(title, words)
+            }
+            Query::Not(query) => self
+                .evaluate(query, note, index, title)
+                .is_none()
+                .then_some(0),
+            Query::And(queries) => queries
+                .iter()
+                .map(|query| self.evaluate(query, note, index, title))
+                .sum(),
+            Query::Or(queries) => queries
+                .iter()
+                .flat_map(|query| self.evaluate(query, note, index, title))
+                .max(),
+        }
+    }
+
+    /// Returns all full-text searches of this filter that can contribute to a match, e.g. to find the matching lines of a note.
+    pub fn full_text_queries(&self) -> Vec<&FullTextQuery> {
+        match &self.query {
+            Some(query) => query.full_text_queries(),
+            None => self.full_text.iter().collect(),
+        }
+    }
+
+    /// Checks if there are any conditions apart from the title words.
+    fn has_note_conditions(&self) -> bool {
+        !(self.tags.is_empty()
+            && self.links.is_empty()
+            && self.blinks.is_empty()
+            && self.properties.is_empty()
+            && self.dates.is_empty()
+            && self.counts.is_empty()
+            && self.folders.is_empty()
+            && self.full_text.is_none())
+    }
+
+    /// Finds the parts of the note matching the conditions of this filter that are not negated, to highlight why the note matched.
+    pub fn highlights(&self, note: &super::Note, index: &super::NoteIndex) -> Highlights {
+        let mut titles = Vec::new();
+        let mut tags = Vec::new();
+        let mut links = Vec::new();
+        let mut blinks = Vec::new();
+        match &self.query {
+            Some(query) => {
+                for condition in query.positive_conditions() {
+                    match condition {
+                        Query::Title(words) => titles.push(words.as_str()),
+                        Query::Tag(tag) => tags.push(tag.as_str()),
+                        Query::Link(link) => links.push(link.as_str()),
+                        Query::Backlink(blink) => blinks.push(blink.as_str()),
+                        _ => {}
+                    }
+                }
+            }
+            None => {
+                fn included(conditions: &[(String, bool)]) -> Vec<&str> {
+                    conditions
+                        .iter()
+                        .filter(|(_, included)| *included)
+                        .map(|(condition, _)| condition.as_str())
+                        .collect()
+                }
+                titles.push(self.title.as_str());
+                tags = included(&self.tags);
+                links = included(&self.links);
+                blinks = included(&self.blinks);
+            }
+        }
+
+        // the characters matched by any of the title words
+        let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+        let mut name = titles
+            .iter()
+            .filter(|words| !words.is_empty())
+            .filter_map(|words| matcher.fuzzy_indices(&note.display_name, words))
+            .flat_map(|(_score, indices)| indices)
+            .collect::<Vec<_>>();
+        name.sort_unstable();
+        name.dedup();
+
+        // links are shown with the name of the note they resolve to
+        let display_name = |link: &str| {
+            index
+                .resolve(link)
+                .and_then(|id| index.get(id))
+                .map(|note| note.display_name.clone())
+                .unwrap_or_else(|| link.to_owned())
+        };
+        let mut conditions = note
+            .tags
+            .iter()
+            .filter(|note_tag| tags.iter().any(|tag| self.tag_matches(note_tag, tag)))
+            .cloned()
+            .chain(
+                links
+                    .iter()
+                    .filter(|link| Self::links_to(note, link, index))
+                    .map(|link| format!(">{}", display_name(link))),
+            )
+            .chain(
+                blinks
+                    .iter()
+                    .filter(|blink| Self::linked_from(note, blink, index))
+                    .map(|blink| format!("<{}", display_name(blink))),
+            )
+            .collect::<Vec<_>>();
+        conditions.dedup();
+
+        Highlights { name, conditions }
+    }
+
+    /// Checks if the note has the given tag, either directly or as the parent of one of its nested tags.
+    fn has_tag(&self, note: &super::Note, tag: &str) -> bool {
+        note.tags
+            .iter()
+            .any(|note_tag| self.tag_matches(note_tag, tag))
+    }
+
+    /// Checks if the given tag of a note is the searched tag or nested within it.
+    fn tag_matches(&self, note_tag: &str, tag: &str) -> bool {
+        // split the tag into an iterator of substring starting at 0 and going to every appearance of /
+        note_tag
+            .match_indices('/')
+            .map(|(index, _match)| &note_tag[0..index])
+            // and appended just a substring that is the whole tag
+            .chain(std::iter::once(note_tag))
+            // check if any of these substring is the searched tag or, in case of a multi-word tag, the tag with appropriate replacements.
+            .any(|subtag| match self.tag_match {
+                // tags needs to be matched exactly
+                TagMatch::Exact => tag.replace('-', " ") == subtag.replace('-', " "),
+                // it is enough if the typed tag is a prefix of the found tag
+                TagMatch::Prefix => subtag.replace('-', " ").starts_with(&tag.replace('-', " ")),
+            })
+    }
+
+    /// Checks if the note links to the note with the given id, either directly, by a relative path or through an alias.
+    fn links_to(note: &super::Note, link: &str, index: &super::NoteIndex) -> bool {
+        // resolve the searched link through aliases, if possible
+        let target = index.resolve(link).unwrap_or(link);
+        // the id of the note is needed to resolve its relative links
+        let id = index.path_to_id(&note.path);
+        note.links
+            .iter()
+            .any(|other| other == link || index.resolve_from(&id, other) == Some(target))
+    }
+
+    /// Checks if the note with the given id links to the note.
+    fn linked_from(note: &super::Note, blink: &str, index: &super::NoteIndex) -> bool {
+        index.resolve(blink).is_some_and(|other_id| {
+            index
+                .outlinks(other_id)
+                .contains(&index.path_to_id(&note.path))
+        })
+    }
+
+    /// Checks if the note has a property of this key with a matching value (or any value, if none was specified).
+    fn has_property(note: &super::Note, key: &str, value: &str) -> bool {
+        note.properties.iter().any(|(other_key, property)| {
+            other_key.to_lowercase() == *key && (value.is_empty() || property.matches(value))
+        })
+    }
+
+    /// Checks if the note lies in the folder with the given id or one of its subfolders.
+    fn in_folder(note: &super::Note, folder: &str, index: &super::NoteIndex) -> bool {
+        folder.is_empty()
+            || index
+                .path_to_id(&note.path)
+                .strip_prefix(folder)
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+
+    /// Checks if the note matches the full-text query and returns its relevance, scaled to be comparable to fuzzy match scores.
+    fn full_text_score(
+        note: &super::Note,
+        full_text: &FullTextQuery,
+        index: &super::NoteIndex,
+    ) -> Option<i64> {
+        let id = index.path_to_id(&note.path);
+        full_text
+            .matches(&id, &note.path, &index.text)
+            .then(|| (full_text.relevance(&id, &index.text) * RELEVANCE_SCALE).round() as i64)
+    }
+
+    /// Applies this filter to a single task of the given note.
+    /// All conditions are checked against the note, except for the title words, which are fuzzy matched with the task text instead.
+    pub fn apply_task(
+        &self,
+        task: &super::Task,
+        note: &super::Note,
+        index: &super::NoteIndex,
+    ) -> Option<i64> {
+        // title words of a boolean query are simply matched with the task text
+        if let Some(query) = &self.query {
+            return self.evaluate(query, note, index, &task.text);
+        }
+
+        // only the title words behave differently, so fall back to the normal filter if there are none
+        if self.title.is_empty() {
+            return self.apply(note, index);
+        }
+
+        // check all other conditions on the note
+        let note_filter = Self {
+            title: String::new(),
+            ..self.clone()
+        };
+        let has_note_conditions = note_filter.has_note_conditions();
+        let note_match = note_filter.apply(note, index).is_some();
+
+        // fuzzy match the title words with the task text
+        let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+        let fuzzy_match = matcher.fuzzy_match(&task.text, &self.title);
+
+        // combine both results according to the any/all mode
+        let matches = if self.any && has_note_conditions {
+            note_match || fuzzy_match.is_some()
+        } else {
+            note_match && fuzzy_match.is_some()
+        };
+
+        matches.then(|| fuzzy_match.unwrap_or_default())
+    }
+}
+
+/// Splits a condition like `status:draft` or `created:>2024` into its key and value.
+/// Returns `None` unless the key is a word like `status` or `word_count` - a letter or `_` followed by letters, digits, `_` or `-` - and the value is neither empty nor starts with `//`.
+/// That way, words like `Note:`, `C:` or `https://example.com` remain title words.
+pub(super) fn split_condition(word: &str) -> Option<(&str, &str)> {
+    let (key, value) = word.split_once(':')?;
+
+    let mut chars = key.chars();
+    let is_key = chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+
+    (is_key && !value.is_empty() && !value.starts_with("//")).then_some((key, value))
 }
+
+/// Turns the value of a property condition into the value to match, where `*` matches any value and becomes the empty string.
+pub(super) fn property_value(value: &str) -> String {
+    if value == "*" {
+        String::new()
+    } else {
+        value.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{data, io};
+    use itertools::Itertools;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -232,9 +604,9 @@ mod tests {
 
         assert_eq!(index.inner.len(), 13);
 
-        let linux = index.inner.get("linux").unwrap();
-        let win = index.inner.get("windows").unwrap();
-        let osx = index.inner.get("osx").unwrap();
+        let linux = index.get("linux").unwrap();
+        let win = index.get("windows").unwrap();
+        let osx = index.get("osx").unwrap();
 
         // === Filter 1 ===
 
@@ -246,6 +618,7 @@ mod tests {
             blinks: vec![],
             title: String::new(),
             full_text: None,
+            ..Default::default()
         };
 
         assert!(filter1.apply(linux, &index).is_some());
@@ -266,14 +639,14 @@ mod tests {
 
         assert_eq!(index.inner.len(), 13);
 
-        let linux = index.inner.get("linux").unwrap();
-        let win = index.inner.get("windows").unwrap();
-        let osx = index.inner.get("osx").unwrap();
-        let topology = index.inner.get("topology").unwrap();
-        let atlas = index.inner.get("atlas").unwrap();
-        let chart = index.inner.get("chart").unwrap();
-        let manifold = index.inner.get("manifold").unwrap();
-        let smooth_map = index.inner.get("smooth-map").unwrap();
+        let linux = index.get("linux").unwrap();
+        let win = index.get("windows").unwrap();
+        let osx = index.get("osx").unwrap();
+        let topology = index.get("topology").unwrap();
+        let atlas = index.get("atlas").unwrap();
+        let chart = index.get("chart").unwrap();
+        let manifold = index.get("manifold").unwrap();
+        let smooth_map = index.get("smooth-map").unwrap();
 
         // === Filter 1 ===
 
@@ -285,6 +658,7 @@ mod tests {
             blinks: vec![],
             title: String::new(),
             full_text: None,
+            ..Default::default()
         };
 
         assert!(filter1.apply(linux, &index).is_none());
@@ -306,6 +680,7 @@ mod tests {
             blinks: vec![],
             title: String::new(),
             full_text: None,
+            ..Default::default()
         };
 
         assert!(filter2.apply(linux, &index).is_none());
@@ -331,14 +706,14 @@ mod tests {
 
         assert_eq!(index.inner.len(), 13);
 
-        let linux = index.inner.get("linux").unwrap();
-        let win = index.inner.get("windows").unwrap();
-        let osx = index.inner.get("osx").unwrap();
-        let topology = index.inner.get("topology").unwrap();
-        let atlas = index.inner.get("atlas").unwrap();
-        let chart = index.inner.get("chart").unwrap();
-        let manifold = index.inner.get("manifold").unwrap();
-        let smooth_map = index.inner.get("smooth-map").unwrap();
+        let linux = index.get("linux").unwrap();
+        let win = index.get("windows").unwrap();
+        let osx = index.get("osx").unwrap();
+        let topology = index.get("topology").unwrap();
+        let atlas = index.get("atlas").unwrap();
+        let chart = index.get("chart").unwrap();
+        let manifold = index.get("manifold").unwrap();
+        let smooth_map = index.get("smooth-map").unwrap();
 
         // === Filter 1 ===
 
@@ -350,6 +725,7 @@ mod tests {
             blinks: vec![],
             title: String::new(),
             full_text: None,
+            ..Default::default()
         };
 
         assert!(filter1.apply(linux, &index).is_some());
@@ -371,6 +747,7 @@ mod tests {
             blinks: vec![],
             title: String::new(),
             full_text: None,
+            ..Default::default()
         };
 
         assert!(filter2.apply(linux, &index).is_none());
@@ -417,11 +794,11 @@ mod tests {
         );
         assert_eq!(filter2.title, "");
 
-        let liegroup = index.inner.get("lie-group").unwrap();
-        let chart = index.inner.get("chart").unwrap();
-        let manifold = index.inner.get("manifold").unwrap();
-        let smoothmap = index.inner.get("smooth-map").unwrap();
-        let topology = index.inner.get("topology").unwrap();
+        let liegroup = index.get("lie-group").unwrap();
+        let chart = index.get("chart").unwrap();
+        let manifold = index.get("manifold").unwrap();
+        let smoothmap = index.get("smooth-map").unwrap();
+        let topology = index.get("topology").unwrap();
 
         assert!(filter2.apply(liegroup, &index).is_none());
         assert!(filter2.apply(chart, &index).is_some());
@@ -456,7 +833,10 @@ mod tests {
         );
         assert_eq!(filter3.title, "");
 
-        assert_eq!(filter3.full_text, Some(String::from("equivalent")));
+        assert_eq!(
+            filter3.full_text.map(|full_text| full_text.to_string()),
+            Some(String::from("equivalent"))
+        );
     }
 
     #[test]
@@ -496,10 +876,404 @@ mod tests {
 
         assert_eq!(filter2.title, "");
 
-        let yamlformat = index.inner.get("note25").unwrap();
-        let chart = index.inner.get("chart").unwrap();
+        let yamlformat = index.get("note25").unwrap();
+        let chart = index.get("chart").unwrap();
 
         assert!(filter2.apply(yamlformat, &index).is_some());
         assert!(filter2.apply(chart, &index).is_none());
     }
+
+    #[test]
+    fn test_filter_properties() {
+        let config = crate::Config {
+            vault_path: Some(std::env::current_dir().unwrap().join("tests")),
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let index = data::NoteIndex::new(tracker, builder, &config).0;
+
+        let yamlformat = index.get("note25").unwrap();
+        let chart = index.get("chart").unwrap();
+
+        let filter1 = Filter::new("status:Draft !published:true", false, TagMatch::Exact);
+
+        assert_eq!(
+            filter1.properties,
+            vec![
+                ("status".to_string(), "Draft".to_string(), true),
+                ("published".to_string(), "true".to_string(), false),
+            ]
+        );
+        assert_eq!(filter1.title, "");
+
+        assert!(filter1.apply(yamlformat, &index).is_some());
+        assert!(filter1.apply(chart, &index).is_none());
+
+        let filter2 = Filter::new("authors:grace-hopper", false, TagMatch::Exact);
+        assert!(filter2.apply(yamlformat, &index).is_some());
+
+        let filter3 = Filter::new("!status:*", false, TagMatch::Exact);
+        assert!(filter3.apply(yamlformat, &index).is_none());
+        assert!(filter3.apply(chart, &index).is_some());
+
+        // only words with a key and a value are conditions, others are title words
+        for word in [
+            "Note:",
+            "C:",
+            "https://example.com",
+            ":draft",
+            "1st:draft",
+            "status:",
+        ] {
+            let filter = Filter::new(word, false, TagMatch::Exact);
+            assert!(filter.properties.is_empty(), "{word}");
+            assert_eq!(filter.error, None, "{word}");
+            assert_eq!(filter.title, word);
+        }
+        assert_eq!(
+            Filter::new("due_date:2024 !my-key:x", false, TagMatch::Exact).properties,
+            vec![
+                ("due_date".to_string(), "2024".to_string(), true),
+                ("my-key".to_string(), "x".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_dates() {
+        let tmp = testdir::testdir!();
+        std::fs::write(tmp.join("Old.md"), "---\ncreated: 2020-05-01\n---\n# Old\n").unwrap();
+        std::fs::write(tmp.join("New.md"), "---\ndate: 2025-03-14\n---\n# New\n").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(tmp.join("Old.md"))
+            .unwrap()
+            .set_modified(
+                std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_600_000_000),
+            )
+            .unwrap();
+
+        let config = crate::Config {
+            vault_path: Some(tmp),
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let index = data::NoteIndex::new(tracker, builder, &config).0;
+
+        let matches = |filter: &str| {
+            let filter = Filter::new(filter, false, TagMatch::Exact);
+            assert_eq!(filter.error, None);
+            index
+                .inner
+                .iter()
+                .filter(|(_id, note)| filter.apply(note, &index).is_some())
+                .map(|(id, _note)| id.as_str())
+                .sorted()
+                .collect_vec()
+        };
+
+        assert_eq!(matches("created:2020"), vec!["old"]);
+        assert_eq!(matches("created:>2020-05-01"), vec!["new"]);
+        assert_eq!(matches("modified:<7d"), vec!["new"]);
+        assert_eq!(matches("!modified:<7d"), vec!["old"]);
+        assert_eq!(matches("modified:2020-09 created:<2021"), vec!["old"]);
+        assert_eq!(matches("created:2020 OR Modified:<1d"), vec!["new", "old"]);
+        // without a value, the word is matched with the title
+        assert_eq!(matches("created:"), Vec::<String>::new());
+
+        assert_eq!(
+            Filter::new("old modified:>", false, TagMatch::Exact).error,
+            Some("Invalid filter: Expected a date after '>'.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_filter_statistics() {
+        let tmp = testdir::testdir!();
+        std::fs::create_dir_all(tmp.join("math/algebra")).unwrap();
+        std::fs::create_dir_all(tmp.join("mathematics")).unwrap();
+        std::fs::write(tmp.join("math/Group.md"), "See [[Ring]] and [[Missing]].").unwrap();
+        std::fs::write(tmp.join("math/algebra/Ring.md"), "A ring.").unwrap();
+        std::fs::write(tmp.join("mathematics/Field.md"), "See [[Group]].").unwrap();
+        std::fs::write(tmp.join("Orphan.md"), "word ".repeat(600)).unwrap();
+
+        let config = crate::Config {
+            vault_path: Some(tmp),
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let index = data::NoteIndex::new(tracker, builder, &config).0;
+
+        let matches = |filter: &str| {
+            let filter = Filter::new(filter, false, TagMatch::Exact);
+            assert_eq!(filter.error, None);
+            index
+                .inner
+                .iter()
+                .filter(|(_id, note)| filter.apply(note, &index).is_some())
+                .map(|(id, _note)| id.as_str())
+                .sorted()
+                .collect_vec()
+        };
+
+        assert_eq!(matches("words:>500"), vec!["orphan"]);
+        assert_eq!(matches("chars:<10"), vec!["math/algebra/ring"]);
+        assert_eq!(matches("broken:>0"), vec!["math/group"]);
+        assert_eq!(matches("inlinks:0"), vec!["mathematics/field", "orphan"]);
+        assert_eq!(matches("outlinks:0"), vec!["math/algebra/ring", "orphan"]);
+        assert_eq!(matches("in:math/"), vec!["math/algebra/ring", "math/group"]);
+        assert_eq!(matches("in:Math/Algebra"), vec!["math/algebra/ring"]);
+        assert_eq!(
+            matches("!in:math inlinks:0 outlinks:>=1"),
+            vec!["mathematics/field"]
+        );
+        assert_eq!(matches("in:math AND NOT outlinks:0"), vec!["math/group"]);
+
+        assert_eq!(
+            Filter::new("words:many", false, TagMatch::Exact).error,
+            Some(
+                "Invalid filter: Expected a number in 'words:many', e.g. words:>10 or words:0."
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_filter_query() {
+        let tmp = testdir::testdir!();
+        std::fs::write(tmp.join("Linux.md"), "#os #unix\n\nA free kernel.").unwrap();
+        std::fs::write(tmp.join("Windows.md"), "#os\n\nSee [[Linux]].").unwrap();
+        std::fs::write(tmp.join("BSD.md"), "#unix\n\nLike [[Linux]], a kernel.").unwrap();
+        std::fs::write(tmp.join("Rust.md"), "#language\n\nUsed in [[Linux]].").unwrap();
+
+        let config = crate::Config {
+            vault_path: Some(tmp),
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let index = data::NoteIndex::new(tracker, builder, &config).0;
+
+        let matches = |filter: &str| {
+            let filter = Filter::new(filter, false, TagMatch::Exact);
+            assert_eq!(filter.error, None);
+            index
+                .inner
+                .iter()
+                .filter(|(_id, note)| filter.apply(note, &index).is_some())
+                .map(|(id, _note)| id.as_str())
+                .sorted()
+                .collect_vec()
+        };
+
+        assert_eq!(matches("(#os OR #unix) AND NOT >linux"), vec!["linux"]);
+        assert_eq!(
+            matches("#os OR #unix NOT #os"),
+            vec!["bsd", "linux", "windows"]
+        );
+        assert_eq!(matches("NOT (#os OR #unix)"), vec!["rust"]);
+        assert_eq!(
+            matches("(|kernel AND !#os) OR <windows"),
+            vec!["bsd", "linux"]
+        );
+        assert_eq!(
+            matches("(rust OR wind) AND >linux"),
+            vec!["rust", "windows"]
+        );
+
+        // the score of matched title words is kept
+        let filter = Filter::new("(linux OR #language) AND #unix", false, TagMatch::Exact);
+        assert!(filter.apply(index.get("linux").unwrap(), &index).unwrap() > 0);
+
+        // parse errors are reported and match everything
+        let filter = Filter::new("(#os OR", false, TagMatch::Exact);
+        assert_eq!(
+            filter.error,
+            Some("Invalid filter: Expected a condition after 'OR'.".to_string())
+        );
+        assert!(filter.apply(index.get("rust").unwrap(), &index).is_some());
+
+        // phrases and regular expressions work with and without operators
+        assert_eq!(matches(r#"#unix |"a kernel""#), vec!["bsd"]);
+        assert_eq!(
+            matches(r#"|"free KERNEL" OR |/^See \[\[/c"#),
+            vec!["linux", "windows"]
+        );
+        assert_eq!(
+            Filter::new(r#"#os |"kernel"#, false, TagMatch::Exact).error,
+            Some("Invalid filter: Missing closing '\"'.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_filter_highlights() {
+        let tmp = testdir::testdir!();
+        std::fs::write(tmp.join("Linux.md"), "#os #unix\n\nA free kernel.").unwrap();
+        std::fs::write(tmp.join("Windows.md"), "#os/closed\n\nSee [[Linux]].").unwrap();
+        std::fs::write(tmp.join("BSD.md"), "#unix\n\nLike [[Linux]], a kernel.").unwrap();
+
+        let config = crate::Config {
+            vault_path: Some(tmp),
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let index = data::NoteIndex::new(tracker, builder, &config).0;
+
+        let highlights = |filter: &str, id: &str| {
+            Filter::new(filter, true, TagMatch::Exact).highlights(index.get(id).unwrap(), &index)
+        };
+
+        assert_eq!(highlights("lnx", "linux").name, vec![0, 2, 4]);
+        assert_eq!(highlights("#os", "linux").name, Vec::<usize>::new());
+        assert_eq!(
+            highlights("#os #unix", "linux").conditions,
+            vec!["#os", "#unix"]
+        );
+        assert_eq!(highlights("#os", "windows").conditions, vec!["#os/closed"]);
+        assert_eq!(highlights(">linux", "bsd").conditions, vec![">Linux"]);
+        assert_eq!(highlights("<bsd", "linux").conditions, vec!["<BSD"]);
+
+        // negated conditions are not highlighted
+        assert_eq!(
+            highlights("!#unix win", "windows"),
+            Highlights {
+                name: vec![0, 1, 2],
+                conditions: Vec::new(),
+            }
+        );
+        assert_eq!(
+            highlights("(bsd OR #os) AND NOT #unix", "bsd"),
+            Highlights {
+                name: vec![0, 1, 2],
+                conditions: Vec::new(),
+            }
+        );
+        assert_eq!(
+            highlights("#unix AND (>linux OR NOT #os)", "bsd").conditions,
+            vec!["#unix", ">Linux"]
+        );
+    }
+
+    #[test]
+    fn test_filter_relevance() {
+        let tmp = testdir::testdir!();
+        std::fs::write(
+            tmp.join("Kernels.md"),
+            "A kernel schedules processes. Every kernel differs.",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.join("Linux.md"),
+            "Linux is a free operating system with a monolithic kernel, used on servers, phones and desktops alike.",
+        )
+        .unwrap();
+        std::fs::write(tmp.join("Shells.md"), "A shell talks to the kernel.").unwrap();
+        std::fs::write(tmp.join("Editors.md"), "Editors edit text.").unwrap();
+
+        let config = crate::Config {
+            vault_path: Some(tmp),
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let index = data::NoteIndex::new(tracker, builder, &config).0;
+
+        let ranking = |filter: &str| {
+            let filter = Filter::new(filter, false, TagMatch::Exact);
+            assert_eq!(filter.error, None);
+            index
+                .inner
+                .iter()
+                .filter_map(|(id, note)| Some((id.as_str(), filter.apply(note, &index)?)))
+                .sorted_by_key(|(id, score)| (-score, *id))
+                .map(|(id, _score)| id)
+                .collect_vec()
+        };
+
+        // more occurrences and shorter notes rank higher
+        assert_eq!(ranking("|kernel"), vec!["kernels", "shells", "linux"]);
+        assert_eq!(ranking(r#"|"kernel""#), vec!["kernels", "shells", "linux"]);
+        assert_eq!(
+            ranking("|kernel OR #none"),
+            vec!["kernels", "shells", "linux"]
+        );
+
+        // the relevance is added to the fuzzy match score of the title
+        let filter = Filter::new("linux |kernel", false, TagMatch::Exact);
+        let title_only = Filter::new("linux", false, TagMatch::Exact);
+        let linux = index.get("linux").unwrap();
+        assert!(filter.apply(linux, &index) > title_only.apply(linux, &index));
+        assert_eq!(ranking("linux |kernel"), vec!["linux"]);
+    }
+
+    #[test]
+    fn test_filter_tasks() {
+        let tmp = testdir::testdir!();
+        std::fs::write(
+            tmp.join("Chores.md"),
+            "#home\n\n- [ ] Clean kitchen\n- [ ] Water plants\n",
+        )
+        .unwrap();
+        std::fs::write(tmp.join("Work.md"), "#job\n\n- [ ] Clean desk\n").unwrap();
+
+        let config = crate::Config {
+            vault_path: Some(tmp),
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let index = data::NoteIndex::new(tracker, builder, &config).0;
+
+        let chores = index.get("chores").unwrap();
+        let work = index.get("work").unwrap();
+
+        let filter = Filter::new("#home clean", false, TagMatch::Exact);
+
+        assert!(filter
+            .apply_task(&chores.tasks[0], chores, &index)
+            .is_some());
+        assert!(filter
+            .apply_task(&chores.tasks[1], chores, &index)
+            .is_none());
+        assert!(filter.apply_task(&work.tasks[0], work, &index).is_none());
+
+        let filter = Filter::new("#home clean", true, TagMatch::Exact);
+
+        assert!(filter
+            .apply_task(&chores.tasks[0], chores, &index)
+            .is_some());
+        assert!(filter
+            .apply_task(&chores.tasks[1], chores, &index)
+            .is_some());
+        assert!(filter.apply_task(&work.tasks[0], work, &index).is_some());
+
+        // title words of boolean queries are matched with the task text as well
+        let filter = Filter::new("#job OR (#home AND water)", false, TagMatch::Exact);
+
+        assert!(filter
+            .apply_task(&chores.tasks[0], chores, &index)
+            .is_none());
+        assert!(filter
+            .apply_task(&chores.tasks[1], chores, &index)
+            .is_some());
+        assert!(filter.apply_task(&work.tasks[0], work, &index).is_some());
+
+        let filter = Filter::new("!#job", false, TagMatch::Exact);
+
+        assert!(filter
+            .apply_task(&chores.tasks[1], chores, &index)
+            .is_some());
+        assert!(filter.apply_task(&work.tasks[0], work, &index).is_none());
+    }
 }
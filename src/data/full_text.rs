@@ -0,0 +1,490 @@
+use crate::error;
+
+use super::TextIndex;
+
+/// A search in the full text of notes, as typed after `|` in the filter area.
+/// Consists of
+///  - plain words, each of which has to be the beginning of a word in the note,
+///  - exact phrases in double quotes, e.g. `"lie group"`,
+///  - regular expressions between slashes, e.g. `/TODO\(\w+\)/`.
+///
+/// Phrases and regular expressions are case-insensitive, unless followed by the flag `c`. In regular expressions, `^` and `$` match at the start and end of every line.
+/// The flag `w` makes them only match whole words, e.g. `"group"w` does not match `groups`.
+#[derive(Debug, Clone)]
+pub struct FullTextQuery {
+    /// The query as typed by the user.
+    source: String,
+    /// The plain words of the query, separated by spaces. These are looked up in the full-text index.
+    words: String,
+    /// The phrases and regular expressions of the query, which are matched with the content of the note.
+    patterns: Vec<regex::Regex>,
+    /// The phrases of the query and whether they have to match whole words, which are looked up in the full-text index before matching the content.
+    phrases: Vec<(String, bool)>,
+    /// The terms of the plain words and phrases, which determine the relevance of a matching note.
+    terms: Vec<String>,
+    /// What was looked up in the full-text index for this query, reused until the index changes.
+    lookup: std::cell::RefCell<Option<IndexLookup>>,
+}
+
+/// What a full-text query looked up in a version of the full-text index, so matching and scoring a note does not search the index again.
+#[derive(Debug, Clone)]
+struct IndexLookup {
+    /// The version of the full-text index this was looked up in.
+    version: u64,
+    /// The ids of the notes containing all plain words and the terms of all phrases, or `None` if there are neither.
+    candidates: Option<std::collections::HashSet<String>>,
+    /// The terms together with their inverse document frequency.
+    idf: Vec<(String, f64)>,
+}
+
+impl PartialEq for FullTextQuery {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for FullTextQuery {}
+
+impl std::fmt::Display for FullTextQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+impl FullTextQuery {
+    /// Parses a full-text query, returning an error for unterminated phrases or regular expressions, unknown flags and invalid regular expressions.
+    pub fn parse(query: &str) -> error::Result<Self> {
+        let mut words = Vec::new();
+        let mut patterns = Vec::new();
+        let mut phrases = Vec::new();
+        let mut terms = Vec::new();
+
+        let mut rest = query.trim_start();
+        while !rest.is_empty() {
+            let delimiter = rest.chars().next().unwrap_or_default();
+
+            // plain words end at the next whitespace
+            if delimiter != '"' && delimiter != '/' {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                words.push(&rest[..end]);
+                rest = rest[end..].trim_start();
+                continue;
+            }
+
+            // phrases and regular expressions end at the next unescaped delimiter
+            let end = section_end(rest).ok_or_else(|| {
+                error::RucolaError::FilterParseError(format!("Missing closing '{delimiter}'."))
+            })?;
+            let content = &rest[1..end - 1];
+
+            // followed by their flags
+            let flags_end = rest[end..]
+                .find(char::is_whitespace)
+                .map(|position| end + position)
+                .unwrap_or(rest.len());
+            let flags = &rest[end..flags_end];
+            if let Some(flag) = flags.chars().find(|flag| !matches!(flag, 'c' | 'w')) {
+                return Err(error::RucolaError::FilterParseError(format!(
+                    "Unknown flag '{flag}', use 'c' for case-sensitive or 'w' for whole words."
+                )));
+            }
+
+            let pattern = if delimiter == '"' {
+                terms.extend(TextIndex::tokenize(content));
+                phrases.push((content.to_string(), flags.contains('w')));
+                regex::escape(content)
+            } else {
+                content.to_string()
+            };
+            let pattern = if flags.contains('w') {
+                format!(r"\b(?:{pattern})\b")
+            } else {
+                pattern
+            };
+            patterns.push(
+                regex::RegexBuilder::new(&pattern)
+                    .case_insensitive(!flags.contains('c'))
+                    .multi_line(true)
+                    .build()
+                    .map_err(|e| {
+                        error::RucolaError::FilterParseError(format!(
+                            "Invalid regular expression: {}",
+                            e.to_string().lines().last().unwrap_or_default()
+                        ))
+                    })?,
+            );
+
+            rest = rest[flags_end..].trim_start();
+        }
+
+        let words = words.join(" ");
+        terms.extend(TextIndex::tokenize(&words));
+
+        Ok(Self {
+            source: query.to_string(),
+            words,
+            patterns,
+            phrases,
+            terms,
+            lookup: std::cell::RefCell::new(None),
+        })
+    }
+
+    /// Checks if the note with the given id and path matches this query.
+    /// Plain words and the terms of phrases are looked up in the full-text index first, so only notes containing all of them are read from disk to match phrases and regular expressions.
+    /// Only queries made of nothing but regular expressions have to read all notes.
+    pub fn matches(&self, id: &str, path: &std::path::Path, index: &TextIndex) -> bool {
+        self.is_candidate(id, index)
+            && (self.patterns.is_empty()
+                || std::fs::read_to_string(path).is_ok_and(|content| {
+                    self.patterns
+                        .iter()
+                        .all(|pattern| pattern.is_match(&content))
+                }))
+    }
+
+    /// Looks up the notes containing all plain words and phrases and the inverse document frequencies of all terms in the given index.
+    /// The results are reused until the index changes, so they are computed once per filter pass over all notes.
+    fn lookup(&self, index: &TextIndex) -> std::cell::Ref<'_, IndexLookup> {
+        if self
+            .lookup
+            .borrow()
+            .as_ref()
+            .is_none_or(|lookup| lookup.version != index.version())
+        {
+            *self.lookup.borrow_mut() =
+                Some(IndexLookup {
+                    version: index.version(),
+                    candidates: std::iter::once(index.candidates(&self.words))
+                        .chain(self.phrases.iter().map(|(phrase, whole_words)| {
+                            index.phrase_candidates(phrase, *whole_words)
+                        }))
+                        .flatten()
+                        .reduce(|candidates, others| {
+                            candidates.intersection(&others).cloned().collect()
+                        }),
+                    idf: self
+                        .terms
+                        .iter()
+                        .map(|term| (term.clone(), index.idf(term)))
+                        .collect(),
+                });
+        }
+        std::cell::Ref::map(self.lookup.borrow(), |lookup| {
+            lookup.as_ref().expect("The lookup was just made.")
+        })
+    }
+
+    /// Checks if the note with the given id contains all plain words and the terms of all phrases of this query, so it can match.
+    fn is_candidate(&self, id: &str, index: &TextIndex) -> bool {
+        self.lookup(index)
+            .candidates
+            .as_ref()
+            .is_none_or(|candidates| candidates.contains(id))
+    }
+
+    /// Scores how relevant the note with the given id is for the plain words and phrases of this query, see [`TextIndex::bm25`].
+    /// The inverse document frequencies are computed again whenever the index changes.
+    pub fn relevance(&self, id: &str, index: &TextIndex) -> f64 {
+        index.bm25(id, &self.lookup(index).idf)
+    }
+}
+
+/// A line of a note containing parts of full-text queries, as shown in the list of full-text results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextMatch {
+    /// The number of the line within the note file, beginning at 1.
+    pub line: usize,
+    /// The content of the line.
+    pub text: String,
+    /// The sorted, non-overlapping byte ranges of the parts of the line matching the queries.
+    pub ranges: Vec<std::ops::Range<usize>>,
+}
+
+impl TextMatch {
+    /// Finds all lines of the given content containing a word, phrase or regular expression of any of the given queries.
+    /// Words of the queries match every word of the line they are the beginning of. Phrases and regular expressions are only found within single lines.
+    pub fn find_all(queries: &[&FullTextQuery], content: &str) -> Vec<Self> {
+        let prefixes = queries
+            .iter()
+            .flat_map(|query| TextIndex::tokenize(&query.words))
+            .collect::<Vec<_>>();
+
+        content
+            .lines()
+            .enumerate()
+            .filter_map(|(number, line)| {
+                // all words of the line that start with one of the words of the queries
+                let mut ranges = line
+                    .split(|c: char| !c.is_alphanumeric())
+                    .filter(|word| !word.is_empty())
+                    .filter(|word| {
+                        let word = word.to_lowercase();
+                        prefixes.iter().any(|prefix| word.starts_with(prefix))
+                    })
+                    .map(|word| {
+                        let start = word.as_ptr() as usize - line.as_ptr() as usize;
+                        start..start + word.len()
+                    })
+                    // all matches of phrases and regular expressions
+                    .chain(queries.iter().flat_map(|query| {
+                        query
+                            .patterns
+                            .iter()
+                            .flat_map(|pattern| pattern.find_iter(line).map(|m| m.range()))
+                    }))
+                    .filter(|range| !range.is_empty())
+                    .collect::<Vec<_>>();
+
+                if ranges.is_empty() {
+                    return None;
+                }
+
+                // merge overlapping ranges
+                ranges.sort_by_key(|range| range.start);
+                let mut merged: Vec<std::ops::Range<usize>> = Vec::new();
+                for range in ranges {
+                    match merged.last_mut() {
+                        Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                        _ => merged.push(range),
+                    }
+                }
+
+                Some(Self {
+                    line: number + 1,
+                    text: line.to_owned(),
+                    ranges: merged,
+                })
+            })
+            .collect()
+    }
+
+    /// Splits the line into parts that are highlighted or not, starting at most `context` characters before the first highlighted part.
+    /// If the beginning of the line is left out, the first part starts with `…`.
+    pub fn snippet(&self, context: usize) -> Vec<(String, bool)> {
+        let first = self
+            .ranges
+            .first()
+            .map(|range| range.start)
+            .unwrap_or_default();
+        let before = &self.text[..first];
+        let start = before
+            .char_indices()
+            .nth(before.chars().count().saturating_sub(context))
+            .map(|(position, _)| position)
+            .unwrap_or(first);
+
+        let mut parts = Vec::new();
+        let mut position = start;
+        for range in &self.ranges {
+            if range.start > position {
+                parts.push((self.text[position..range.start].to_owned(), false));
+            }
+            parts.push((self.text[range.clone()].to_owned(), true));
+            position = range.end;
+        }
+        if position < self.text.len() {
+            parts.push((self.text[position..].to_owned(), false));
+        }
+
+        if start > 0 {
+            match parts.first_mut() {
+                Some((text, false)) => text.insert(0, '…'),
+                _ => parts.insert(0, ("…".to_owned(), false)),
+            }
+        }
+
+        parts
+    }
+}
+
+/// Returns the position after the closing delimiter of a phrase or regular expression at the start of the given string, if there is one.
+/// The delimiter is the first character. Within regular expressions, delimiters can be escaped by a backslash.
+pub(super) fn section_end(section: &str) -> Option<usize> {
+    let delimiter = section.chars().next()?;
+    let mut escaped = false;
+    for (position, c) in section.char_indices().skip(1) {
+        if c == delimiter && !escaped {
+            return Some(position + c.len_utf8());
+        }
+        escaped = delimiter == '/' && c == '\\' && !escaped;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_section_end() {
+        assert_eq!(section_end(r#""lie group"w rest"#), Some(11));
+        assert_eq!(section_end(r"/a\/b/ rest"), Some(6));
+        assert_eq!(section_end(r#""a\"b""#), Some(4));
+        assert_eq!(section_end(r"/unterminated"), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        let query = FullTextQuery::parse(r#"Lie-group "Exact Phrase"cw /TODO\(\w+\)/c"#).unwrap();
+
+        assert_eq!(query.words, "Lie-group");
+        assert_eq!(
+            query
+                .patterns
+                .iter()
+                .map(|pattern| pattern.as_str())
+                .collect::<Vec<_>>(),
+            vec![r"\b(?:Exact Phrase)\b", r"TODO\(\w+\)"]
+        );
+        assert_eq!(
+            query.to_string(),
+            r#"Lie-group "Exact Phrase"cw /TODO\(\w+\)/c"#
+        );
+
+        for (query, message) in [
+            (r#""lie group"#, "Missing closing '\"'."),
+            (r"/TODO\/", "Missing closing '/'."),
+            (
+                r#""lie"x"#,
+                "Unknown flag 'x', use 'c' for case-sensitive or 'w' for whole words.",
+            ),
+            (
+                r"/TODO(/",
+                "Invalid regular expression: error: unclosed group",
+            ),
+        ] {
+            assert_eq!(
+                FullTextQuery::parse(query).unwrap_err().to_string(),
+                format!("Invalid filter: {message}"),
+                "{query}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let tmp = testdir::testdir!();
+        let path = tmp.join("Note.md");
+        std::fs::write(&path, "A Lie group acts on itself.\nTODO(alice): Groups\n").unwrap();
+
+        let mut index = TextIndex::default();
+        index.insert("note", &std::fs::read_to_string(&path).unwrap());
+
+        let matches = |query: &str| {
+            FullTextQuery::parse(query)
+                .unwrap()
+                .matches("note", &path, &index)
+        };
+
+        assert!(matches("lie gro"));
+        assert!(!matches("lie algebra"));
+        assert!(matches(r#""lie group acts""#));
+        assert!(!matches(r#""lie group acts"c"#));
+        assert!(matches(r#""Lie group"c"#));
+        assert!(matches(r#""group"w"#));
+        assert!(!matches(r#""grou"w"#));
+        assert!(matches(r"/TODO\(\w+\)/c"));
+        assert!(!matches(r"/todo\(\d+\)/"));
+        assert!(matches(r#"acts "itself" /^todo/"#));
+        assert!(!matches(r#"algebra "itself""#));
+
+        // phrases are only searched in notes containing their terms, without reading the others
+        let mut other = TextIndex::default();
+        other.insert("note", "Nothing about groups.");
+        let phrase = FullTextQuery::parse(r#""lie group""#).unwrap();
+        assert!(!phrase.matches("note", &path, &other));
+        assert!(!phrase.matches("note", &tmp.join("Missing.md"), &index));
+        assert!(phrase.matches("note", &path, &index));
+
+        // the notes containing the words are looked up again when the index changes
+        let query = FullTextQuery::parse("algebra").unwrap();
+        assert!(!query.matches("note", &path, &index));
+        index.insert("note", "A Lie algebra");
+        assert!(query.matches("note", &path, &index));
+    }
+
+    #[test]
+    fn test_relevance() {
+        let mut index = TextIndex::default();
+        index.insert("group", "A group acts on a set.");
+        index.insert("ring", "A ring is a group.");
+        index.insert("field", "A field is a ring.");
+
+        let query = FullTextQuery::parse("group").unwrap();
+        let before = query.relevance("group", &index);
+        assert!(before > 0.0);
+
+        // the inverse document frequencies follow changes of the index
+        index.insert("field", "A field is a group.");
+        let common = query.relevance("group", &index);
+        assert!(common < before);
+        index.insert("lattice", "A lattice is a partially ordered set.");
+        index.insert("monoid", "A monoid is a semigroup with an identity.");
+        assert!(query.relevance("group", &index) > common);
+    }
+
+    #[test]
+    fn test_find_all() {
+        let content = "# Groups\nA Lie group acts on itself.\n\nTODO(alice): groups of Lie type\n";
+        let words = FullTextQuery::parse("grou").unwrap();
+        let todo = FullTextQuery::parse(r"/TODO\(\w+\)/c").unwrap();
+
+        // the byte ranges of the highlighted parts of each matching line
+        let ranges = |text_match: &TextMatch| {
+            text_match
+                .ranges
+                .iter()
+                .map(|range| (range.start, range.end))
+                .collect::<Vec<_>>()
+        };
+
+        let matches = TextMatch::find_all(&[&words, &todo], content);
+        assert_eq!(
+            matches
+                .iter()
+                .map(|m| (m.line, ranges(m)))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, vec![(2, 8)]),
+                (2, vec![(6, 11)]),
+                (4, vec![(0, 11), (13, 19)])
+            ]
+        );
+        assert_eq!(matches[1].text, "A Lie group acts on itself.");
+
+        // overlapping matches are merged
+        let phrase = FullTextQuery::parse(r#"lie "lie group""#).unwrap();
+        assert_eq!(
+            ranges(&TextMatch::find_all(&[&phrase], content)[0]),
+            vec![(2, 11)]
+        );
+        assert!(TextMatch::find_all(&[&phrase], "Nothing here.").is_empty());
+    }
+
+    #[test]
+    fn test_snippet() {
+        let text_match = TextMatch {
+            line: 1,
+            text: "A rather long introduction to Lie groups and Lie algebras".to_owned(),
+            ranges: vec![30..33, 45..48],
+        };
+
+        assert_eq!(
+            text_match.snippet(8),
+            vec![
+                ("…tion to ".to_owned(), false),
+                ("Lie".to_owned(), true),
+                (" groups and ".to_owned(), false),
+                ("Lie".to_owned(), true),
+                (" algebras".to_owned(), false),
+            ]
+        );
+        assert_eq!(
+            text_match.snippet(30)[0],
+            ("A rather long introduction to ".to_owned(), false)
+        );
+        assert_eq!(text_match.snippet(0)[0], ("…".to_owned(), false));
+    }
+}
@@ -1,11 +1,11 @@
-use std::{borrow::BorrowMut, collections::HashMap, path};
+use std::{collections::{HashMap, HashSet}, path};
 
 use itertools::Itertools;
 use std::io::Write;
 
 use crate::{error, io};
 
-use super::Note;
+use super::{Note, TextIndex};
 
 /// Contains a NoteIndex and wraps it to provide easy mutable access from different areas of the code.
 pub type NoteIndexContainer = std::rc::Rc<std::cell::RefCell<NoteIndex>>;
@@ -13,16 +13,33 @@ pub type NoteIndexContainer = std::rc::Rc<std::cell::RefCell<NoteIndex>>;
 /// Contains an indexed and hashed list of notes
 pub struct NoteIndex {
     /// The wrapped HashMap, available only in the data module.
+    /// Notes are keyed by the id of their path relative to the vault.
     pub(super) inner: HashMap<String, Note>,
+    /// Maps the last segment of the ids of all notes, i.e. the id of their file name, to the full ids of all notes with this file name, shortest paths first.
+    names: HashMap<String, Vec<String>>,
+    /// Maps the ids of all aliases to the ids of the notes they belong to, shortest paths first.
+    aliases: HashMap<String, Vec<String>>,
+    /// Maps the ids of all notes to the ids of the notes they link to, with one entry per link that can be resolved.
+    outlinks: HashMap<String, Vec<String>>,
+    /// Maps the ids of all notes to the ids of the notes linking to them, with one entry per link.
+    backlinks: HashMap<String, Vec<String>>,
+    /// Maps the last segment of the ids of all links, i.e. the file name or alias they refer to, to the ids of the notes containing such links.
+    /// Used to find the links whose targets can change when notes or aliases are added or removed.
+    linking: HashMap<String, HashSet<String>>,
+    /// The terms of the full text of all notes, so full-text filters do not need to read the files again.
+    pub(super) text: TextIndex,
 
 
     /// === Config ===
     /// The vault path
     vault_path: path::PathBuf,
     /// The file tracker that sends file events and watches the structure of the vault of this index.
-    tracker: io::FileTracker,
+    /// Kept behind a mutex, so the index can be shared with the worker threads creating HTML files.
+    tracker: std::sync::Mutex<io::FileTracker>,
     /// The HtmlBuilder this index uses to create its HTML files.
     builder: io::HtmlBuilder,
+    /// Whether the full-text index is saved alongside the index cache.
+    cache_full_text: bool,
 }
 
 impl std::fmt::Debug for NoteIndex {
@@ -31,9 +48,22 @@ impl std::fmt::Debug for NoteIndex {
     }
 }
 
+/// The version of the cached index, to be increased whenever notes gain data that cached notes would be missing.
+/// Caches of other versions are discarded, so all notes are read again.
+const CACHE_VERSION: u32 = 1;
+
+/// The contents of the cached index file.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct IndexCache {
+    /// The version of the cache format, see `CACHE_VERSION`.
+    version: u32,
+    /// All notes, keyed by their id.
+    notes: std::collections::BTreeMap<String, Note>,
+}
+
 impl NoteIndex {
 
-    /// Loads the cached index from file.
+    /// Loads the cached index from file, if it exists and was written in the current format.
     fn load_cached_index(
         vault_path: path::PathBuf
     ) -> Option<HashMap<String, Note>> {
@@ -43,79 +73,492 @@ impl NoteIndex {
         // Check if it exist and load the contents from file.
         if cache_path.exists() {
             let file_content = std::fs::read_to_string(cache_path).ok()?;
-            let mut cached_index: HashMap<String, Note> = toml::from_str(&file_content).ok()?;
-            for (_id, note) in cached_index.iter_mut() {
-                note.path = vault_path.join(note.path.clone());
+            let cached_index: IndexCache = toml::from_str(&file_content).ok()?;
+            if cached_index.version != CACHE_VERSION {
+                return None;
             }
-            Some(cached_index)
+            // Restore the full paths and re-key the notes by them, in case the cache was written with other ids.
+            Some(cached_index.notes.into_values().map(|mut note| {
+                note.path = vault_path.join(note.path.clone());
+                (super::path_to_id(&note.path, &vault_path), note)
+            }).collect())
         } else {
              None   
         }
     }
+
+    /// Loads the cached full-text index from file.
+    fn load_cached_text(
+        vault_path: &path::Path
+    ) -> Option<HashMap<String, super::text_index::TermCounts>> {
+        let file_content = std::fs::read_to_string(vault_path.join("rucola_text_index.toml")).ok()?;
+        toml::from_str(&file_content).ok()
+    }
     
     /// Reads a passed directory recursively, returning a hashmap containing
     ///  - An entry for every '.md' file in the directory or any subdirectories
-    ///  - The key will be the file name, without the file extension, in lowercase and with spaces replaced by dashes
+    ///  - The key will be the path relative to the vault, without the file extension, in lowercase and with spaces replaced by dashes
     ///  - The value will be an instance of Note containing metadata of the file.
     ///
-    /// All IO errors that happeded during the creation or the (potential) HTML conversion are returned alongside.
-    pub fn new(
-        mut tracker: io::FileTracker,
+    /// If caching is enabled, notes from the cache are re-used as long as the modification time and size of their file have not changed.
+    /// Only new and changed files are read again, notes of deleted files are dropped.
+    /// The same holds for the terms of the full-text index, if it is cached as well.
+    ///
+    /// Notes are parsed and HTML files are created on all available cores.
+    /// The progress of both steps is reported to the passed callback as a description of the current step, the number of finished files and the total number of files in this step.
+    ///
+    /// All IO errors that happeded during the creation or the (potential) HTML conversion are returned alongside, as well as warnings about ambiguous links.
+    pub fn new_with_progress<F: FnMut(&str, usize, usize)>(
+        tracker: io::FileTracker,
         builder: io::HtmlBuilder,
         config: &crate::Config,
+        mut progress: F,
     ) -> (Self, Vec<error::RucolaError>) {
         // create an error struct
         let mut errors = vec![];
 
         let vault_path = config.vault_path.clone().expect("Vault path should be set.");
+        // use the canonical vault path, as the paths of all notes are canonical as well
+        let vault_path = vault_path.canonicalize().unwrap_or(vault_path);
 
         // check if an index file exists
-        let cached_index = config.cache_index.then(|| Self::load_cached_index(vault_path)).flatten();
-        
-        // collect all the notes from the vault folder
-        let inner = cached_index.unwrap_or_else(|| tracker
+        let mut cached_index = config.cache_index.then(|| Self::load_cached_index(vault_path.clone())).flatten().unwrap_or_default();
+        let mut cached_text = (config.cache_index && config.cache_full_text).then(|| Self::load_cached_text(&vault_path)).flatten().unwrap_or_default();
+
+        // the notes that can be taken from the cache
+        let mut inner = HashMap::new();
+        // the paths of all other files, which need to be read
+        let mut to_read = Vec::new();
+        // the terms of the full text that can be taken from the cache
+        let mut text = TextIndex::default();
+
+        // go through all files in the vault folder
+        for entry in tracker
             .get_walker() // Check only OKs
             .flatten()
-            // Convert tiles to notes and skip errors
             .filter(|entry| entry.metadata().is_ok_and(|md| md.is_file()))
-            .flat_map(|entry| match Note::from_path(entry.path()) {
-                Ok(note) => {
-                    // Also ensure the html is created along the way.
-                    if let Err(e) = builder.create_html(&note, false){
-                        errors.push(e);
+        {
+            let path = entry.path().canonicalize().unwrap_or(entry.path().to_path_buf());
+            // Convert the path to an id
+            let id = super::path_to_id(&path, &vault_path);
+            // Re-use the cached note if its file has not changed since, otherwise remember to read it
+            match cached_index.remove(&id).filter(|note| note.is_up_to_date(&path)) {
+                Some(note) => {
+                    // the cached terms are only valid if the note itself did not change
+                    if let Some(counts) = cached_text.remove(&id) {
+                        text.insert_counts(id.clone(), counts);
                     }
-                    Some(note)
-                },
-                Err(e) => {
-                    errors.push(e);
-                    None
+                    inner.insert(id, note);
                 }
-            })
-            // Extract name and convert to id
-            .map(|note| (super::name_to_id(&note.name), note))
-            // Collect into hash map
-            .collect::<HashMap<_, _>>());
+                None => to_read.push((id, path)),
+            }
+        }
+
+        // remember which notes were (re-)read and need new html files
+        let mut changed = std::collections::HashSet::new();
+
+        // convert the files to notes and skip errors
+        for (id, note) in parallel_map(
+            &to_read,
+            |(id, path)| (id.to_owned(), Note::from_path(path)),
+            |done, total| progress("Indexing notes...", done, total),
+        ) {
+            match note {
+                Ok(note) => {
+                    changed.insert(id.clone());
+                    inner.insert(id, note);
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+
+        // index the full text of all notes whose terms were not cached
+        let to_tokenize = inner
+            .iter()
+            .filter(|(id, _note)| !text.documents().contains_key(*id))
+            .map(|(id, note)| (id, &note.path))
+            .collect_vec();
+        for (id, counts) in parallel_map(
+            &to_tokenize,
+            |(id, path)| (id.to_string(), std::fs::read_to_string(path).map(|content| TextIndex::count_terms(&content))),
+            |done, total| progress("Indexing full text...", done, total),
+        ) {
+            match counts {
+                Ok(counts) => text.insert_counts(id, counts),
+                Err(e) => errors.push(e.into()),
+            }
+        }
+
+        let mut index = Self {
+            inner,
+            names: HashMap::new(),
+            aliases: HashMap::new(),
+            outlinks: HashMap::new(),
+            backlinks: HashMap::new(),
+            linking: HashMap::new(),
+            text,
+            tracker: std::sync::Mutex::new(tracker),
+            builder,
+            vault_path,
+            cache_full_text: config.cache_full_text,
+        };
+        index.rebuild_lookup();
+
+        // Warn about all links that could refer to multiple notes.
+        errors.extend(index.ambiguous_links());
+
+        // Create the html files of all new and changed notes (and all notes that lack one) only once all notes are known, so links can be resolved through aliases.
+        if config.enable_html {
+            let to_build = index.inner
+                .iter()
+                .filter(|(id, note)| changed.contains(*id) || !index.builder.html_exists(note))
+                .map(|(_id, note)| note)
+                .collect_vec();
+
+            errors.extend(
+                parallel_map(
+                    &to_build,
+                    |note| index.builder.create_html(note, &index, false),
+                    |done, total| progress("Creating HTML files...", done, total),
+                )
+                .into_iter()
+                .flat_map(|result| result.err()),
+            );
+        }
+
+        // Remove the HTML files that earlier versions created for notes in subfolders directly in the `.html` folder.
+        if let Err(e) = index.builder.remove_stale_html(&index) {
+            errors.push(e);
+        }
 
         // let the watcher start watching _after_ all htmls have been re-done
-        match tracker.initialize_watching() {
+        match index.tracker().initialize_watching() {
             Ok(_) => {}
             Err(e) => errors.push(e.into()),
         };
 
-        (
-            Self {
-                inner,
-                tracker,
-                builder,
-                vault_path: config.vault_path.clone().expect("Vault path should be set."),
-            },
-            errors,
-        )
+        (index, errors)
+    }
+
+    /// Creates a new index like `new_with_progress`, without reporting any progress.
+    #[cfg(test)]
+    pub fn new(
+        tracker: io::FileTracker,
+        builder: io::HtmlBuilder,
+        config: &crate::Config,
+    ) -> (Self, Vec<error::RucolaError>) {
+        Self::new_with_progress(tracker, builder, config, |_, _, _| {})
+    }
+
+    /// Gives access to the file tracker of this index.
+    fn tracker(&self) -> std::sync::MutexGuard<'_, io::FileTracker> {
+        // the tracker is never left in an inconsistent state, so it can still be used after a panic of another thread
+        self.tracker.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Creates the maps used to resolve links by file name and by alias, as well as the link maps, from all notes of this index.
+    /// Afterwards, `insert_note` and `remove_note` keep them up to date.
+    fn rebuild_lookup(&mut self) {
+        self.names.clear();
+        self.aliases.clear();
+        self.linking.clear();
+        for (id, note) in self.inner.iter() {
+            Self::add_lookup(&mut self.names, &mut self.aliases, &mut self.linking, id, note);
+        }
+
+        self.outlinks.clear();
+        self.backlinks.clear();
+        for id in self.inner.keys().cloned().collect_vec() {
+            self.update_links(&id);
+        }
+    }
+
+    /// Adds the file name, aliases and links of the given note to the lookup maps.
+    fn add_lookup(
+        names: &mut HashMap<String, Vec<String>>,
+        aliases: &mut HashMap<String, Vec<String>>,
+        linking: &mut HashMap<String, HashSet<String>>,
+        id: &str,
+        note: &Note,
+    ) {
+        let by_path_length = |id: &String| (id.matches('/').count(), id.to_owned());
+
+        let ids = names.entry(Self::id_to_name_id(id).to_owned()).or_default();
+        ids.push(id.to_owned());
+        ids.sort_by_key(by_path_length);
+
+        // aliases are matched with whole link ids, so an alias like `TCP/IP` keeps its slash
+        for alias in note.aliases.iter().map(|alias| super::link_to_id(alias)).unique() {
+            let ids = aliases.entry(alias).or_default();
+            ids.push(id.to_owned());
+            ids.sort_by_key(by_path_length);
+        }
+
+        for link_id in note.links.iter() {
+            linking.entry(Self::id_to_name_id(link_id).to_owned()).or_default().insert(id.to_owned());
+        }
+    }
+
+    /// Returns the file name and all aliases the given note can be linked by, reduced to their last segment like the keys of `linking`.
+    fn lookup_keys(id: &str, note: &Note) -> HashSet<String> {
+        note.aliases
+            .iter()
+            .map(|alias| Self::id_to_name_id(&super::link_to_id(alias)).to_owned())
+            .chain(std::iter::once(Self::id_to_name_id(id).to_owned()))
+            .collect()
+    }
+
+    /// Inserts the given note into the index, replacing a previous note with the same id, and updates the lookup and link maps.
+    /// Besides the links of the note itself, only the links of other notes that refer to an added or removed file name or alias are resolved again.
+    fn insert_note(&mut self, id: String, note: Note) {
+        let old_keys = self.detach_note(&id).map(|old| Self::lookup_keys(&id, &old)).unwrap_or_default();
+        let new_keys = Self::lookup_keys(&id, &note);
+
+        Self::add_lookup(&mut self.names, &mut self.aliases, &mut self.linking, &id, &note);
+        self.inner.insert(id.clone(), note);
+        self.update_links(&id);
+
+        self.update_links_to(old_keys.symmetric_difference(&new_keys));
+    }
+
+    /// Removes the note with the given id from the index and updates the lookup and link maps.
+    /// Only the links of other notes that refer to the file name or an alias of the removed note are resolved again.
+    fn remove_note(&mut self, id: &str) -> Option<Note> {
+        let note = self.detach_note(id)?;
+        self.update_links_to(&Self::lookup_keys(id, &note));
+        Some(note)
+    }
+
+    /// Removes the note with the given id from the index, the lookup maps and the link maps, without resolving the links of any other notes again.
+    fn detach_note(&mut self, id: &str) -> Option<Note> {
+        let note = self.inner.remove(id)?;
+
+        // the maps keep no empty entries
+        fn remove_from(map: &mut HashMap<String, Vec<String>>, key: &str, id: &str) {
+            if let Some(ids) = map.get_mut(key) {
+                ids.retain(|other| other != id);
+                if ids.is_empty() {
+                    map.remove(key);
+                }
+            }
+        }
+
+        remove_from(&mut self.names, Self::id_to_name_id(id), id);
+        for alias in note.aliases.iter() {
+            remove_from(&mut self.aliases, &super::link_to_id(alias), id);
+        }
+        for link_id in note.links.iter() {
+            let key = Self::id_to_name_id(link_id);
+            if let Some(sources) = self.linking.get_mut(key) {
+                sources.remove(id);
+                if sources.is_empty() {
+                    self.linking.remove(key);
+                }
+            }
+        }
+
+        // as the note is gone, this removes its links from the maps
+        self.update_links(id);
+
+        Some(note)
+    }
+
+    /// Re-resolves the links of all notes containing links that refer to one of the given file names or aliases.
+    fn update_links_to<'a>(&mut self, keys: impl IntoIterator<Item = &'a String>) {
+        let sources = keys
+            .into_iter()
+            .flat_map(|key| self.linking.get(key))
+            .flatten()
+            .cloned()
+            .collect::<HashSet<_>>();
+        for source_id in sources {
+            self.update_links(&source_id);
+        }
+    }
+
+    /// Re-resolves the links of the note with the given id and updates the link maps accordingly.
+    /// If the note no longer exists, its links are removed from the maps.
+    fn update_links(&mut self, id: &str) {
+        // remove the previous links of this note
+        for target_id in self.outlinks.remove(id).unwrap_or_default() {
+            if let Some(sources) = self.backlinks.get_mut(&target_id) {
+                if let Some(position) = sources.iter().position(|source_id| source_id == id) {
+                    sources.swap_remove(position);
+                }
+            }
+        }
+
+        // resolve the current links
+        let Some(note) = self.inner.get(id) else {
+            return;
+        };
+        let targets = note
+            .links
+            .iter()
+            .flat_map(|link_id| self.resolve_from(id, link_id))
+            .map(|target_id| target_id.to_owned())
+            .collect_vec();
+
+        for target_id in targets.iter() {
+            self.backlinks.entry(target_id.to_owned()).or_default().push(id.to_owned());
+        }
+        self.outlinks.insert(id.to_owned(), targets);
+    }
+
+    /// Re-reads the full text of the note with the given id and updates its terms in the full-text index.
+    /// If the note no longer exists or cannot be read, its terms are removed from the index.
+    fn update_text(&mut self, id: &str) {
+        match self.inner.get(id).and_then(|note| std::fs::read_to_string(&note.path).ok()) {
+            Some(content) => self.text.insert(id, &content),
+            None => self.text.remove(id),
+        }
+    }
+
+    /// Returns the ids of all notes the note with the given id links to, with one entry per link that can be resolved.
+    pub fn outlinks(&self, id: &str) -> &[String] {
+        self.outlinks.get(id).map(|ids| ids.as_slice()).unwrap_or_default()
+    }
+
+    /// Returns the ids of all notes linking to the note with the given id, with one entry per link.
+    pub fn backlinks(&self, id: &str) -> &[String] {
+        self.backlinks.get(id).map(|ids| ids.as_slice()).unwrap_or_default()
+    }
+
+    /// Returns how many links of the note with the given id do not have a valid target anywhere.
+    pub fn broken_links(&self, id: &str) -> usize {
+        self.inner.get(id).map(|note| note.links.len().saturating_sub(self.outlinks(id).len())).unwrap_or_default()
+    }
+
+    /// Returns the last segment of an id, i.e. the id of the file name without any folders.
+    fn id_to_name_id(id: &str) -> &str {
+        id.rsplit('/').next().unwrap_or(id)
     }
 
-    /// Wrapper of the HashMap::get() Function
+    /// Returns the ids of all notes the given link id could refer to, i.e. all notes whose path ends with the link, shortest paths first.
+    fn candidates<'a>(&'a self, link_id: &'a str) -> impl Iterator<Item = &'a String> {
+        self.names
+            .get(Self::id_to_name_id(link_id))
+            .into_iter()
+            .flatten()
+            .filter(move |id| {
+                id.as_str() == link_id
+                    || id
+                        .strip_suffix(link_id)
+                        .is_some_and(|folders| folders.ends_with('/'))
+            })
+    }
+
+    /// Returns the note with the given id, or the note the given link id resolves to.
     pub fn get(&self, key: &str) -> Option<&Note> {
-        self.inner.get(key)
+        self.inner.get(key).or_else(|| self.inner.get(self.resolve(key)?))
+    }
+
+    /// Returns the ids of all notes, in no particular order.
+    pub fn ids(&self) -> impl Iterator<Item = &String> {
+        self.inner.keys()
+    }
+
+    /// Resolves the id of a link target to the id of an existing note.
+    ///  - A note whose full id (i.e. vault-relative path) matches the link is always taken first.
+    ///  - Otherwise, the link is matched with the end of the paths of all notes, so both `[[Index]]` and `[[projects/Index]]` find `work/projects/index.md`.
+    ///    If this is ambiguous, the note with the shortest path is taken.
+    ///  - Finally, the link is matched with the aliases of all notes.
+    ///
+    /// Returns `None` if neither a note nor an alias with this id exists.
+    pub fn resolve<'a>(&'a self, link_id: &'a str) -> Option<&'a str> {
+        if self.inner.contains_key(link_id) {
+            Some(link_id)
+        } else {
+            self.candidates(link_id)
+                .next()
+                .or_else(|| self.aliases.get(link_id).and_then(|ids| ids.first()))
+                .map(|id| id.as_str())
+        }
+    }
+
+    /// Resolves the id of a link target contained in the note with the given id to the id of an existing note.
+    /// Relative links, i.e. markdown links starting with `./` or `../`, are resolved against the folder of that note.
+    /// If there is no such note and the link is not qualified by any folders, it is resolved like a wikilink by `resolve`.
+    /// All other links are resolved by `resolve`.
+    pub fn resolve_from<'a>(&'a self, source_id: &str, link_id: &'a str) -> Option<&'a str> {
+        if !super::is_relative_link(link_id) {
+            return self.resolve(link_id);
+        }
+
+        // start in the folder of the source note
+        let mut segments = source_id.split('/').collect_vec();
+        segments.pop();
+        for segment in link_id.split('/') {
+            match segment {
+                "." => {}
+                ".." => {
+                    // links leaving the vault cannot be resolved
+                    segments.pop()?;
+                }
+                _ => segments.push(segment),
+            }
+        }
+
+        self.inner
+            .get_key_value(&segments.join("/"))
+            .map(|(id, _)| id.as_str())
+            .or_else(|| {
+                link_id
+                    .strip_prefix("./")
+                    .filter(|name| !name.contains('/'))
+                    .and_then(|name| self.resolve(name))
+            })
+    }
+
+    /// Checks whether the given link id could refer to multiple notes.
+    pub fn is_ambiguous(&self, link_id: &str) -> bool {
+        !self.inner.contains_key(link_id) && self.candidates(link_id).nth(1).is_some()
+    }
+
+    /// Returns a warning for every link in any note that could refer to multiple notes.
+    pub fn ambiguous_links(&self) -> Vec<error::RucolaError> {
+        self.inner
+            .values()
+            .flat_map(|note| {
+                note.links
+                    .iter()
+                    .unique()
+                    .filter(|link_id| self.is_ambiguous(link_id))
+                    .map(|link_id| {
+                        error::RucolaError::AmbiguousLink(
+                            link_id.to_owned(),
+                            note.display_name.to_owned(),
+                            self.resolve(link_id).unwrap_or_default().to_owned(),
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns the id of the given path within this index.
+    pub fn path_to_id(&self, path: &path::Path) -> String {
+        super::path_to_id(path, &self.vault_path)
+    }
+
+    /// Returns the folder the note with the given id is stored in, relative to the vault.
+    /// Returns an empty string for notes in the top level of the vault.
+    pub fn folder(&self, id: &str) -> String {
+        // the id contains exactly one segment per folder between the vault and the note
+        let depth = id.matches('/').count();
+        self.inner
+            .get(id)
+            .and_then(|note| note.path.parent())
+            .map(|folder| {
+                let mut components = folder
+                    .components()
+                    .rev()
+                    .take(depth)
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>();
+                components.reverse();
+                components.join("/")
+            })
+            .unwrap_or_default()
     }
 
     /// Loads the note at the given path and inserts it into the index.
@@ -125,11 +568,25 @@ impl NoteIndex {
     /// creating a new subdirectory and a file inside it in quick succession).
     pub fn insert_note_from_path(&mut self, path: &std::path::Path) -> error::Result<()> {
         let note = Note::from_path(path)?;
-        self.builder.create_html(&note, false)?;
-        self.inner.insert(super::name_to_id(&note.name), note);
+        let id = self.path_to_id(&note.path);
+        self.insert_note(id.clone(), note);
+        self.update_text(&id);
+        if let Some(note) = self.inner.get(&id) {
+            self.builder.create_html(note, self, false)?;
+        }
         Ok(())
     }
 
+    /// Reads the note with the given id again after its file was changed programmatically, e.g. by checking a task, without waiting for the file watcher.
+    /// Like for file events, only the links that could refer to this note are resolved again.
+    pub fn reload_note(&mut self, id: &str) -> error::Result<()> {
+        let path = self.inner
+            .get(id)
+            .map(|note| note.path.clone())
+            .ok_or_else(|| error::RucolaError::NoteNotFound(id.to_owned()))?;
+        self.insert_note_from_path(&path)
+    }
+
     /// Handle all file events on notes, as found by the contained tracker.
     ///  - Renames and moves are tracked
     ///  - new file creations with in the vault folder are checked for notes and added if appropriate
@@ -140,7 +597,9 @@ impl NoteIndex {
     pub fn handle_file_events(&mut self) -> error::Result<(bool, Vec<String>)> {
         let mut modifications = false;
         let mut id_changes = vec![];
-        for event in self.tracker.try_events_iter().flatten() {
+        let mut changed_notes = vec![];
+        let events = self.tracker().try_events_iter().flatten().collect_vec();
+        for event in events {
             match event.kind {
                 notify::EventKind::Create(_)
                 // also trigger on the target of a rename (new location)
@@ -153,12 +612,12 @@ impl NoteIndex {
                     // - Check for each path if we are interested in it (gitignore + extensions from config)
                     // - Try to load the note and index it
                     for path in event.paths {
-                        if self.tracker.is_tracked(&path) {
+                        if self.tracker().is_tracked(&path) {
                             if let Ok(note) = super::Note::from_path(&path) {
-                                // create html on creation
-                                self.builder.create_html(&note, false)?;
-                                // insert the note
-                                self.inner.insert(super::name_to_id(&note.name), note);
+                                let id = self.path_to_id(&note.path);
+                                // insert the note and remember to create its html
+                                self.insert_note(id.clone(), note);
+                                changed_notes.push(id);
                                 modifications = true;
                             }
                         }
@@ -185,7 +644,8 @@ impl NoteIndex {
                         .find(|(_id, note)| note.path.to_path_buf() == *deleted_path)
                         .map(|(id, _n)| id.to_owned())
                     {
-                        self.inner.remove(&old_id);
+                        self.remove_note(&old_id);
+                        self.text.remove(&old_id);
                         modifications = true;
                         id_changes.push(old_id);
                     }
@@ -193,17 +653,20 @@ impl NoteIndex {
                 notify::EventKind::Modify(_kind) => {
                     // Modifications
                     // - For modifications, reload the entire note
-                            for (_id, note) in self.inner.borrow_mut().iter_mut() {
-                                if event.paths.iter().flat_map(|path| path.canonicalize()).contains(&note.path) {
-                                    if let Ok(new_note) = Note::from_path(&note.path) {
-                                        // create html on creation
-                                        self.builder.create_html(&new_note, false)?;
-                                        // replace the index entry
-                                        *note = new_note;
-                                        modifications = true;
-                                    }
-                                }
-                            }
+                    let paths = event.paths.iter().flat_map(|path| path.canonicalize()).collect_vec();
+                    let modified = self.inner
+                        .iter()
+                        .filter(|(_id, note)| paths.contains(&note.path))
+                        .map(|(id, note)| (id.to_owned(), note.path.to_owned()))
+                        .collect_vec();
+                    for (id, path) in modified {
+                        if let Ok(new_note) = Note::from_path(&path) {
+                            // replace the index entry and remember to create its html
+                            self.insert_note(id.clone(), new_note);
+                            changed_notes.push(id);
+                            modifications = true;
+                        }
+                    }
                 }
                 // Do nothing in the other cases
                 notify::EventKind::Access(_) => {}
@@ -213,42 +676,66 @@ impl NoteIndex {
         }
         // just to be sure
         modifications |= !id_changes.is_empty();
+
+        // re-index the full text of all new and modified notes
+        for id in changed_notes.iter().unique() {
+            self.update_text(id);
+        }
+
+        // create html of all new and modified notes, now that all links can be resolved
+        for id in changed_notes.iter().unique() {
+            if let Some(note) = self.inner.get(id) {
+                self.builder.create_html(note, self, false)?;
+            }
+        }
+
         Ok((modifications, id_changes))
     }
 
     /// Returns an iterator over pairs of (id, name) of notes linked from this note.
+    /// Links are resolved through relative paths and aliases.
     pub fn links_vec(&self, source_id: &str) -> Vec<(String, String)> {
-        self.inner
-            .get(source_id)
-            .map(|source| {
-                source
-                    .links
-                    .iter()
-                    .flat_map(|link_id| {
-                        self.inner
-                            .get(link_id)
-                            .map(|note| note.name.clone())
-                            .map(|name| (link_id.to_owned(), name))
-                    })
-                    .unique()
-                    .sorted_by(|(id1, _), (id2, _)| id1.cmp(id2))
-                    .collect()
-            })
+        self.resolve(source_id)
+            .map(|source_id| self.outlinks(source_id))
             .unwrap_or_default()
+            .iter()
+            .flat_map(|target_id| {
+                self.inner
+                    .get(target_id)
+                    .map(|note| note.name.clone())
+                    .map(|name| (target_id.to_owned(), name))
+            })
+            .unique()
+            .sorted_by(|(id1, _), (id2, _)| id1.cmp(id2))
+            .collect()
     }
 
-    /// Returns a vector over pairs of (id, name) of notes linking to this note.
+    /// Returns a vector over pairs of (id, name) of notes linking to this note, directly or through one of its aliases.
     pub fn blinks_vec(&self, target_id: &str) -> Vec<(String, String)> {
-        let id_copy = target_id.to_string();
-        self.inner
+        self.resolve(target_id)
+            .map(|target_id| self.backlinks(target_id))
+            .unwrap_or_default()
             .iter()
-            .filter(|(_other_id, note)| note.links.contains(&id_copy))
-            .map(|(id, note)| (id.to_owned(), note.name.to_owned()))
             .unique()
+            .flat_map(|source_id| {
+                self.inner
+                    .get(source_id)
+                    .map(|note| (source_id.to_owned(), note.name.to_owned()))
+            })
             .sorted_by(|(id1, _), (id2, _)| id1.cmp(id2))
             .collect()
     }
 
+    /// Resolves the heading fragment of a link against the outline of the target note, which may also be given by an alias.
+    /// The fragment can either be the text of the heading (as in wikilinks) or its slug (as in markdown links).
+    /// Returns `None` if the target note does not exist or has no such heading.
+    pub fn resolve_heading(&self, target_id: &str, fragment: &str) -> Option<&super::Heading> {
+        let slug = super::heading_to_slug(fragment);
+        self.inner.get(self.resolve(target_id)?)?.headings.iter().find(|heading| {
+            heading.slug == slug || super::heading_to_slug(&heading.text) == slug
+        })
+    }
+
     /// Returns a map of all tags contained in this index with their number of appearances, sorted in descending order.
     pub fn tags_vec(&self) -> Vec<(String, usize)> {
         self.inner
@@ -264,44 +751,157 @@ impl NoteIndex {
             .collect()
     }
 
+    /// Returns pairs of (id, task) of all tasks in notes of this index that match the given filter.
+    /// Tasks that are done are only included if requested.
+    /// Tasks with a due date come first, sorted by it, followed by all others in order of their notes.
+    pub fn tasks_vec(&self, filter: &super::Filter, include_done: bool) -> Vec<(String, super::Task)> {
+        self.inner
+            .iter()
+            .flat_map(|(id, note)| {
+                note.tasks
+                    .iter()
+                    .filter(|task| include_done || !task.done)
+                    .filter(|task| filter.apply_task(task, note, self).is_some())
+                    .map(move |task| (id.to_owned(), task.to_owned()))
+            })
+            .sorted_by(|(id1, task1), (id2, task2)| {
+                task1.due.is_none().cmp(&task2.due.is_none())
+                    .then(task1.due.cmp(&task2.due))
+                    .then(id1.cmp(id2))
+                    .then(task1.line.cmp(&task2.line))
+            })
+            .collect()
+    }
+
     /// Requests this index to update itself to be in sync with the tracked file system.
     pub fn poll_file_system(&self) {
-        self.tracker.poll_file_system();
+        self.tracker().poll_file_system();
     }
 
     /// Saves a copy of this index to the vault path to be quickly reloaded on the next launch.
+    /// The copy is first written to a temporary file that then replaces the old cache, so an interrupted save never leaves a broken cache behind.
     pub fn save(
         &self,
-    ) {
-        // Create the file at the correct location.
-        let mut file = std::fs::File::create(self.vault_path.join("rucola_index.toml")).unwrap();
-
-
+    ) -> error::Result<()> {
         // Copy the inner index into a BTreeMap to get automatic sorting by key.
         let mut copy: std::collections::BTreeMap<String, Note>= self.inner.iter().map(|(a,b)| (a.clone(), b.clone())).collect();
 
         // Change all paths to be relative to the vault path (important for reloading on multiple machines with differing vault paths).
         for (_id, note) in copy.iter_mut() {
-            let string = note.path.to_str().map(|s| s.replace(self.vault_path.to_str().unwrap(), "")).unwrap();
-            let string = string.trim_start_matches("/");
-            note.path = path::PathBuf::from(string);
+            if let Ok(relative) = note.path.strip_prefix(&self.vault_path) {
+                note.path = relative.to_path_buf();
+            }
+        }
+
+        let cache = IndexCache {
+            version: CACHE_VERSION,
+            notes: copy,
+        };
+        Self::write_cache_file(&self.vault_path.join("rucola_index.toml"), &toml::to_string(&cache)?)?;
+
+        // Save the full-text index, sorted by id as well, or remove an old one that would be out of date when loaded again.
+        let text_path = self.vault_path.join("rucola_text_index.toml");
+        if self.cache_full_text {
+            let documents: std::collections::BTreeMap<_, _> = self.text.documents().iter().collect();
+            Self::write_cache_file(&text_path, &toml::to_string(&documents)?)?;
+        } else if text_path.exists() {
+            std::fs::remove_file(text_path)?;
         }
 
-        // Write the modified index to the file.
-        write!(file, "{}", toml::to_string(&copy).unwrap()).unwrap();
+        Ok(())
+    }
+
+    /// Writes the given content to a temporary file first and then moves it to the given path, so an interrupted write never leaves a broken cache file behind.
+    fn write_cache_file(
+        path: &path::Path,
+        content: &str,
+    ) -> error::Result<()> {
+        let tmp_path = path.with_extension("toml.tmp");
+
+        // Write the content to the temporary file.
+        let mut file = std::fs::File::create(&tmp_path)?;
+        write!(file, "{}", content)?;
+        file.sync_all()?;
+
+        // Replace the old cache.
+        std::fs::rename(&tmp_path, path)?;
+
+        Ok(())
     }
 }
 
+/// Applies the given function to all items, spread over all available cores.
+/// The number of finished items and the total number of items are regularly reported to `progress` on the calling thread.
+/// The results are returned in no particular order.
+fn parallel_map<T: Sync, R: Send>(
+    items: &[T],
+    f: impl Fn(&T) -> R + Sync,
+    mut progress: impl FnMut(usize, usize),
+) -> Vec<R> {
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(items.len());
+
+    // the index of the next item to be processed by any worker
+    let next = std::sync::atomic::AtomicUsize::new(0);
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let (next, f) = (&next, &f);
+            scope.spawn(move || {
+                while let Some(item) = items.get(next.fetch_add(1, std::sync::atomic::Ordering::Relaxed)) {
+                    if sender.send(f(item)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        // only the workers keep senders, so the receiver finishes once all workers are done
+        drop(sender);
+
+        let mut results = Vec::with_capacity(items.len());
+        let mut last_report = std::time::Instant::now();
+        for result in receiver {
+            results.push(result);
+            // do not report every single item, as redrawing is slow
+            if results.len() == items.len() || last_report.elapsed() > std::time::Duration::from_millis(50) {
+                progress(results.len(), items.len());
+                last_report = std::time::Instant::now();
+            }
+        }
+        results
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
     use super::*;
     use crate::io;
 
+    /// Copies the notes of the test vault in `tests/` into the given directory and returns it, so tests can cache the index there without leaving files in the repository.
+    fn copy_test_vault(target: path::PathBuf) -> path::PathBuf {
+        fn copy(from: &path::Path, to: &path::Path) {
+            std::fs::create_dir_all(to).unwrap();
+            for entry in std::fs::read_dir(from).unwrap().flatten() {
+                if entry.path().is_dir() {
+                    copy(&entry.path(), &to.join(entry.file_name()));
+                } else {
+                    std::fs::copy(entry.path(), to.join(entry.file_name())).unwrap();
+                }
+            }
+        }
+        copy(&std::env::current_dir().unwrap().join("tests/common"), &target.join("common"));
+        target
+    }
+
     #[test]
     fn test_index() {
         let config = crate::Config{
-            vault_path: Some(std::env::current_dir().unwrap().join("tests")),
+            vault_path: Some(copy_test_vault(testdir::testdir!())),
             cache_index: true,
             ..Default::default()
         };
@@ -314,9 +914,9 @@ mod tests {
 
         assert!(!index.inner.contains_key("booksold"));
 
-        let os = index.inner.get("operating-systems").unwrap();
-        let lg = index.inner.get("lie-group").unwrap();
-        let ma = index.inner.get("manifold").unwrap();
+        let os = index.get("operating-systems").unwrap();
+        let lg = index.get("lie-group").unwrap();
+        let ma = index.get("manifold").unwrap();
 
         assert_eq!(os.links.len(), 6);
         assert_eq!(os.tags, ["#os"]);
@@ -331,7 +931,7 @@ mod tests {
     #[test]
     fn test_links() {
         let config = crate::Config{
-            vault_path: Some(std::env::current_dir().unwrap().join("tests")),
+            vault_path: Some(copy_test_vault(testdir::testdir!())),
             cache_index: true,
             ..Default::default()
         };
@@ -345,18 +945,18 @@ mod tests {
         assert_eq!(
             index.links_vec("lie-group"),
             vec![
-                ("manifold".to_string(), "Manifold".to_string()),
-                ("smooth-map".to_string(), "Smooth Map".to_string()),
-                ("topology".to_string(), "Topology".to_string()),
+                ("common/notes/math/manifold".to_string(), "Manifold".to_string()),
+                ("common/notes/math/smooth-map".to_string(), "Smooth Map".to_string()),
+                ("common/notes/math/topology".to_string(), "Topology".to_string()),
             ]
         );
 
         assert_eq!(
             index.links_vec("atlas"),
             vec![
-                ("chart".to_string(), "Chart".to_string()),
-                ("manifold".to_string(), "Manifold".to_string()),
-                ("topology".to_string(), "Topology".to_string()),
+                ("common/notes/math/chart".to_string(), "Chart".to_string()),
+                ("common/notes/math/manifold".to_string(), "Manifold".to_string()),
+                ("common/notes/math/topology".to_string(), "Topology".to_string()),
             ]
         );
     }
@@ -365,7 +965,7 @@ mod tests {
     #[test]
     fn test_blinks() {
         let config = crate::Config{
-            vault_path: Some(std::env::current_dir().unwrap().join("tests")),
+            vault_path: Some(copy_test_vault(testdir::testdir!())),
             cache_index: true,
             ..Default::default()
         };
@@ -378,16 +978,325 @@ mod tests {
 
         assert_eq!(
             index.blinks_vec("lie-group"),
-            vec![("manifold".to_string(), "Manifold".to_string())]
+            vec![("common/notes/math/manifold".to_string(), "Manifold".to_string())]
         );
 
         assert_eq!(
             index.blinks_vec("manifold"),
             vec![
-                ("atlas".to_string(), "Atlas".to_string()),
-                ("chart".to_string(), "Chart".to_string()),
+                ("common/notes/math/atlas".to_string(), "Atlas".to_string()),
+                ("common/notes/math/chart".to_string(), "Chart".to_string()),
+                ("common/notes/math/lie-group".to_string(), "Lie Group".to_string()),
+                ("common/notes/math/smooth-map".to_string(), "Smooth Map".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_heading() {
+        let config = crate::Config{
+            vault_path: Some(std::env::current_dir().unwrap().join("tests")),
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let index = NoteIndex::new(tracker, builder, &config).0;
+
+        assert_eq!(
+            index.resolve_heading("manifold", "Definition").map(|h| h.slug.as_str()),
+            Some("definition")
+        );
+        assert_eq!(
+            index.resolve_heading("windows", "another-heading-1").map(|h| h.level),
+            Some(3)
+        );
+        assert_eq!(
+            index.resolve_heading("windows", "Another Heading").map(|h| h.level),
+            Some(2)
+        );
+        assert!(index.resolve_heading("manifold", "Nonexistent").is_none());
+        assert!(index.resolve_heading("nonexistent", "Definition").is_none());
+    }
+
+    #[test]
+    fn test_tasks_vec() {
+        let tmp = testdir::testdir!();
+        std::fs::write(tmp.join("Chores.md"), "- [ ] Clean\n- [x] Cook\n- [ ] Shop @due(2025-05-02)\n").unwrap();
+        std::fs::write(tmp.join("Work.md"), "- [ ] Report @due(2025-05-01)\n- [ ] Email\n").unwrap();
+
+        let config = crate::Config{
+            vault_path: Some(tmp.clone()),
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let mut index = NoteIndex::new(tracker, builder, &config).0;
+
+        let open = index.tasks_vec(&super::super::Filter::default(), false);
+        assert_eq!(
+            open.iter().map(|(id, task)| (id.as_str(), task.text.as_str())).collect_vec(),
+            vec![("work", "Report"), ("chores", "Shop"), ("chores", "Clean"), ("work", "Email")]
+        );
+
+        let all = index.tasks_vec(&super::super::Filter::default(), true);
+        assert_eq!(all.len(), 5);
+        assert!(all.iter().any(|(_id, task)| task.done && task.text == "Cook"));
+
+        // checking a task in the file updates only this note
+        std::fs::write(tmp.join("Work.md"), "- [x] Report @due(2025-05-01)\n- [ ] Email\n").unwrap();
+        index.reload_note("work").unwrap();
+        let open = index.tasks_vec(&super::super::Filter::default(), false);
+        assert_eq!(
+            open.iter().map(|(id, task)| (id.as_str(), task.text.as_str())).collect_vec(),
+            vec![("chores", "Shop"), ("chores", "Clean"), ("work", "Email")]
+        );
+        assert!(index.reload_note("nonexistent").is_err());
+    }
+
+    #[test]
+    fn test_aliases() {
+        let tmp = testdir::testdir!();
+        std::fs::write(tmp.join("Special Orthogonal Group.md"), "---\naliases: [SO(3), Lie Group]\n---\n## Definition\n").unwrap();
+        std::fs::write(tmp.join("Lie Group.md"), "[[SO(3)#Definition]] and [[Special Orthogonal Group]]").unwrap();
+        std::fs::write(tmp.join("Internet Protocol Suite.md"), "---\naliases: [TCP/IP]\n---\n").unwrap();
+        std::fs::write(tmp.join("Network.md"), "[[TCP/IP]]").unwrap();
+        std::fs::write(tmp.join("Rotation.md"), "[[so(3)]] and [[Lie Group]]").unwrap();
+
+        let config = crate::Config{
+            vault_path: Some(tmp),
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let index = NoteIndex::new(tracker, builder, &config).0;
+
+        assert_eq!(index.resolve("so(3)"), Some("special-orthogonal-group"));
+        assert_eq!(index.resolve("special-orthogonal-group"), Some("special-orthogonal-group"));
+        // ids of notes take precedence over aliases
+        assert_eq!(index.resolve("lie-group"), Some("lie-group"));
+        assert_eq!(index.resolve("nonexistent"), None);
+        // aliases keep their folders like links do
+        assert_eq!(index.resolve("tcp/ip"), Some("internet-protocol-suite"));
+        assert_eq!(index.resolve("ip"), None);
+        assert_eq!(index.outlinks("network"), ["internet-protocol-suite"]);
+
+        assert_eq!(
+            index.links_vec("rotation"),
+            vec![
                 ("lie-group".to_string(), "Lie Group".to_string()),
-                ("smooth-map".to_string(), "Smooth Map".to_string()),
+                ("special-orthogonal-group".to_string(), "Special Orthogonal Group".to_string()),
+            ]
+        );
+        assert_eq!(
+            index.blinks_vec("special-orthogonal-group"),
+            vec![
+                ("lie-group".to_string(), "Lie Group".to_string()),
+                ("rotation".to_string(), "Rotation".to_string()),
+            ]
+        );
+        assert_eq!(
+            index.resolve_heading("so(3)", "Definition").map(|h| h.level),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_link_maps() {
+        let tmp = testdir::testdir!();
+        std::fs::write(tmp.join("Group.md"), "[[Ring]]").unwrap();
+        std::fs::write(tmp.join("Ring.md"), "[[Group]], [[Group]] and [[Field]]").unwrap();
+
+        let config = crate::Config{
+            vault_path: Some(tmp.clone()),
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let mut index = NoteIndex::new(tracker, builder, &config).0;
+
+        // one entry per link, broken links are left out
+        assert_eq!(index.outlinks("ring"), ["group", "group"]);
+        assert_eq!(index.backlinks("group"), ["ring", "ring"]);
+        assert!(index.backlinks("field").is_empty());
+        assert_eq!(index.blinks_vec("group"), vec![("ring".to_string(), "Ring".to_string())]);
+
+        // inserting a note resolves previously broken links
+        std::fs::write(tmp.join("Field.md"), "[[Ring]]").unwrap();
+        index.insert_note_from_path(&tmp.join("Field.md")).unwrap();
+
+        assert_eq!(index.outlinks("ring"), ["group", "group", "field"]);
+        assert_eq!(index.backlinks("field"), ["ring"]);
+        assert_eq!(
+            index.blinks_vec("ring"),
+            vec![
+                ("field".to_string(), "Field".to_string()),
+                ("group".to_string(), "Group".to_string()),
+            ]
+        );
+
+        // modifying a note updates only its own links
+        std::fs::write(tmp.join("Group.md"), "[[Field]]").unwrap();
+        std::thread::sleep(std::time::Duration::from_secs(2));
+        let (modifications, _) = index.handle_file_events().unwrap();
+
+        assert!(modifications);
+        assert_eq!(index.outlinks("group"), ["field"]);
+        assert_eq!(index.blinks_vec("ring"), vec![("field".to_string(), "Field".to_string())]);
+        assert_eq!(index.backlinks("field").iter().sorted().collect_vec(), ["group", "ring"]);
+    }
+
+    #[test]
+    fn test_incremental_links() {
+        let tmp = testdir::testdir!();
+        std::fs::create_dir_all(tmp.join("Archive")).unwrap();
+        std::fs::write(tmp.join("Group.md"), "[[SO(3)]] and [[Index]]").unwrap();
+        std::fs::write(tmp.join("Archive/Index.md"), "[[Group]]").unwrap();
+
+        let config = crate::Config{
+            vault_path: Some(tmp.clone()),
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let mut index = NoteIndex::new(tracker, builder, &config).0;
+
+        assert_eq!(index.outlinks("group"), ["archive/index"]);
+
+        // a note with a shorter path takes over the link
+        std::fs::write(tmp.join("Index.md"), "").unwrap();
+        index.insert_note_from_path(&tmp.join("Index.md")).unwrap();
+        assert_eq!(index.outlinks("group"), ["index"]);
+        assert!(index.backlinks("archive/index").is_empty());
+
+        // adding and removing an alias resolves the link to it
+        std::fs::write(tmp.join("Rotation.md"), "---\naliases: [SO(3)]\n---\n").unwrap();
+        index.insert_note_from_path(&tmp.join("Rotation.md")).unwrap();
+        assert_eq!(index.outlinks("group"), ["rotation", "index"]);
+        std::fs::write(tmp.join("Rotation.md"), "").unwrap();
+        index.insert_note_from_path(&tmp.join("Rotation.md")).unwrap();
+        assert_eq!(index.outlinks("group"), ["index"]);
+        assert!(index.backlinks("rotation").is_empty());
+
+        // removing a note resolves links to it again
+        index.remove_note("index");
+        assert_eq!(index.outlinks("group"), ["archive/index"]);
+        assert_eq!(index.backlinks("archive/index"), ["group"]);
+
+        // the maps are the same as when creating them from scratch
+        let maps = |index: &NoteIndex| {
+            let sorted = |map: &HashMap<String, Vec<String>>| {
+                map.iter()
+                    .filter(|(_key, ids)| !ids.is_empty())
+                    .map(|(key, ids)| (key.to_owned(), ids.iter().sorted().cloned().collect_vec()))
+                    .collect::<std::collections::BTreeMap<_, _>>()
+            };
+            let linking = index.linking
+                .iter()
+                .map(|(key, ids)| (key.to_owned(), ids.iter().sorted().cloned().collect_vec()))
+                .collect::<std::collections::BTreeMap<_, _>>();
+            (sorted(&index.names), sorted(&index.aliases), sorted(&index.outlinks), sorted(&index.backlinks), linking)
+        };
+        let incremental = maps(&index);
+        index.rebuild_lookup();
+        assert_eq!(incremental, maps(&index));
+    }
+
+    #[test]
+    fn test_duplicate_names() {
+        let tmp = testdir::testdir!();
+        std::fs::create_dir_all(tmp.join("Projects")).unwrap();
+        std::fs::create_dir_all(tmp.join("Archive/Old Projects")).unwrap();
+        std::fs::write(tmp.join("Projects/Index.md"), "").unwrap();
+        std::fs::write(tmp.join("Archive/Old Projects/Index.md"), "").unwrap();
+        std::fs::write(tmp.join("Overview.md"), "[[Index]] and [[old projects/Index#Goals]]").unwrap();
+        std::fs::write(tmp.join("Current.md"), "[[Projects/Index]]").unwrap();
+
+        let config = crate::Config{
+            vault_path: Some(tmp),
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let (index, errors) = NoteIndex::new(tracker, builder, &config);
+
+        // both notes are kept
+        assert_eq!(index.inner.len(), 4);
+        assert!(index.inner.contains_key("projects/index"));
+        assert!(index.inner.contains_key("archive/old-projects/index"));
+
+        // shortest unique path wins
+        assert_eq!(index.resolve("projects/index"), Some("projects/index"));
+        assert_eq!(index.resolve("old-projects/index"), Some("archive/old-projects/index"));
+        assert_eq!(index.resolve("index"), Some("projects/index"));
+        assert!(index.is_ambiguous("index"));
+        assert!(!index.is_ambiguous("projects/index"));
+
+        assert_eq!(index.folder("archive/old-projects/index"), "Archive/Old Projects");
+        assert_eq!(index.folder("overview"), "");
+
+        assert_eq!(
+            index.blinks_vec("archive/old-projects/index"),
+            vec![("overview".to_string(), "Overview".to_string())]
+        );
+        assert_eq!(
+            index.blinks_vec("projects/index"),
+            vec![
+                ("current".to_string(), "Current".to_string()),
+                ("overview".to_string(), "Overview".to_string()),
+            ]
+        );
+
+        // only the bare link is reported
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            error::RucolaError::AmbiguousLink(link, source, target)
+                if link == "index" && source == "Overview" && target == "projects/index"
+        ));
+    }
+
+    #[test]
+    fn test_relative_links() {
+        let tmp = testdir::testdir!();
+        std::fs::create_dir_all(tmp.join("math")).unwrap();
+        std::fs::create_dir_all(tmp.join("physics")).unwrap();
+        std::fs::write(tmp.join("math/Chart.md"), "").unwrap();
+        std::fs::write(tmp.join("physics/Chart.md"), "").unwrap();
+        std::fs::write(tmp.join("math/Atlas.md"), "[charts](Chart.md) and [plots](../physics/Chart)").unwrap();
+        std::fs::write(tmp.join("physics/Motion.md"), "[see](./Chart.md) and [nothing](../../outside.md)").unwrap();
+
+        let config = crate::Config{
+            vault_path: Some(tmp),
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let index = NoteIndex::new(tracker, builder, &config).0;
+
+        assert_eq!(index.resolve_from("math/atlas", "./chart"), Some("math/chart"));
+        assert_eq!(index.resolve_from("physics/motion", "./chart"), Some("physics/chart"));
+        assert_eq!(index.resolve_from("math/atlas", "../physics/chart"), Some("physics/chart"));
+        assert_eq!(index.resolve_from("physics/motion", "../../outside"), None);
+
+        assert_eq!(
+            index.links_vec("math/atlas"),
+            vec![
+                ("math/chart".to_string(), "Chart".to_string()),
+                ("physics/chart".to_string(), "Chart".to_string()),
+            ]
+        );
+        assert_eq!(
+            index.blinks_vec("physics/chart"),
+            vec![
+                ("math/atlas".to_string(), "Atlas".to_string()),
+                ("physics/motion".to_string(), "Motion".to_string()),
             ]
         );
     }
@@ -395,7 +1304,7 @@ mod tests {
     #[test]
     fn test_links_yaml() {
         let config = crate::Config{
-            vault_path: Some(std::env::current_dir().unwrap().join("tests")),
+            vault_path: Some(copy_test_vault(testdir::testdir!())),
             cache_index: true,
             ..Default::default()
         };
@@ -409,22 +1318,22 @@ mod tests {
         assert_eq!(
             index.links_vec("windows"),
             vec![
-                ("note25".to_string(), "note25".to_string()),
+                ("common/notes/note25".to_string(), "note25".to_string()),
             ]
         );
 
         assert_eq!(
             index.blinks_vec("note25"),
             vec![
-                ("windows".to_string(), "Windows".to_string()),
+                ("common/notes/windows".to_string(), "Windows".to_string()),
             ]
         );
     }
 
     #[test]
     fn test_index_saving() {
-        let testdir = std::env::current_dir().unwrap().join("tests");
-        
+        let testdir = copy_test_vault(testdir::testdir!());
+
         let config = crate::Config{
             vault_path: Some(testdir.clone()),
             cache_index: true,
@@ -437,7 +1346,7 @@ mod tests {
 
         assert_eq!(index.inner.len(), 13);
 
-        index.save();
+        index.save().unwrap();
 
         let other_inner = NoteIndex::load_cached_index(testdir.clone()).expect("To load the index correctly.");
 
@@ -455,8 +1364,157 @@ mod tests {
             assert_eq!(note1.words, note2.words);
             assert_eq!(note1.characters, note2.characters);
             assert_eq!(note1.last_modification, note2.last_modification);
+            assert_eq!(note1.size, note2.size);
             assert_eq!(note1.yaml_frontmatter, note2.yaml_frontmatter);
             assert_eq!(note1.path, testdir.join(note2.path));
         }
     }
+
+    #[test]
+    fn test_index_cache_validation() {
+        let tmp = testdir::testdir!();
+        std::fs::write(tmp.join("Unchanged.md"), "Some words.").unwrap();
+        std::fs::write(tmp.join("Changed.md"), "Some words.").unwrap();
+        std::fs::write(tmp.join("Deleted.md"), "Some words.").unwrap();
+
+        let config = crate::Config{
+            vault_path: Some(tmp.clone()),
+            cache_index: true,
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let mut index = NoteIndex::new(tracker, builder, &config).0;
+        assert_eq!(index.inner.len(), 3);
+
+        // Tamper with the cached data to find out which notes are re-read.
+        for note in index.inner.values_mut() {
+            note.words = 42;
+        }
+        index.save().unwrap();
+        drop(index);
+
+        // Change the vault while rucola is closed
+        std::fs::write(tmp.join("Changed.md"), "Some more words.").unwrap();
+        std::fs::remove_file(tmp.join("Deleted.md")).unwrap();
+        std::fs::write(tmp.join("Created.md"), "Words.").unwrap();
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let index = NoteIndex::new(tracker, builder, &config).0;
+
+        assert_eq!(index.inner.len(), 3);
+        // unchanged notes are taken from the cache
+        assert_eq!(index.get("unchanged").unwrap().words, 42);
+        // changed and new notes are read again
+        assert_eq!(index.get("changed").unwrap().words, 3);
+        assert_eq!(index.get("created").unwrap().words, 1);
+        // deleted notes are dropped
+        assert!(index.get("deleted").is_none());
+        // the temporary file was moved
+        assert!(!tmp.join("rucola_index.toml.tmp").exists());
+
+        // caches of other versions are not loaded
+        let cache = std::fs::read_to_string(tmp.join("rucola_index.toml")).unwrap();
+        assert!(NoteIndex::load_cached_index(tmp.clone()).is_some());
+        std::fs::write(tmp.join("rucola_index.toml"), cache.replace(&format!("version = {CACHE_VERSION}"), "version = 0")).unwrap();
+        assert!(NoteIndex::load_cached_index(tmp.clone()).is_none());
+    }
+
+    #[test]
+    fn test_text_index_caching() {
+        // whether the note contains terms starting with all words of the query
+        let contains = |index: &NoteIndex, id: &str, query: &str| {
+            index.text.candidates(query).is_some_and(|ids| ids.contains(id))
+        };
+
+        let tmp = testdir::testdir!();
+        std::fs::write(tmp.join("Unchanged.md"), "Some words.").unwrap();
+        std::fs::write(tmp.join("Changed.md"), "Some words.").unwrap();
+
+        let mut config = crate::Config{
+            vault_path: Some(tmp.clone()),
+            cache_index: true,
+            cache_full_text: true,
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let mut index = NoteIndex::new(tracker, builder, &config).0;
+        assert!(contains(&index, "unchanged", "word"));
+
+        // Tamper with the cached terms to find out which notes are re-read.
+        for id in ["unchanged", "changed"] {
+            index.text.insert(id, "tampered");
+        }
+        index.save().unwrap();
+        drop(index);
+
+        std::fs::write(tmp.join("Changed.md"), "Other words.").unwrap();
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let mut index = NoteIndex::new(tracker, builder, &config).0;
+
+        // unchanged notes take their terms from the cache, changed ones are read again
+        assert!(contains(&index, "unchanged", "tampered"));
+        assert!(contains(&index, "changed", "other words"));
+        assert!(!contains(&index, "changed", "tampered"));
+
+        // file events keep the full-text index up to date
+        std::fs::write(tmp.join("Unchanged.md"), "Now changed.").unwrap();
+        std::fs::remove_file(tmp.join("Changed.md")).unwrap();
+        std::thread::sleep(std::time::Duration::from_secs(2));
+        index.handle_file_events().unwrap();
+
+        assert!(contains(&index, "unchanged", "now"));
+        assert!(!index.text.documents().contains_key("changed"));
+
+        // without caching the full text, an old cache file is removed
+        config.cache_full_text = false;
+        index.cache_full_text = false;
+        index.save().unwrap();
+        assert!(!tmp.join("rucola_text_index.toml").exists());
+
+        // and all notes are read again
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let index = NoteIndex::new(tracker, builder, &config).0;
+        assert!(contains(&index, "unchanged", "now changed"));
+    }
+
+    #[test]
+    fn test_parallel_map() {
+        let items = (0..1000).collect_vec();
+        let mut reports = vec![];
+
+        let results = parallel_map(&items, |i| i * 2, |done, total| reports.push((done, total)));
+
+        assert_eq!(results.into_iter().sorted().collect_vec(), (0..1000).map(|i| i * 2).collect_vec());
+        assert_eq!(reports.last(), Some(&(1000, 1000)));
+        assert!(parallel_map(&Vec::<usize>::new(), |i| *i, |_, _| panic!("Nothing to report.")).is_empty());
+    }
+
+    #[test]
+    fn test_index_progress() {
+        let config = crate::Config{
+            vault_path: Some(std::env::current_dir().unwrap().join("tests")),
+            cache_index: false,
+            ..Default::default()
+        };
+
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let mut reports = vec![];
+        let (index, errors) = NoteIndex::new_with_progress(tracker, builder, &config, |step, done, total| {
+            reports.push((step.to_owned(), done, total))
+        });
+
+        assert_eq!(index.inner.len(), 13);
+        assert!(errors.is_empty());
+        assert!(reports.contains(&("Indexing notes...".to_owned(), 13, 13)));
+        assert!(reports.contains(&("Creating HTML files...".to_owned(), 13, 13)));
+    }
 }
@@ -1,18 +1,43 @@
 mod note;
+pub use note::Heading;
 pub use note::Note;
+pub use note::Task;
 
 mod note_statistics;
 pub use note_statistics::EnvironmentStats;
 pub use note_statistics::NoteColumn;
+pub use note_statistics::SortKey;
 
 mod filter;
 pub use filter::Filter;
+pub use filter::Highlights;
 pub use filter::TagMatch;
 
+mod query;
+pub use query::Query;
+
 mod index;
 pub use index::NoteIndex;
 pub use index::NoteIndexContainer;
 
+mod text_index;
+pub use text_index::TextIndex;
+
+mod full_text;
+pub use full_text::FullTextQuery;
+pub use full_text::TextMatch;
+
+mod date_condition;
+pub use date_condition::DateCondition;
+pub use date_condition::DateField;
+
+mod completion;
+pub use completion::filter_completions;
+
+mod count_condition;
+pub use count_condition::CountCondition;
+pub use count_condition::CountField;
+
 use unicode_normalization::UnicodeNormalization;
 
 use crate::error;
@@ -44,6 +69,151 @@ pub fn name_to_id(name: &str) -> String {
         .replace(".md", "")
 }
 
+/// Turns a link, possibly qualified by (part of) the path of its target, into an id in the same way as `name_to_id`, but keeps the folders:
+///  - everything after the first #, including the #, is ignored
+///  - the last segment is converted by `name_to_id`
+///  - all folders in front of it are turned to lowercase, with spaces replaced by dashes
+///  - empty segments and leading slashes are removed
+/// ```
+///  assert_eq!(link_to_id("Projects/Index#Goals"), "projects/index");
+///  assert_eq!(link_to_id("/Math/Lie Theory.md"), "math/lie-theory");
+///  assert_eq!(link_to_id("Lie Theory"), "lie-theory");
+/// ```
+pub fn link_to_id(link: &str) -> String {
+    let link = link.nfc().collect::<String>();
+    let link = link.split('#').next().unwrap_or_default();
+
+    let mut segments = link
+        .split('/')
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .collect::<Vec<_>>();
+
+    let name = segments.pop().map(name_to_id).unwrap_or_default();
+
+    segments
+        .into_iter()
+        .map(|folder| folder.to_lowercase().replace(' ', "-"))
+        .chain(std::iter::once(name))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Turns a folder within the vault into the beginning of the ids of all notes in it, converting every segment like the folders in `link_to_id`.
+/// ```
+///  assert_eq!(folder_to_id("Math/Lie Theory/"), "math/lie-theory");
+///  assert_eq!(folder_to_id("/"), "");
+/// ```
+pub fn folder_to_id(folder: &str) -> String {
+    folder
+        .nfc()
+        .collect::<String>()
+        .split('/')
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .map(|folder| folder.to_lowercase().replace(' ', "-"))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Turns the url of a markdown link into the id of its target, keeping relative paths intact, so they can later be resolved against the folder of the linking note:
+///  - the url is percent-decoded and everything after the first #, including the #, is ignored
+///  - `.` segments are removed and `..` segments cancel out preceding folders where possible
+///  - the remaining segments are converted as in `link_to_id`
+///  - relative urls start with `./` or `../`, while urls starting with a slash are relative to the vault and lose it
+///
+/// Returns `None` for urls with a scheme like `https://` and for urls of files with an extension other than `.md`, like images, which do not point to notes.
+/// ```
+///  assert_eq!(markdown_link_to_id("../Math/Chart.md#Definition"), Some("../math/chart".to_owned()));
+///  assert_eq!(markdown_link_to_id("Lie%20Group.md"), Some("./lie-group".to_owned()));
+///  assert_eq!(markdown_link_to_id("/Math/Chart"), Some("math/chart".to_owned()));
+///  assert_eq!(markdown_link_to_id("files/report.pdf"), None);
+/// ```
+pub fn markdown_link_to_id(url: &str) -> Option<String> {
+    let target = url.split('#').next().unwrap_or_default();
+
+    // Urls with a scheme point outside of the vault
+    if target
+        .split_once(':')
+        .is_some_and(|(scheme, _)| !scheme.is_empty() && scheme.chars().all(char::is_alphanumeric))
+    {
+        return None;
+    }
+
+    let target = percent_encoding::percent_decode_str(target).decode_utf8_lossy();
+
+    // links to headings within the same note have no target
+    if target.is_empty() {
+        return Some(String::new());
+    }
+
+    // Links to other files than notes are no note links
+    if std::path::Path::new(target.rsplit('/').next().unwrap_or_default())
+        .extension()
+        .is_some_and(|extension| !extension.eq_ignore_ascii_case("md"))
+    {
+        return None;
+    }
+
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in target.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." if segments.last().is_some_and(|last| *last != "..") => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+
+    let id = link_to_id(&segments.join("/"));
+
+    Some(if target.starts_with('/') || id.starts_with("../") {
+        id
+    } else {
+        format!("./{}", id)
+    })
+}
+
+/// Checks whether the given link id, as created by `markdown_link_to_id`, is relative to the folder of the linking note.
+pub fn is_relative_link(link_id: &str) -> bool {
+    link_id.starts_with("./") || link_id.starts_with("../")
+}
+
+/// Turns the path of a note into its id, which is the id of its path relative to the vault as given by `link_to_id`.
+/// If the path does not lie within the vault, only the file name is used.
+pub fn path_to_id(path: &std::path::Path, vault_path: &std::path::Path) -> String {
+    let relative = path
+        .strip_prefix(vault_path)
+        .map(|relative| relative.to_path_buf())
+        .or_else(|_| {
+            vault_path
+                .canonicalize()
+                .map_err(|_| ())
+                .and_then(|vault_path| {
+                    path.strip_prefix(vault_path)
+                        .map(|relative| relative.to_path_buf())
+                        .map_err(|_| ())
+                })
+        })
+        .unwrap_or_else(|_| path.file_name().map(std::path::PathBuf::from).unwrap_or_default());
+
+    link_to_id(
+        &relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
+/// Turns the text of a heading or a heading fragment of a link into the anchor used for it in the generated HTML.
+/// ```
+///  assert_eq!(heading_to_slug("Another Heading"), "another-heading");
+///  assert_eq!(heading_to_slug("Ticks aren't in"), "ticks-arent-in");
+/// ```
+pub fn heading_to_slug(heading: &str) -> String {
+    comrak::Anchorizer::new().anchorize(heading.nfc().collect())
+}
+
 /// Converts a path to the name of the file, removing the file extension.
 pub fn path_to_name(path: &std::path::Path) -> Result<String, error::RucolaError> {
     path.file_stem()
@@ -99,6 +269,78 @@ mod tests {
         assert_eq!(name_to_id("Monthlies/monthly-06-26"), "monthly-06-26");
     }
 
+    #[test]
+    fn test_link_id_conversion() {
+        assert_eq!(link_to_id("Lie Theory#Definition"), "lie-theory");
+        assert_eq!(link_to_id("Lie Theory.md"), "lie-theory");
+        assert_eq!(link_to_id("Math/Lie Theory.md"), "math/lie-theory");
+        assert_eq!(link_to_id("/Math/Algebra/Lie Theory"), "math/algebra/lie-theory");
+        assert_eq!(link_to_id("./Math Stuff/lie-theory"), "math-stuff/lie-theory");
+        assert_eq!(link_to_id("#Definition"), "");
+    }
+
+    #[test]
+    fn test_folder_id_conversion() {
+        assert_eq!(folder_to_id("Math/Lie Theory/"), "math/lie-theory");
+        assert_eq!(folder_to_id("./v1.2"), "v1.2");
+        assert_eq!(folder_to_id("/"), "");
+    }
+
+    #[test]
+    fn test_markdown_link_id_conversion() {
+        assert_eq!(
+            markdown_link_to_id("../Math/Chart.md"),
+            Some("../math/chart".to_owned())
+        );
+        assert_eq!(
+            markdown_link_to_id("../math/Chart#Definition"),
+            Some("../math/chart".to_owned())
+        );
+        assert_eq!(
+            markdown_link_to_id("Lie%20Group.md"),
+            Some("./lie-group".to_owned())
+        );
+        assert_eq!(
+            markdown_link_to_id("./Math%20Stuff/../Topology.md"),
+            Some("./topology".to_owned())
+        );
+        assert_eq!(
+            markdown_link_to_id("../../notes/K%C3%B6rper.md"),
+            Some("../../notes/körper".to_owned())
+        );
+        assert_eq!(markdown_link_to_id("/Math/Chart"), Some("math/chart".to_owned()));
+        assert_eq!(markdown_link_to_id("#Definition"), Some(String::new()));
+        assert_eq!(markdown_link_to_id("https://example.com/page"), None);
+        assert_eq!(markdown_link_to_id("mailto:someone@example.com"), None);
+        assert_eq!(markdown_link_to_id("files/report.pdf"), None);
+        assert_eq!(markdown_link_to_id("../images/Chart.png#center"), None);
+        assert_eq!(markdown_link_to_id("data.csv"), None);
+        assert_eq!(
+            markdown_link_to_id("Notes/Chart.MD"),
+            Some("./notes/chart".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_path_id_conversion() {
+        let vault = std::path::Path::new("/home/user/vault");
+        assert_eq!(
+            path_to_id(
+                std::path::Path::new("/home/user/vault/Projects/Index.md"),
+                vault
+            ),
+            "projects/index"
+        );
+        assert_eq!(
+            path_to_id(std::path::Path::new("/home/user/vault/Lie Theory.md"), vault),
+            "lie-theory"
+        );
+        assert_eq!(
+            path_to_id(std::path::Path::new("/elsewhere/Lie Theory.md"), vault),
+            "lie-theory"
+        );
+    }
+
     #[test]
     fn test_id_conversion_unicode() {
         // Composed form "ö".
@@ -124,6 +366,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_heading_slugs() {
+        assert_eq!(heading_to_slug("Definition"), "definition");
+        assert_eq!(heading_to_slug("definition"), "definition");
+        assert_eq!(heading_to_slug("Another Heading"), "another-heading");
+        assert_eq!(heading_to_slug("another-heading"), "another-heading");
+        assert_eq!(heading_to_slug("Ticks aren't in"), "ticks-arent-in");
+    }
+
     #[test]
     fn test_path_name_conversion() {
         assert_eq!(
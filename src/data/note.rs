@@ -1,5 +1,5 @@
 use ratatui::{prelude::*, widgets::*};
-use std::{fmt::Debug, fs, path, time};
+use std::{collections::BTreeMap, fmt::Debug, fs, path, time};
 
 use itertools::Itertools;
 
@@ -14,8 +14,16 @@ pub struct Note {
     pub name: String,
     /// All tags contained at any part of the note.
     pub tags: Vec<String>,
+    /// Alternative names of the note specified in the YAML frontmatter, under which links can refer to it.
+    #[serde(default)]
+    pub aliases: Vec<String>,
     /// All links contained within the note - no external (e.g. web) links.
+    /// Markdown links relative to the folder of this note start with `./` or `../`.
     pub links: Vec<String>,
+    /// All links pointing to a heading, as pairs of the target id and the heading fragment after the `#`.
+    /// Links to headings within this note itself have an empty target id.
+    #[serde(default)]
+    pub heading_links: Vec<(String, String)>,
     /// The number of words.
     pub words: usize,
     /// The number of characters.
@@ -24,8 +32,137 @@ pub struct Note {
     pub path: path::PathBuf,
     /// The date and time when the note was last modified.
     pub last_modification: Option<time::SystemTime>,
+    /// The date and time when the note was created, taken from the `created` or `date` property of the YAML frontmatter or, if there is none, from the file system.
+    #[serde(default)]
+    pub created: Option<time::SystemTime>,
+    /// The size of the file in bytes when the note was read, used to validate cached notes.
+    #[serde(default)]
+    pub size: u64,
     /// Whether or not the note contains (valid) YAML frontmatter. If it does, this is the index of the beginning of the actual content.
     pub yaml_frontmatter: Option<usize>,
+    /// The outline of all headings in the note, in order of appearance.
+    #[serde(default)]
+    pub headings: Vec<Heading>,
+    /// All further properties specified in the YAML frontmatter, apart from title and tags.
+    #[serde(default)]
+    pub properties: BTreeMap<String, Property>,
+    /// All task list items (checkboxes) in the note, in order of appearance.
+    #[serde(default)]
+    pub tasks: Vec<Task>,
+}
+
+/// A single heading within a note.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Heading {
+    /// The level of the heading, from 1 to 6.
+    pub level: u8,
+    /// The plain text content of the heading.
+    pub text: String,
+    /// The anchor of this heading, unique within the note and identical to the one used in the generated HTML.
+    pub slug: String,
+}
+
+/// A single task list item, i.e. a list item starting with a checkbox like `- [ ]`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Task {
+    /// The line of the note file the task starts in, beginning at 1.
+    pub line: usize,
+    /// The plain text of the task, without the due date.
+    pub text: String,
+    /// Whether the checkbox is checked.
+    pub done: bool,
+    /// The date specified with `@due(YYYY-MM-DD)` within the task, if any.
+    pub due: Option<chrono::NaiveDate>,
+}
+
+impl Task {
+    /// Extracts a due date of the form `@due(YYYY-MM-DD)` from the given task text.
+    /// Returns the text with the due date removed and the date, if one was found and valid.
+    fn split_due(text: &str) -> (String, Option<chrono::NaiveDate>) {
+        text.split_once("@due(")
+            .and_then(|(before, rest)| {
+                let (date, after) = rest.split_once(')')?;
+                let date = chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()?;
+                Some((
+                    format!("{} {}", before.trim_end(), after.trim_start())
+                        .trim()
+                        .to_owned(),
+                    Some(date),
+                ))
+            })
+            .unwrap_or_else(|| (text.trim().to_owned(), None))
+    }
+}
+
+/// A typed value of a property specified in the YAML frontmatter of a note.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum Property {
+    /// A boolean value such as `true` or `false`.
+    Bool(bool),
+    /// A whole number.
+    Integer(i64),
+    /// A floating point number.
+    Real(f64),
+    /// Any other scalar value, including dates.
+    Text(String),
+    /// A list of values.
+    List(Vec<Property>),
+}
+
+impl Property {
+    /// Converts a YAML node into a property, if it is of a supported type.
+    /// Nested maps and null values are not supported and return `None`.
+    fn from_yaml(yaml: &yaml_rust::Yaml) -> Option<Self> {
+        match yaml {
+            yaml_rust::Yaml::Boolean(b) => Some(Self::Bool(*b)),
+            yaml_rust::Yaml::Integer(i) => Some(Self::Integer(*i)),
+            yaml_rust::Yaml::Real(r) => r.parse().ok().map(Self::Real),
+            yaml_rust::Yaml::String(s) => Some(Self::Text(s.to_owned())),
+            yaml_rust::Yaml::Array(list) => {
+                Some(Self::List(list.iter().flat_map(Self::from_yaml).collect()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks whether this property matches the given value.
+    /// Comparison ignores case and treats dashes like spaces, just as tags do.
+    /// A list matches if any of its entries matches.
+    pub fn matches(&self, value: &str) -> bool {
+        match self {
+            Self::List(list) => list.iter().any(|entry| entry.matches(value)),
+            _ => {
+                self.to_string().to_lowercase().replace('-', " ")
+                    == value.to_lowercase().replace('-', " ")
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Property {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(b) => write!(f, "{}", b),
+            Self::Integer(i) => write!(f, "{}", i),
+            Self::Real(r) => write!(f, "{}", r),
+            Self::Text(s) => write!(f, "{}", s),
+            Self::List(list) => write!(f, "{}", list.iter().join(", ")),
+        }
+    }
+}
+
+/// The data extracted from the YAML frontmatter of a note.
+#[derive(Debug, Default)]
+struct Frontmatter {
+    /// The title specified in the frontmatter, if any.
+    title: Option<String>,
+    /// The tags specified in the frontmatter, hash included.
+    tags: Vec<String>,
+    /// The aliases specified in the frontmatter.
+    aliases: Vec<String>,
+    /// All other properties.
+    properties: BTreeMap<String, Property>,
 }
 
 impl Note {
@@ -33,9 +170,10 @@ impl Note {
     pub fn from_path(path: &path::Path) -> error::Result<Self> {
         // Open the file.
         let content = fs::read_to_string(path)?;
+        let metadata = path.metadata().ok();
 
         // Attempt to identify YAML frontmatter
-        let (title, tags, begin_content) =
+        let (frontmatter, begin_content) =
         // File needs to start with three dashes.
         if content.starts_with("---\n") {
             // Then search for the next three dashes.
@@ -45,22 +183,22 @@ impl Note {
                 // Take everything in between
                 let possible_frontmatter = content.split_at(break_position).0;
                 // Attempt to parse it as YAML.
-                if let Ok((title, tags)) =
+                if let Ok(frontmatter) =
                     Self::parse_yaml(possible_frontmatter.trim_start_matches("---\n"))
                 {
                     // If it worked, return the parsed data and the start of the actual note.
-                    (title, tags, Some(break_position + 5))
+                    (frontmatter, Some(break_position + 5))
                 } else {
                     // Fail case: Parsing failed.
-                    (None, Vec::new(), None)
+                    (Frontmatter::default(), None)
                 }
             } else {
                 // Fail case: File has no second ---.
-                (None, Vec::new(), None)
+                (Frontmatter::default(), None)
             }
         } else {
             // Fail case: File doesn't start with ---.
-            (None, Vec::new(), None)
+            (Frontmatter::default(), None)
         };
 
         // Parse markdown into AST
@@ -71,23 +209,105 @@ impl Note {
             &comrak::Options {
                 extension: comrak::ExtensionOptions::builder()
                     .wikilinks_title_after_pipe(true)
+                    .tasklist(true)
                     .build(),
                 ..Default::default()
             },
         );
 
-        // Parse YAML.
+        // The number of lines taken up by the frontmatter, to convert line numbers of the AST to lines in the file.
+        let frontmatter_lines = content
+            .split_at(begin_content.unwrap_or(0))
+            .0
+            .matches('\n')
+            .count();
+
+        // Collect the target ids and heading fragments of all links, both from wikilinks and markdown links.
+        // Markdown links are relative to the folder of this note, links to other websites are skipped.
+        let targets = root
+            .descendants()
+            .flat_map(|node| match &node.data.borrow().value {
+                comrak::nodes::NodeValue::WikiLink(link) => {
+                    let fragment = link.url.split_once('#').map(|(_, fragment)| fragment);
+                    Some((
+                        super::link_to_id(&link.url),
+                        fragment.unwrap_or_default().to_owned(),
+                    ))
+                }
+                comrak::nodes::NodeValue::Link(link) => {
+                    let fragment = link.url.split_once('#').map(|(_, fragment)| fragment);
+                    super::markdown_link_to_id(&link.url).map(|id| {
+                        (
+                            id,
+                            percent_encoding::percent_decode_str(fragment.unwrap_or_default())
+                                .decode_utf8_lossy()
+                                .to_string(),
+                        )
+                    })
+                }
+                _ => None,
+            })
+            .collect_vec();
+
+        // Create the outline, using one anchorizer for the whole document to keep the slugs unique.
+        let mut anchorizer = comrak::Anchorizer::new();
+        let headings = root
+            .descendants()
+            .flat_map(|node| match &node.data.borrow().value {
+                comrak::nodes::NodeValue::Heading(heading) => {
+                    let mut text = Vec::new();
+                    comrak::html::collect_text(node, &mut text);
+                    let text = String::from_utf8_lossy(&text).to_string();
+                    Some(Heading {
+                        level: heading.level,
+                        slug: anchorizer.anchorize(text.clone()),
+                        text,
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+
+        // Collect all task list items with their state, line and due date.
+        let tasks = root
+            .descendants()
+            .flat_map(|node| {
+                let data = node.data.borrow();
+                match &data.value {
+                    comrak::nodes::NodeValue::TaskItem(symbol) => {
+                        // Only take the text of the first paragraph, not of nested lists.
+                        let mut text = Vec::new();
+                        if let Some(paragraph) = node.first_child() {
+                            comrak::html::collect_text(paragraph, &mut text);
+                        }
+                        let (text, due) = Task::split_due(&String::from_utf8_lossy(&text));
+                        Some(Task {
+                            line: data.sourcepos.start.line + frontmatter_lines,
+                            text,
+                            done: symbol.is_some(),
+                            due,
+                        })
+                    }
+                    _ => None,
+                }
+            })
+            .collect();
 
         Ok(Self {
             // Name: Check if there was one specified in the YAML frontmatter.
             // If not, get the name from the path.
-            display_name: title.unwrap_or(super::path_to_name(path)?),
+            display_name: frontmatter.title.unwrap_or(super::path_to_name(path)?),
             // File name: Get it from the path.
             name: super::path_to_name(path)?,
             // Path: Already given - convert to owned version.
             path: path.canonicalize().unwrap_or(path.to_path_buf()),
             // Modification: Can be read from the metadata of the path.
-            last_modification: path.metadata().and_then(|m| m.modified()).ok(),
+            last_modification: metadata.as_ref().and_then(|m| m.modified().ok()),
+            // Creation: Prefer the frontmatter, as file systems do not keep it when copying or syncing.
+            created: Self::parse_creation(&frontmatter.properties)
+                .or_else(|| metadata.as_ref().and_then(|m| m.created().ok())),
+            // Size: Can also be read from the metadata.
+            size: metadata.map(|m| m.len()).unwrap_or_default(),
             // Tags: Go through all text nodes in the AST, split them at whitespace and look for those starting with a hash.
             // Finally, append tags specified in the YAML frontmatter.
             tags: root
@@ -100,22 +320,20 @@ impl Note {
                         .collect_vec(),
                     _ => vec![],
                 })
-                .chain(tags)
+                .chain(frontmatter.tags)
                 .collect(),
-            // Links: Go through all wikilinks in the syntax tree and map them
-            links: root
-                .descendants()
-                .flat_map(|node| match &node.data.borrow().value {
-                    comrak::nodes::NodeValue::WikiLink(link) => Some(super::name_to_id(&link.url)),
-                    comrak::nodes::NodeValue::Link(link) => {
-                        if !link.url.contains('/') {
-                            Some(super::name_to_id(&link.url))
-                        } else {
-                            None
-                        }
-                    }
-                    _ => None,
-                })
+            // Aliases: Already parsed from the YAML frontmatter.
+            aliases: frontmatter.aliases,
+            // Links: Map all link urls to ids, skipping links to headings within this note.
+            links: targets
+                .iter()
+                .map(|(id, _fragment)| id.to_owned())
+                .filter(|id| !id.is_empty())
+                .collect(),
+            // Heading links: Keep all links that have a fragment.
+            heading_links: targets
+                .into_iter()
+                .filter(|(_id, fragment)| !fragment.is_empty())
                 .collect(),
             // Words: Split at whitespace, grouping multiple consecutive instances of whitespace together.
             // See definition of `split_whitespace` for criteria.
@@ -124,6 +342,12 @@ impl Note {
             characters: content.len(),
             // YAML: We already set this bool.
             yaml_frontmatter: begin_content,
+            // Headings: Already collected above.
+            headings,
+            // Properties: Already parsed from the YAML frontmatter.
+            properties: frontmatter.properties,
+            // Tasks: Already collected above.
+            tasks,
         })
     }
 
@@ -183,8 +407,52 @@ impl Note {
         Table::new(stats_rows, stats_widths).column_spacing(1)
     }
 
-    /// Takes a str that possibly contains YAML frontmatter and attempts to parse it into a title and a list of tags.
-    fn parse_yaml(yaml: &str) -> Result<(Option<String>, Vec<String>), error::RucolaError> {
+    /// Checks whether the file at the given path still has the same modification time and size as when this note was read from it.
+    /// If not (or if the file cannot be accessed), the note needs to be read again.
+    pub fn is_up_to_date(&self, path: &path::Path) -> bool {
+        path.metadata().is_ok_and(|metadata| {
+            metadata.len() == self.size
+                && metadata.modified().ok().is_some()
+                && metadata.modified().ok() == self.last_modification
+        })
+    }
+
+    /// Reads the creation time from the `created` or `date` property, if there is one.
+    /// Accepts dates like `2025-03-14` and times like `2025-03-14 09:30` or `2025-03-14T09:30:00+01:00`, missing time zones are taken as local time.
+    fn parse_creation(properties: &BTreeMap<String, Property>) -> Option<time::SystemTime> {
+        ["created", "date"].iter().find_map(|key| {
+            let Some((_, Property::Text(text))) = properties
+                .iter()
+                .find(|(other_key, _)| other_key.to_lowercase() == *key)
+            else {
+                return None;
+            };
+            let text = text.trim();
+
+            if let Ok(time) = chrono::DateTime::parse_from_rfc3339(text) {
+                return Some(time.into());
+            }
+            [
+                "%Y-%m-%dT%H:%M:%S",
+                "%Y-%m-%d %H:%M:%S",
+                "%Y-%m-%dT%H:%M",
+                "%Y-%m-%d %H:%M",
+            ]
+            .iter()
+            .find_map(|format| chrono::NaiveDateTime::parse_from_str(text, format).ok())
+            .or_else(|| {
+                chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                    .ok()?
+                    .and_hms_opt(0, 0, 0)
+            })?
+            .and_local_timezone(chrono::Local)
+            .earliest()
+            .map(Into::into)
+        })
+    }
+
+    /// Takes a str that possibly contains YAML frontmatter and attempts to parse it into a title, a list of tags, a list of aliases and a map of all other properties.
+    fn parse_yaml(yaml: &str) -> Result<Frontmatter, error::RucolaError> {
         let docs = yaml_rust::YamlLoader::load_from_str(yaml)?;
         let doc = &docs
             .first()
@@ -227,7 +495,37 @@ impl Note {
             // Collect all tags in a vec.
             .collect_vec();
 
-        Ok((title, tags))
+        // Check if aliases were specified, either as a list or a single value.
+        let aliases = ["aliases", "alias"]
+            .iter()
+            .flat_map(|key| match Property::from_yaml(&doc[*key]) {
+                Some(Property::List(list)) => list.iter().map(|alias| alias.to_string()).collect(),
+                Some(alias) => vec![alias.to_string()],
+                None => vec![],
+            })
+            .collect_vec();
+
+        // Collect all other entries with string keys into the property map.
+        let properties = doc
+            .as_hash()
+            .map(|hash| {
+                hash.iter()
+                    .filter_map(|(key, value)| {
+                        key.as_str()
+                            .filter(|key| !["title", "tags", "aliases", "alias"].contains(key))
+                            .zip(Property::from_yaml(value))
+                            .map(|(key, value)| (key.to_owned(), value))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Frontmatter {
+            title,
+            tags,
+            aliases,
+            properties,
+        })
     }
 }
 
@@ -266,6 +564,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_headings() {
+        let note =
+            crate::data::Note::from_path(Path::new("./tests/common/notes/Windows.md")).unwrap();
+
+        assert_eq!(
+            note.headings,
+            vec![
+                super::Heading {
+                    level: 2,
+                    text: String::from("A heading"),
+                    slug: String::from("a-heading"),
+                },
+                super::Heading {
+                    level: 2,
+                    text: String::from("Another Heading"),
+                    slug: String::from("another-heading"),
+                },
+                super::Heading {
+                    level: 3,
+                    text: String::from("Another Heading"),
+                    slug: String::from("another-heading-1"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_heading_links() {
+        let tmp = testdir::testdir!();
+        let path = tmp.join("Lie Group.md");
+        std::fs::write(
+            &path,
+            "# Lie Group\nSee [[Manifold#Definition]], [[Atlas]] and [below](#Examples).\n\n## Examples\n",
+        )
+        .unwrap();
+
+        let note = crate::data::Note::from_path(&path).unwrap();
+
+        assert_eq!(
+            note.links,
+            vec![String::from("manifold"), String::from("atlas")]
+        );
+        assert_eq!(
+            note.heading_links,
+            vec![
+                (String::from("manifold"), String::from("Definition")),
+                (String::new(), String::from("Examples")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_relative_links() {
+        let tmp = testdir::testdir!();
+        let path = tmp.join("Lie Group.md");
+        std::fs::write(
+            &path,
+            "[Charts](../math/Chart.md), [Atlas](Atlas), [Smooth Maps](./Smooth%20Map.md#Definition), [Rust](https://www.rust-lang.org/), [Data](data.csv), ![Plot](plot.png) and [[Manifold]].\n",
+        )
+        .unwrap();
+
+        let note = crate::data::Note::from_path(&path).unwrap();
+
+        assert_eq!(
+            note.links,
+            vec![
+                String::from("../math/chart"),
+                String::from("./atlas"),
+                String::from("./smooth-map"),
+                String::from("manifold"),
+            ]
+        );
+        assert_eq!(
+            note.heading_links,
+            vec![(String::from("./smooth-map"), String::from("Definition"))]
+        );
+    }
+
     #[test]
     fn test_yaml_name() {
         let note =
@@ -297,4 +674,141 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_yaml_properties() {
+        use super::Property;
+
+        let note =
+            crate::data::Note::from_path(Path::new("./tests/common/notes/note25.md")).unwrap();
+
+        assert_eq!(note.properties.len(), 4);
+        assert_eq!(
+            note.properties.get("status"),
+            Some(&Property::Text(String::from("draft")))
+        );
+        assert_eq!(note.properties.get("priority"), Some(&Property::Integer(2)));
+        assert_eq!(
+            note.properties.get("published"),
+            Some(&Property::Bool(false))
+        );
+        assert_eq!(
+            note.properties.get("authors"),
+            Some(&Property::List(vec![
+                Property::Text(String::from("Ada Lovelace")),
+                Property::Text(String::from("Grace Hopper")),
+            ]))
+        );
+        assert!(!note.properties.contains_key("title"));
+        assert!(!note.properties.contains_key("tags"));
+
+        assert!(note.properties["status"].matches("Draft"));
+        assert!(note.properties["authors"].matches("grace-hopper"));
+        assert!(!note.properties["authors"].matches("grace"));
+        assert!(note.properties["priority"].matches("2"));
+    }
+
+    #[test]
+    fn test_tasks() {
+        let tmp = testdir::testdir!();
+        let path = tmp.join("Todo.md");
+        std::fs::write(
+            &path,
+            "---\ntitle: Todo\n---\n# Todo\n\n- [ ] Write thesis @due(2025-03-01)\n  - [x] Read *papers*\n- [X] Submit\n- No task\n",
+        )
+        .unwrap();
+
+        let note = crate::data::Note::from_path(&path).unwrap();
+
+        assert_eq!(
+            note.tasks,
+            vec![
+                super::Task {
+                    line: 6,
+                    text: "Write thesis".to_owned(),
+                    done: false,
+                    due: chrono::NaiveDate::from_ymd_opt(2025, 3, 1),
+                },
+                super::Task {
+                    line: 7,
+                    text: "Read papers".to_owned(),
+                    done: true,
+                    due: None,
+                },
+                super::Task {
+                    line: 8,
+                    text: "Submit".to_owned(),
+                    done: true,
+                    due: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_aliases() {
+        let tmp = testdir::testdir!();
+
+        let path = tmp.join("Special Orthogonal Group.md");
+        std::fs::write(
+            &path,
+            "---\naliases:\n  - SO(3)\n  - Rotation Group\n---\n# SO(3)\n",
+        )
+        .unwrap();
+        let note = crate::data::Note::from_path(&path).unwrap();
+        assert_eq!(note.aliases, vec!["SO(3)", "Rotation Group"]);
+        assert!(note.properties.is_empty());
+
+        let path = tmp.join("Atlas.md");
+        std::fs::write(&path, "---\nalias: Charts\n---\n# Atlas\n").unwrap();
+        let note = crate::data::Note::from_path(&path).unwrap();
+        assert_eq!(note.aliases, vec!["Charts"]);
+    }
+
+    #[test]
+    fn test_created() {
+        use chrono::TimeZone;
+
+        let tmp = testdir::testdir!();
+        let created = |frontmatter: &str| {
+            let path = tmp.join("Note.md");
+            std::fs::write(&path, format!("{frontmatter}# Note\n")).unwrap();
+            crate::data::Note::from_path(&path).unwrap().created
+        };
+
+        assert_eq!(
+            created("---\ncreated: 2025-03-14\n---\n"),
+            Some(
+                chrono::Local
+                    .with_ymd_and_hms(2025, 3, 14, 0, 0, 0)
+                    .unwrap()
+                    .into()
+            )
+        );
+        assert_eq!(
+            created("---\nDate: 2025-03-14 09:30\n---\n"),
+            Some(
+                chrono::Local
+                    .with_ymd_and_hms(2025, 3, 14, 9, 30, 0)
+                    .unwrap()
+                    .into()
+            )
+        );
+        assert_eq!(
+            created("---\ndate: 2024-01-01\ncreated: 2025-03-14T09:30:00Z\n---\n"),
+            Some(
+                chrono::Utc
+                    .with_ymd_and_hms(2025, 3, 14, 9, 30, 0)
+                    .unwrap()
+                    .into()
+            )
+        );
+
+        // without a valid date in the frontmatter, the file system is asked
+        let path = tmp.join("Note.md");
+        assert_eq!(
+            created("---\ncreated: yesterday\n---\n"),
+            path.metadata().unwrap().created().ok()
+        );
+    }
 }
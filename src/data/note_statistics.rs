@@ -1,7 +1,7 @@
 use crate::{data, ui};
-use rand::seq::SliceRandom;
+use itertools::Itertools;
 use ratatui::{prelude::*, widgets::*};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// A struct describing statistics to a note in relation to a containing environment.
 #[derive(Debug, Clone)]
@@ -21,6 +21,8 @@ pub struct NoteEnvStatistics {
     outlinks_global: usize,
     /// The amount of links originating from this note that do not have a valid target anywhere.
     broken_links: usize,
+    /// The parts of this note that matched the filter used to create the environment.
+    highlights: data::Highlights,
 }
 
 impl NoteEnvStatistics {
@@ -34,9 +36,67 @@ impl NoteEnvStatistics {
             outlinks_local: 0,
             outlinks_global: 0,
             broken_links: 0,
+            highlights: data::Highlights::default(),
         }
     }
 
+    /// Returns the value of this note in the column of the given sort key, to compare it with other notes.
+    fn sort_value(
+        &self,
+        index: &data::NoteIndex,
+        key: SortKey,
+        rng: &mut impl rand::RngExt,
+    ) -> SortValue {
+        let Some(note) = index.get(&self.id) else {
+            return SortValue::Number(0);
+        };
+        match key.column {
+            NoteColumn::Shuffle => SortValue::Number(rng.random::<u32>().into()),
+            NoteColumn::Name => {
+                SortValue::Text(self.id.rsplit('/').next().unwrap_or_default().to_owned())
+            }
+            NoteColumn::Folder => SortValue::Text(index.folder(&self.id).to_lowercase()),
+            NoteColumn::Words => SortValue::Number(note.words as i64),
+            NoteColumn::Chars => SortValue::Number(note.characters as i64),
+            NoteColumn::GlobalOutLinks => SortValue::Number(self.outlinks_global as i64),
+            NoteColumn::LocalOutLinks => SortValue::Number(self.outlinks_local as i64),
+            NoteColumn::GlobalInLinks => SortValue::Number(self.inlinks_global as i64),
+            NoteColumn::LocalInLinks => SortValue::Number(self.inlinks_local as i64),
+            NoteColumn::Score => SortValue::Number(self.match_score),
+            NoteColumn::Broken => SortValue::Number(self.broken_links as i64),
+            NoteColumn::LastModified => SortValue::Number(
+                // Get the file's modification time as seconds since epoch
+                note.last_modification
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs() as i64)
+                    // If no time since modification can be determined, use a value that will put this note at the end of the sorted list.
+                    .unwrap_or(if key.ascending { i64::MAX } else { i64::MIN }),
+            ),
+        }
+    }
+
+    /// Returns the display name of this note with the characters matched by the filter highlighted, followed by the matched tags and links.
+    fn name_line(&self, display_name: &str, styles: &ui::UiStyles) -> Line<'static> {
+        let mut spans = Vec::new();
+        for (highlighted, chunk) in &display_name
+            .chars()
+            .enumerate()
+            .chunk_by(|(position, _)| self.highlights.name.binary_search(position).is_ok())
+        {
+            let text = chunk.map(|(_, c)| c).collect::<String>();
+            spans.push(if highlighted {
+                Span::styled(text, styles.hotkey_style)
+            } else {
+                Span::raw(text)
+            });
+        }
+        for condition in &self.highlights.conditions {
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(condition.clone(), styles.hotkey_style));
+        }
+        Line::from(spans)
+    }
+
     /// Extracts a certain data point (given by the entry) from this NoteEnvStatistics and returns a formatted string of the specified length (or possibly longer) that contains it.
     /// The actual lenght will later be capped by the width of the column ratatui renders this in.
     fn to_data_string(
@@ -45,12 +105,13 @@ impl NoteEnvStatistics {
         note_entry: NoteColumn,
         min_width: usize,
     ) -> String {
+        let index = index.borrow();
         index
-            .borrow()
             .get(&self.id)
             .map(|note| match note_entry {
                 NoteColumn::Shuffle => " ".repeat(min_width),
                 NoteColumn::Name => note.display_name.clone(),
+                NoteColumn::Folder => format!("{:min_width$}", index.folder(&self.id)),
                 NoteColumn::Words => format!("{:min_width$}", note.words),
                 NoteColumn::Chars => format!("{:min_width$}", note.characters),
                 NoteColumn::GlobalOutLinks => format!("{:min_width$}", self.outlinks_global),
@@ -74,11 +135,52 @@ impl NoteEnvStatistics {
     }
 }
 
+/// The value of a note in a column, used to compare notes when sorting by it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum SortValue {
+    Number(i64),
+    Text(String),
+}
+
+/// A column to sort notes by, together with the direction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SortKey {
+    /// The column to sort by.
+    pub column: NoteColumn,
+    /// Whether to sort in ascending or descending order.
+    #[serde(default)]
+    pub ascending: bool,
+}
+
+impl SortKey {
+    /// Creates a sort key from the column and direction.
+    pub fn new(column: NoteColumn, ascending: bool) -> Self {
+        Self { column, ascending }
+    }
+
+    /// Returns the marker shown after the title of the given column if the notes are sorted by it.
+    /// It shows the direction, e.g. ` ↓`, and, if the notes are sorted by multiple columns, the position of the column, e.g. ` ↓2`.
+    pub fn marker(sorting: &[Self], column: NoteColumn) -> Option<String> {
+        let position = sorting.iter().position(|key| key.column == column)?;
+        let arrow = if sorting[position].ascending {
+            '↑'
+        } else {
+            '↓'
+        };
+        Some(if sorting.len() > 1 {
+            format!(" {arrow}{}", position + 1)
+        } else {
+            format!(" {arrow}")
+        })
+    }
+}
+
 /// Describes a column of the note table. Can be used to sort by it on in the config to specify which columns to show.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum NoteColumn {
     Shuffle,
     Name,
+    Folder,
     Words,
     Chars,
     GlobalOutLinks,
@@ -96,6 +198,7 @@ impl NoteColumn {
         let l = title.len() as u16;
         match self {
             NoteColumn::Name => Constraint::Min(25.max(l)),
+            NoteColumn::Folder => Constraint::Min(15.max(l)),
             NoteColumn::Shuffle
             | NoteColumn::Words
             | NoteColumn::Chars
@@ -114,6 +217,7 @@ impl NoteColumn {
         let key = match self {
             NoteColumn::Shuffle => 'S',
             NoteColumn::Name => 'A',
+            NoteColumn::Folder => 'F',
             NoteColumn::Words => 'W',
             NoteColumn::Chars => 'C',
             NoteColumn::GlobalOutLinks => 'O',
@@ -173,50 +277,31 @@ impl EnvironmentStats {
             .iter()
             .filter_map(|(id, note)| {
                 filter.apply(note, &index).map(|score| {
-                    (
-                        id.clone(),
-                        (NoteEnvStatistics::new_empty(id.clone(), score), note),
-                    )
+                    let mut stats = NoteEnvStatistics::new_empty(id.clone(), score);
+                    stats.highlights = filter.highlights(note, &index);
+                    (id.clone(), (stats, note))
                 })
             })
             .collect::<HashMap<_, _>>();
 
-        // Count links by iterating over unfiltered index
-        for (id, note) in index.inner.iter() {
-            // Remember if source is from within the environment.
-            let local_source = filtered_index.contains_key(id);
-            // Keep track of found local targets.
-            let mut local_targets = 0;
-            // Keep track of found targets.
-            let mut global_targets = 0;
-
-            // Then go over its links.
-            for link in &note.links {
-                // Check if target exists
-                if index.inner.contains_key(link) {
-                    // and increase count of valid targets if so.
-                    global_targets += 1;
-
-                    // Now check if target is local.
-                    if let Some((target, _)) = filtered_index.get_mut(link) {
-                        // Always count up global inlink count of target.
-                        target.inlinks_global += 1;
-                        // If id of source is also in filtered index, also count up local inlink count of target.
-                        if local_source {
-                            target.inlinks_local += 1;
-                        }
-                        // Since this target was in the environment, increment the counter.
-                        local_targets += 1;
-                    }
-                }
-            }
-            // If source was local, we are interested in its stats.
-            // Add the found local/global targets to the statistics (this could be an assignment).
-            if let Some((source, _)) = filtered_index.get_mut(id) {
-                source.outlinks_local += local_targets;
-                source.outlinks_global += global_targets;
-                source.broken_links = note.links.len() - global_targets;
-            }
+        // Count links using the link maps of the index, so only notes within the environment need to be visited.
+        let local_ids = filtered_index.keys().cloned().collect::<HashSet<_>>();
+        for (id, (stats, _)) in filtered_index.iter_mut() {
+            let outlinks = index.outlinks(id);
+            let inlinks = index.backlinks(id);
+
+            stats.outlinks_global = outlinks.len();
+            stats.outlinks_local = outlinks
+                .iter()
+                .filter(|target_id| local_ids.contains(*target_id))
+                .count();
+            stats.inlinks_global = inlinks.len();
+            stats.inlinks_local = inlinks
+                .iter()
+                .filter(|source_id| local_ids.contains(*source_id))
+                .count();
+            // Links without a valid target anywhere are broken.
+            stats.broken_links = index.broken_links(id);
         }
 
         Self {
@@ -233,7 +318,7 @@ impl EnvironmentStats {
             tag_count_total: filtered_index
                 .values()
                 .flat_map(|(_, stats)| &stats.tags)
-                .collect::<std::collections::HashSet<_>>()
+                .collect::<HashSet<_>>()
                 .len(),
             // Local-Local links: Check outgoing local links of all notes. Could also check incoming local links of all notes.
             local_local_links: filtered_index
@@ -276,60 +361,52 @@ impl EnvironmentStats {
         self.filtered_stats.get(index)
     }
 
-    /// Reverses the underlying vec
-    pub fn reverse_order(&mut self) {
-        self.filtered_stats.reverse();
-    }
-
-    /// Sorts the underlying vec
-    pub fn sort(&mut self, index: data::NoteIndexContainer, mode: NoteColumn, ascending: bool) {
-        // Always sort by name first
-        self.filtered_stats
-            .sort_by_cached_key(|env_stats| env_stats.id.clone());
-
-        // If the column to sort by is not name or shuffle, now sort by the actual column.
-        if mode != NoteColumn::Name && mode != NoteColumn::Shuffle {
-            // If sorting in reverse is desired, pre-reverse this, so when reversing again later, the list will still be sub-sorted by name ascendingly.
-            if !ascending {
-                self.filtered_stats.reverse();
-            }
-            // all others are usize and can be done in one thing
-            self.filtered_stats.sort_by_cached_key(|env_stats| {
-                if let Some(note) = index.borrow().get(&env_stats.id) {
-                    match mode {
-                        // This should not appear
-                        NoteColumn::Shuffle | NoteColumn::Name => 0,
-                        // These should appear
-                        NoteColumn::Words => note.words,
-                        NoteColumn::Chars => note.characters,
-                        NoteColumn::GlobalOutLinks => env_stats.outlinks_global,
-                        NoteColumn::LocalOutLinks => env_stats.outlinks_local,
-                        NoteColumn::GlobalInLinks => env_stats.inlinks_global,
-                        NoteColumn::LocalInLinks => env_stats.inlinks_local,
-                        NoteColumn::Score => env_stats.match_score as usize,
-                        NoteColumn::Broken => env_stats.broken_links,
-                        NoteColumn::LastModified => {
-                            // Get the file's modification time as seconds since epoch
-                            note.last_modification
-                                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                                .map(|d| d.as_secs() as usize)
-                                // If no time since modification can be determined, use a value that will put this note at the end of the sorted list.
-                                .unwrap_or(if ascending { usize::MAX } else { usize::MIN })
-                        }
-                    }
-                } else {
-                    0
-                }
+    /// Sorts the underlying vec by the given columns, where later columns only decide between notes that are equal in all earlier ones.
+    /// Notes that are equal in all columns are sorted by name and then by folder.
+    pub fn sort(&mut self, index: data::NoteIndexContainer, sorting: &[SortKey]) {
+        let index = index.borrow();
+        let mut rng = rand::rng();
+
+        // Compute the values to compare only once per note.
+        let mut entries = std::mem::take(&mut self.filtered_stats)
+            .into_iter()
+            .map(|env_stats| {
+                let values = sorting
+                    .iter()
+                    .map(|key| env_stats.sort_value(&index, *key, &mut rng))
+                    .collect::<Vec<_>>();
+                let name = env_stats
+                    .id
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or_default()
+                    .to_owned();
+                (values, name, env_stats)
             })
-        // if the column is shuffle, then shuffle the list
-        } else if mode == NoteColumn::Shuffle {
-            self.filtered_stats.shuffle(&mut rand::rng());
-        }
-
-        // Potentially reverse sorting
-        if !ascending {
-            self.filtered_stats.reverse();
-        }
+            .collect::<Vec<_>>();
+
+        entries.sort_by(|(values1, name1, stats1), (values2, name2, stats2)| {
+            values1
+                .iter()
+                .zip(values2)
+                .zip(sorting)
+                .map(|((value1, value2), key)| {
+                    if key.ascending {
+                        value1.cmp(value2)
+                    } else {
+                        value2.cmp(value1)
+                    }
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| name1.cmp(name2))
+                .then_with(|| stats1.id.cmp(&stats2.id))
+        });
+
+        self.filtered_stats = entries
+            .into_iter()
+            .map(|(_values, _name, env_stats)| env_stats)
+            .collect();
     }
 
     /// Returns the amount of notes in this environment.
@@ -338,25 +415,51 @@ impl EnvironmentStats {
     }
 
     /// Converts this environment to a table of rows with the (sorted) notes contained in it.
+    /// Columns the notes are sorted by are widened to make room for their marker in the header.
+    /// If any notes are marked, a narrow first column shows which ones.
     pub fn to_note_table(
         &self,
         index: data::NoteIndexContainer,
         styles: &ui::UiStyles,
         column_config: &[(String, NoteColumn)],
+        sorting: &[SortKey],
+        marked: &HashSet<String>,
     ) -> Table<'_> {
+        let show_marks = !marked.is_empty();
+
         // Calculate widths
-        let notes_table_widths = column_config
-            .iter()
-            .map(|(title, mode)| mode.to_width(title));
+        let notes_table_widths = show_marks
+            .then_some(Constraint::Length(1))
+            .into_iter()
+            .chain(column_config.iter().map(|(title, mode)| {
+                let marker = SortKey::marker(sorting, *mode).unwrap_or_default();
+                mode.to_width(&format!("{title}{}", " ".repeat(marker.chars().count())))
+            }));
 
         // Construct rows
         let notes_rows = self
             .filtered_stats
             .iter()
             .map(|note_env| {
-                Row::new(column_config.iter().map(|(title, mode)| {
-                    note_env.to_data_string(index.clone(), *mode, title.len())
-                }))
+                let mark = show_marks.then(|| {
+                    if marked.contains(&note_env.id) {
+                        Cell::from(Span::styled("*", styles.hotkey_style))
+                    } else {
+                        Cell::default()
+                    }
+                });
+                Row::new(
+                    mark.into_iter()
+                        .chain(column_config.iter().map(|(title, mode)| {
+                            let data = note_env.to_data_string(index.clone(), *mode, title.len());
+                            // names are highlighted to show why the note matched the filter
+                            if *mode == NoteColumn::Name {
+                                Cell::from(note_env.name_line(&data, styles))
+                            } else {
+                                Cell::from(data)
+                            }
+                        })),
+                )
                 .style(styles.text_style)
             })
             .collect::<Vec<Row>>();
@@ -517,6 +620,7 @@ mod tests {
             blinks: vec![],
             title: String::new(),
             full_text: None,
+            ..Default::default()
         };
 
         let env1 = EnvironmentStats::new_with_filter(&index, filter1);
@@ -555,6 +659,7 @@ mod tests {
             blinks: vec![],
             title: String::new(),
             full_text: None,
+            ..Default::default()
         };
         let env2 = EnvironmentStats::new_with_filter(&index, filter2);
 
@@ -601,6 +706,7 @@ mod tests {
             blinks: vec![],
             title: "operating".to_string(),
             full_text: None,
+            ..Default::default()
         };
         let env3 = EnvironmentStats::new_with_filter(&index, filter3);
 
@@ -636,6 +742,7 @@ mod tests {
             blinks: vec![("atlas".to_string(), true)],
             title: String::new(),
             full_text: None,
+            ..Default::default()
         };
         let env4 = EnvironmentStats::new_with_filter(&index, filter4);
 
@@ -671,6 +778,7 @@ mod tests {
             blinks: vec![("atlas".to_string(), true)],
             title: String::new(),
             full_text: None,
+            ..Default::default()
         };
         let env5 = EnvironmentStats::new_with_filter(&index, filter5);
 
@@ -681,4 +789,125 @@ mod tests {
         assert_eq!(env5.global_local_links, 10);
         assert_eq!(env5.broken_links, 1);
     }
+
+    #[test]
+    fn test_env_stats_sorting() {
+        let tmp = testdir::testdir!();
+        std::fs::create_dir_all(tmp.join("work")).unwrap();
+        std::fs::write(tmp.join("Atlas.md"), "[[Chart]] [[Manifold]]").unwrap();
+        std::fs::write(tmp.join("Chart.md"), "[[Manifold]] one two three").unwrap();
+        std::fs::write(tmp.join("Manifold.md"), "[[Chart]]").unwrap();
+        std::fs::write(tmp.join("Basis.md"), "one").unwrap();
+        std::fs::write(tmp.join("work/Basis.md"), "one two").unwrap();
+
+        let config = crate::Config {
+            vault_path: Some(tmp),
+            ..Default::default()
+        };
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let index = data::NoteIndex::new(tracker, builder, &config).0;
+        let index = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        let mut env = EnvironmentStats::new_with_filter(&index, data::Filter::default());
+        let mut sorted = |sorting: &[SortKey]| {
+            env.sort(index.clone(), sorting);
+            env.filtered_stats
+                .iter()
+                .map(|env_stats| env_stats.id.clone())
+                .collect::<Vec<_>>()
+        };
+
+        // ties are sorted by name and then by folder
+        assert_eq!(
+            sorted(&[SortKey::new(NoteColumn::LocalInLinks, false)]),
+            vec!["chart", "manifold", "atlas", "basis", "work/basis"]
+        );
+        assert_eq!(
+            sorted(&[
+                SortKey::new(NoteColumn::LocalInLinks, false),
+                SortKey::new(NoteColumn::Words, true),
+            ]),
+            vec!["manifold", "chart", "basis", "atlas", "work/basis"]
+        );
+        assert_eq!(
+            sorted(&[
+                SortKey::new(NoteColumn::Name, false),
+                SortKey::new(NoteColumn::Folder, false),
+            ]),
+            vec!["manifold", "chart", "work/basis", "basis", "atlas"]
+        );
+        assert_eq!(
+            sorted(&[]),
+            vec!["atlas", "basis", "work/basis", "chart", "manifold"]
+        );
+
+        let sorting = [
+            SortKey::new(NoteColumn::LocalInLinks, false),
+            SortKey::new(NoteColumn::Name, true),
+        ];
+        assert_eq!(
+            SortKey::marker(&sorting, NoteColumn::Name),
+            Some(" ↑2".to_string())
+        );
+        assert_eq!(SortKey::marker(&sorting, NoteColumn::Words), None);
+        assert_eq!(
+            SortKey::marker(&sorting[..1], NoteColumn::LocalInLinks),
+            Some(" ↓".to_string())
+        );
+    }
+
+    #[test]
+    fn test_env_stats_aliases() {
+        let tmp = testdir::testdir!();
+        std::fs::write(
+            tmp.join("Special Orthogonal Group.md"),
+            "---\naliases: [SO(3), Rotation Group]\n---\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.join("Lie Group.md"),
+            "[[SO(3)]], [[rotation group|rotations]] and [[Nonexistent]]",
+        )
+        .unwrap();
+
+        let config = crate::Config {
+            vault_path: Some(tmp),
+            ..Default::default()
+        };
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let index = data::NoteIndex::new(tracker, builder, &config).0;
+        let index = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        let env = EnvironmentStats::new_with_filter(&index, data::Filter::default());
+
+        assert_eq!(env.local_local_links, 2);
+        assert_eq!(env.broken_links, 1);
+    }
+    #[test]
+    fn test_env_stats_relative_links() {
+        let tmp = testdir::testdir!();
+        std::fs::create_dir_all(tmp.join("math")).unwrap();
+        std::fs::write(tmp.join("math/Chart.md"), "").unwrap();
+        std::fs::write(
+            tmp.join("Atlas.md"),
+            "[charts](math/Chart.md), [again](./math/Chart) and [missing](math/Atlas.md)",
+        )
+        .unwrap();
+
+        let config = crate::Config {
+            vault_path: Some(tmp),
+            ..Default::default()
+        };
+        let tracker = io::FileTracker::new(&config).unwrap();
+        let builder = io::HtmlBuilder::new(&config);
+        let index = data::NoteIndex::new(tracker, builder, &config).0;
+        let index = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        let env = EnvironmentStats::new_with_filter(&index, data::Filter::default());
+
+        assert_eq!(env.local_local_links, 2);
+        assert_eq!(env.broken_links, 1);
+    }
 }
@@ -0,0 +1,483 @@
+use crate::error;
+use itertools::Itertools;
+
+use super::filter::{property_value, split_condition};
+use super::{CountCondition, CountField, DateCondition, DateField, FullTextQuery};
+
+/// A boolean combination of filter conditions, as typed into the filter area with `AND`, `OR`, `NOT` and parentheses.
+/// Conditions written next to each other without an operator have to hold all at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    /// A tag the note has to have, hash included.
+    Tag(String),
+    /// The id of a note the note has to link to.
+    Link(String),
+    /// The id of a note that has to link to the note.
+    Backlink(String),
+    /// A frontmatter property the note has to have, as lowercase key and value.
+    /// An empty value, written as `*`, matches any note that has the property at all.
+    Property(String, String),
+    /// A range the modification or creation time of the note has to lie in.
+    Date(DateCondition),
+    /// A range a statistic of the note like its number of words has to lie in.
+    Count(CountCondition),
+    /// The id of a folder the note has to lie in, possibly within subfolders.
+    Folder(String),
+    /// A search in the full text of the note.
+    FullText(FullTextQuery),
+    /// Words to be fuzzy matched with the title of the note.
+    Title(String),
+    /// A query that must not hold.
+    Not(Box<Query>),
+    /// Queries that all have to hold.
+    And(Vec<Query>),
+    /// Queries of which at least one has to hold.
+    Or(Vec<Query>),
+}
+
+impl Query {
+    /// Checks if the given filter string uses any of the boolean operators or parentheses and should thus be parsed as a query.
+    pub fn is_query(filter_string: &str) -> bool {
+        tokenize(filter_string).any(|token| matches!(token, "AND" | "OR" | "NOT" | "(" | ")"))
+    }
+
+    /// Returns all full-text searches within this query that are not negated, i.e. those that can contribute to a match.
+    pub fn full_text_queries(&self) -> Vec<&FullTextQuery> {
+        self.positive_conditions()
+            .into_iter()
+            .filter_map(|query| match query {
+                Query::FullText(text) => Some(text),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns all single conditions within this query that are not negated, i.e. those that can contribute to a match.
+    pub fn positive_conditions(&self) -> Vec<&Query> {
+        match self {
+            Query::Not(_) => Vec::new(),
+            Query::And(queries) | Query::Or(queries) => queries
+                .iter()
+                .flat_map(|query| query.positive_conditions())
+                .collect(),
+            condition => vec![condition],
+        }
+    }
+
+    /// Parses a query from the given filter string.
+    /// `NOT` binds strongest, followed by `AND` and finally `OR`, so `#os AND NOT >windows OR #linux` is read as `(#os AND (NOT >windows)) OR #linux`.
+    pub fn parse(filter_string: &str) -> error::Result<Self> {
+        let tokens = tokenize(filter_string).collect::<Vec<_>>();
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+        };
+
+        let query = parser.parse_or()?;
+
+        // a sequence only ends early at a closing parenthesis without an opening one
+        if parser.peek().is_some() {
+            return Err(error::RucolaError::FilterParseError(
+                "Unmatched ')'.".to_string(),
+            ));
+        }
+
+        Ok(query)
+    }
+}
+
+/// Splits a filter string into words, operators and parentheses.
+/// Parentheses are only split off at the start and end of words, so titles like `SO(3)` stay intact.
+/// Phrases in double quotes and regular expressions between slashes at the start of a word (or right after a `|`) are kept in one word, including their whitespace.
+fn tokenize(filter_string: &str) -> impl Iterator<Item = &str> {
+    let mut words = Vec::new();
+    let mut rest = filter_string.trim_start();
+    while !rest.is_empty() {
+        // find the end of the word, skipping over a phrase or regular expression at its start
+        let opening = rest.len() - rest.trim_start_matches('(').len();
+        let section_start = opening + usize::from(rest[opening..].starts_with('|'));
+        let section_end = rest[section_start..]
+            .starts_with(['"', '/'])
+            .then(|| super::full_text::section_end(&rest[section_start..]))
+            .flatten()
+            .map(|end| section_start + end)
+            .unwrap_or(section_start);
+        let end = rest[section_end..]
+            .find(char::is_whitespace)
+            .map(|position| section_end + position)
+            .unwrap_or(rest.len());
+
+        words.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+
+    words.into_iter().flat_map(|word| {
+        let opening = word.len() - word.trim_start_matches('(').len();
+        let rest = &word[opening..];
+        // closing parentheses that close an opening one within the word belong to it
+        let trailing = rest.len() - rest.trim_end_matches(')').len();
+        let unclosed = rest
+            .matches('(')
+            .count()
+            .saturating_sub(rest.matches(')').count() - trailing);
+        let closing = trailing.saturating_sub(unclosed);
+        let inner = &rest[..rest.len() - closing];
+
+        std::iter::repeat_n("(", opening)
+            .chain((!inner.is_empty()).then_some(inner))
+            .chain(std::iter::repeat_n(")", closing))
+    })
+}
+
+/// A recursive descent parser over the tokens of a filter string.
+struct Parser<'a> {
+    tokens: Vec<&'a str>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Returns the current token without consuming it.
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.position).copied()
+    }
+
+    /// Consumes and returns the current token.
+    fn next(&mut self) -> Option<&'a str> {
+        let token = self.peek();
+        self.position += 1;
+        token
+    }
+
+    /// Checks if the given token ends a sequence of conditions.
+    fn ends_sequence(token: &str) -> bool {
+        matches!(token, "OR" | ")")
+    }
+
+    /// Creates the error for a condition missing at the current position.
+    fn missing_condition(&self) -> error::RucolaError {
+        let previous = self
+            .position
+            .checked_sub(1)
+            .and_then(|position| self.tokens.get(position))
+            .filter(|token| matches!(**token, "AND" | "OR" | "NOT"));
+
+        error::RucolaError::FilterParseError(match (previous, self.peek()) {
+            (Some(previous), _) => format!("Expected a condition after '{previous}'."),
+            (None, Some(next)) => format!("Expected a condition before '{next}'."),
+            (None, None) => "Expected a condition.".to_string(),
+        })
+    }
+
+    /// `or := and ("OR" and)*`
+    fn parse_or(&mut self) -> error::Result<Query> {
+        let mut operands = vec![self.parse_and()?];
+        while self.peek() == Some("OR") {
+            self.next();
+            operands.push(self.parse_and()?);
+        }
+
+        Ok(if operands.len() == 1 {
+            operands.remove(0)
+        } else {
+            Query::Or(operands)
+        })
+    }
+
+    /// `and := not (("AND")? not)*`
+    /// Consecutive title words are joined into a single title condition.
+    fn parse_and(&mut self) -> error::Result<Query> {
+        let mut operands: Vec<Query> = vec![];
+        let mut explicit = false;
+
+        loop {
+            match self.peek() {
+                None => break,
+                Some(token) if Self::ends_sequence(token) => break,
+                Some("AND") => {
+                    if operands.is_empty() || explicit {
+                        return Err(self.missing_condition());
+                    }
+                    self.next();
+                    explicit = true;
+                    continue;
+                }
+                _ => {}
+            }
+
+            let operand = self.parse_not()?;
+            match (operands.last_mut(), operand) {
+                // join title words written next to each other
+                (Some(Query::Title(title)), Query::Title(word)) if !explicit => {
+                    title.push_str(&word);
+                }
+                (_, operand) => operands.push(operand),
+            }
+            explicit = false;
+        }
+
+        if explicit {
+            return Err(self.missing_condition());
+        }
+
+        match operands.len() {
+            0 => Err(self.missing_condition()),
+            1 => Ok(operands.remove(0)),
+            _ => Ok(Query::And(operands)),
+        }
+    }
+
+    /// `not := "NOT" not | primary`
+    fn parse_not(&mut self) -> error::Result<Query> {
+        if self.peek() == Some("NOT") {
+            self.next();
+            match self.peek() {
+                Some(token) if !Self::ends_sequence(token) && token != "AND" => {
+                    Ok(Query::Not(Box::new(self.parse_not()?)))
+                }
+                _ => Err(self.missing_condition()),
+            }
+        } else {
+            self.parse_primary()
+        }
+    }
+
+    /// `primary := "(" or ")" | "|" words | condition`
+    fn parse_primary(&mut self) -> error::Result<Query> {
+        let token = self.next().unwrap_or_default();
+
+        if token == "(" {
+            if self.peek() == Some(")") {
+                return Err(error::RucolaError::FilterParseError(
+                    "Empty parentheses.".to_string(),
+                ));
+            }
+            let query = self.parse_or()?;
+            return match self.next() {
+                Some(")") => Ok(query),
+                _ => Err(error::RucolaError::FilterParseError(
+                    "Missing ')'.".to_string(),
+                )),
+            };
+        }
+
+        // full text: all words, phrases and regular expressions up to the next operator or parenthesis
+        if let Some(first) = token.strip_prefix('|') {
+            let mut words = vec![first];
+            while let Some(word) = self
+                .peek()
+                .filter(|word| !matches!(*word, "AND" | "OR" | "NOT" | "(" | ")"))
+            {
+                words.push(word.strip_prefix('|').unwrap_or(word));
+                self.next();
+            }
+            return Ok(Query::FullText(FullTextQuery::parse(
+                &words.into_iter().filter(|word| !word.is_empty()).join(" "),
+            )?));
+        }
+
+        // a single condition, possibly negated by a leading '!'
+        let (word, included) = match token.strip_prefix('!') {
+            Some(word) if !word.is_empty() => (word, false),
+            _ => (token, true),
+        };
+
+        let condition = if word.starts_with('#') {
+            Query::Tag(word.to_string())
+        } else if let Some(link) = word.strip_prefix('>') {
+            Query::Link(super::link_to_id(link))
+        } else if let Some(blink) = word.strip_prefix('<') {
+            Query::Backlink(super::link_to_id(blink))
+        } else if let Some((key, value)) = split_condition(word) {
+            let lowercase_key = key.to_lowercase();
+            if let Some(field) = DateField::from_key(&lowercase_key) {
+                Query::Date(DateCondition::parse(field, value, chrono::Local::now())?)
+            } else if let Some(field) = CountField::from_key(&lowercase_key) {
+                Query::Count(CountCondition::parse(field, key, value)?)
+            } else if lowercase_key == "in" {
+                Query::Folder(super::folder_to_id(value))
+            } else {
+                Query::Property(lowercase_key, property_value(value))
+            }
+        } else {
+            // title words keep a leading '!'
+            return Ok(Query::Title(token.to_string()));
+        };
+
+        Ok(if included {
+            condition
+        } else {
+            Query::Not(Box::new(condition))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize(r#"(|"lie (group" OR /a b/w) |x"#).collect::<Vec<_>>(),
+            vec!["(", r#"|"lie (group""#, "OR", "/a b/w", ")", "|x"]
+        );
+        assert_eq!(
+            tokenize("((#os OR SO(3)) AND !>windows)").collect::<Vec<_>>(),
+            vec!["(", "(", "#os", "OR", "SO(3)", ")", "AND", "!>windows", ")"]
+        );
+        assert_eq!(
+            tokenize("SO(3) (f(x))").collect::<Vec<_>>(),
+            vec!["SO(3)", "(", "f(x)", ")"]
+        );
+    }
+
+    #[test]
+    fn test_is_query() {
+        assert!(Query::is_query("#os OR #linux"));
+        assert!(Query::is_query("(#os)"));
+        assert!(!Query::is_query("#os or linux"));
+        assert!(!Query::is_query("SO(3) |lie group"));
+        assert!(Query::is_query("|lie group OR |lie algebra"));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Query::parse("(#os OR #linux) AND NOT >Windows").unwrap(),
+            Query::And(vec![
+                Query::Or(vec![
+                    Query::Tag("#os".to_string()),
+                    Query::Tag("#linux".to_string())
+                ]),
+                Query::Not(Box::new(Query::Link("windows".to_string()))),
+            ])
+        );
+
+        // AND binds stronger than OR, also when left out
+        assert_eq!(
+            Query::parse("<atlas !status:draft OR lie group").unwrap(),
+            Query::Or(vec![
+                Query::And(vec![
+                    Query::Backlink("atlas".to_string()),
+                    Query::Not(Box::new(Query::Property(
+                        "status".to_string(),
+                        "draft".to_string()
+                    ))),
+                ]),
+                Query::Title("liegroup".to_string()),
+            ])
+        );
+
+        // full-text words go up to the next operator
+        assert_eq!(
+            Query::parse("#os AND (|Kernel Driver OR |module)").unwrap(),
+            Query::And(vec![
+                Query::Tag("#os".to_string()),
+                Query::Or(vec![
+                    Query::FullText(FullTextQuery::parse("Kernel Driver").unwrap()),
+                    Query::FullText(FullTextQuery::parse("module").unwrap()),
+                ]),
+            ])
+        );
+
+        // phrases and regular expressions may contain operators and parentheses
+        assert_eq!(
+            Query::parse(r#"(|"this OR that" /f\(x\) AND/c) OR NOT |"(a)""#).unwrap(),
+            Query::Or(vec![
+                Query::FullText(FullTextQuery::parse(r#""this OR that" /f\(x\) AND/c"#).unwrap()),
+                Query::Not(Box::new(Query::FullText(
+                    FullTextQuery::parse(r#""(a)""#).unwrap()
+                ))),
+            ])
+        );
+
+        // dates are parsed instead of taken as properties
+        assert_eq!(
+            Query::parse("#os AND NOT Modified:>2025-01").unwrap(),
+            Query::And(vec![
+                Query::Tag("#os".to_string()),
+                Query::Not(Box::new(Query::Date(
+                    DateCondition::parse(DateField::Modified, ">2025-01", chrono::Local::now())
+                        .unwrap()
+                ))),
+            ])
+        );
+
+        // statistics and folders
+        assert_eq!(
+            Query::parse("(inlinks:0 OR Words:<100) in:Math/").unwrap(),
+            Query::And(vec![
+                Query::Or(vec![
+                    Query::Count(
+                        CountCondition::parse(CountField::InLinks, "inlinks", "0").unwrap()
+                    ),
+                    Query::Count(
+                        CountCondition::parse(CountField::Words, "words", "<100").unwrap()
+                    ),
+                ]),
+                Query::Folder("math".to_string()),
+            ])
+        );
+
+        assert_eq!(
+            Query::parse("(|lie OR #os) AND NOT |windows")
+                .unwrap()
+                .full_text_queries(),
+            vec![&FullTextQuery::parse("lie").unwrap()]
+        );
+
+        assert_eq!(
+            Query::parse("NOT NOT #os").unwrap(),
+            Query::Not(Box::new(Query::Not(Box::new(Query::Tag(
+                "#os".to_string()
+            )))))
+        );
+    }
+
+    #[test]
+    fn test_parse_conditions() {
+        // words without a key or a value are title words
+        assert_eq!(
+            Query::parse("Note: OR https://example.com").unwrap(),
+            Query::Or(vec![
+                Query::Title("Note:".to_string()),
+                Query::Title("https://example.com".to_string()),
+            ])
+        );
+        assert_eq!(
+            Query::parse("!due:* OR in:math").unwrap(),
+            Query::Or(vec![
+                Query::Not(Box::new(Query::Property("due".to_string(), String::new()))),
+                Query::Folder("math".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for (filter, message) in [
+            ("(#os OR #linux", "Missing ')'."),
+            ("#os)", "Unmatched ')'."),
+            ("#os OR", "Expected a condition after 'OR'."),
+            ("OR #os", "Expected a condition before 'OR'."),
+            ("#os AND", "Expected a condition after 'AND'."),
+            ("AND #os", "Expected a condition before 'AND'."),
+            ("#os AND NOT", "Expected a condition after 'NOT'."),
+            ("#os AND AND #linux", "Expected a condition after 'AND'."),
+            ("#os AND ()", "Empty parentheses."),
+            ("(#os AND)", "Expected a condition after 'AND'."),
+            ("#os OR created:<", "Expected a date after '<'."),
+            (
+                "(broken:>some)",
+                "Expected a number in 'broken:>some', e.g. broken:>10 or broken:0.",
+            ),
+        ] {
+            assert_eq!(
+                Query::parse(filter).unwrap_err().to_string(),
+                format!("Invalid filter: {message}"),
+                "{filter}"
+            );
+        }
+    }
+}
@@ -0,0 +1,338 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// The terms of a single note and how often each of them appears in it.
+pub type TermCounts = BTreeMap<String, usize>;
+
+/// How quickly the relevance of a note saturates with repeated occurrences of a term, see [`TextIndex::bm25`].
+const BM25_K1: f64 = 1.2;
+/// How strongly the relevance of a note is normalized by its length, see [`TextIndex::bm25`].
+const BM25_B: f64 = 0.75;
+
+/// Gives every change of a full-text index a new version, unique across all indices.
+static NEXT_VERSION: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+
+/// An inverted index of the terms in the full text of all notes.
+/// Allows full-text queries without reading the note files again, and finds the notes containing a term without visiting all notes.
+#[derive(Debug, Clone, Default)]
+pub struct TextIndex {
+    /// Maps every term to the ids of all notes containing it and how often it appears in each of them.
+    /// The terms are kept sorted, so all terms starting with a prefix can be found by a range scan.
+    postings: BTreeMap<String, HashMap<String, usize>>,
+    /// Maps the ids of all notes to their terms and how often each of them appears.
+    /// Used to remove notes from the postings, to count terms within a single note and to cache the index.
+    documents: HashMap<String, TermCounts>,
+    /// Maps the ids of all notes to their number of terms.
+    lengths: HashMap<String, usize>,
+    /// The total number of terms in all notes.
+    total_length: usize,
+    /// Changes whenever notes are added or removed, so results computed from this index can be reused until then.
+    version: u64,
+}
+
+impl TextIndex {
+    /// Splits the given text into its terms, i.e. all maximal runs of alphanumeric characters, in lowercase.
+    /// ```
+    /// assert_eq!(tokenize("Lie-Group's 2 rotations").collect_vec(), vec!["lie", "group", "s", "2", "rotations"]);
+    /// ```
+    pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|term| !term.is_empty())
+            .map(|term| term.to_lowercase())
+    }
+
+    /// Counts how often every term appears in the given text.
+    pub fn count_terms(text: &str) -> TermCounts {
+        let mut counts = TermCounts::new();
+        for term in Self::tokenize(text) {
+            *counts.entry(term).or_default() += 1;
+        }
+        counts
+    }
+
+    /// Returns the term counts of all notes, e.g. to cache them.
+    pub fn documents(&self) -> &HashMap<String, TermCounts> {
+        &self.documents
+    }
+
+    /// Adds the note with the given id and text to the index, replacing previous entries of this id.
+    pub fn insert(&mut self, id: &str, text: &str) {
+        self.insert_counts(id.to_owned(), Self::count_terms(text));
+    }
+
+    /// Adds the note with the given id and term counts to the index, replacing previous entries of this id.
+    pub fn insert_counts(&mut self, id: String, counts: TermCounts) {
+        self.remove(&id);
+        for (term, count) in counts.iter() {
+            self.postings
+                .entry(term.to_owned())
+                .or_default()
+                .insert(id.clone(), *count);
+        }
+        let length = counts.values().sum();
+        self.total_length += length;
+        self.lengths.insert(id.clone(), length);
+        self.documents.insert(id, counts);
+        self.version = NEXT_VERSION.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Removes the note with the given id from the index.
+    pub fn remove(&mut self, id: &str) {
+        let Some(counts) = self.documents.remove(id) else {
+            return;
+        };
+        for term in counts.keys() {
+            if let Some(ids) = self.postings.get_mut(term) {
+                ids.remove(id);
+                if ids.is_empty() {
+                    self.postings.remove(term);
+                }
+            }
+        }
+        if let Some(length) = self.lengths.remove(id) {
+            self.total_length -= length;
+        }
+        self.version = NEXT_VERSION.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Returns the current version of this index, which changes whenever notes are added or removed.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Returns how often terms starting with the given prefix appear in the note with the given id.
+    pub fn count(&self, id: &str, prefix: &str) -> usize {
+        self.documents
+            .get(id)
+            .map(|counts| {
+                counts
+                    .range(prefix.to_owned()..)
+                    .take_while(|(term, _)| term.starts_with(prefix))
+                    .map(|(_, count)| count)
+                    .sum()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the ids of all notes containing a term starting with the given prefix.
+    fn containing(&self, prefix: &str) -> HashSet<&String> {
+        self.postings
+            .range(prefix.to_owned()..)
+            .take_while(|(term, _)| term.starts_with(prefix))
+            .flat_map(|(_, ids)| ids.keys())
+            .collect()
+    }
+
+    /// Returns the ids of all notes containing a term for which the given function returns true.
+    /// Visits all terms, so [`Self::containing`] is preferred for prefixes.
+    fn containing_where(&self, condition: impl Fn(&str) -> bool) -> HashSet<&String> {
+        self.postings
+            .iter()
+            .filter(|(term, _)| condition(term))
+            .flat_map(|(_, ids)| ids.keys())
+            .collect()
+    }
+
+    /// Returns the ids of all notes contained in all of the given sets, or `None` if there are no sets.
+    fn intersect<'a>(sets: impl Iterator<Item = HashSet<&'a String>>) -> Option<HashSet<String>> {
+        sets.reduce(|candidates, containing| {
+            candidates
+                .into_iter()
+                .filter(|id| containing.contains(id))
+                .collect()
+        })
+        .map(|candidates| candidates.into_iter().cloned().collect())
+    }
+
+    /// Returns the ids of all notes containing a term starting with each of the terms of the query.
+    /// Returns `None` for a query without terms, which matches all notes.
+    pub fn candidates(&self, query: &str) -> Option<HashSet<String>> {
+        Self::intersect(Self::tokenize(query).map(|prefix| self.containing(&prefix)))
+    }
+
+    /// Returns the ids of all notes whose terms allow them to contain the given phrase, so only these have to be searched for it.
+    /// The terms within the phrase have to appear as they are, while the first one may be the end and the last one the beginning of a longer term - unless `whole_words` is set.
+    /// Returns `None` for a phrase without terms, which may appear in all notes.
+    pub fn phrase_candidates(&self, phrase: &str, whole_words: bool) -> Option<HashSet<String>> {
+        let terms = Self::tokenize(phrase).collect::<Vec<_>>();
+        let last = terms.len().checked_sub(1)?;
+
+        Self::intersect(terms.iter().enumerate().map(|(position, term)| {
+            // whether the phrase may start or end within this term
+            let within_start = !whole_words && position == 0;
+            let within_end = !whole_words && position == last;
+            match (within_start, within_end) {
+                (false, false) => self
+                    .postings
+                    .get(term)
+                    .into_iter()
+                    .flatten()
+                    .map(|(id, _)| id)
+                    .collect(),
+                (true, false) => self.containing_where(|other| other.ends_with(term.as_str())),
+                (false, true) => self.containing(term),
+                (true, true) => self.containing_where(|other| other.contains(term.as_str())),
+            }
+        }))
+    }
+
+    /// Returns the inverse document frequency of terms starting with the given prefix.
+    /// The fewer notes contain such a term, the higher it is, so rare terms weigh more than common ones.
+    pub fn idf(&self, prefix: &str) -> f64 {
+        let total = self.documents.len() as f64;
+        let containing = self.containing(prefix).len() as f64;
+        ((total - containing + 0.5) / (containing + 0.5)).ln_1p()
+    }
+
+    /// Scores how relevant the note with the given id is for the given prefixes and their inverse document frequencies with the Okapi BM25 ranking function.
+    /// The score grows with how often the prefixes appear in the note, with diminishing returns, and is lower for notes longer than average.
+    pub fn bm25(&self, id: &str, prefixes: &[(String, f64)]) -> f64 {
+        let Some(&length) = self.lengths.get(id) else {
+            return 0.0;
+        };
+        let average_length = (self.total_length as f64 / self.documents.len() as f64).max(1.0);
+        let normalization = 1.0 - BM25_B + BM25_B * length as f64 / average_length;
+
+        prefixes
+            .iter()
+            .map(|(prefix, idf)| {
+                let frequency = self.count(id, prefix) as f64;
+                idf * frequency * (BM25_K1 + 1.0) / (frequency + BM25_K1 * normalization)
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextIndex;
+    use itertools::Itertools;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            TextIndex::tokenize("Lie-Group's 2 rotations, ÄRGER!").collect_vec(),
+            vec!["lie", "group", "s", "2", "rotations", "ärger"]
+        );
+        assert_eq!(TextIndex::tokenize(" -- ").count(), 0);
+    }
+
+    #[test]
+    fn test_text_index() {
+        let mut index = TextIndex::default();
+        index.insert(
+            "group",
+            "A group is a set with an operation. Groups act on sets.",
+        );
+        index.insert("ring", "A ring is a group with a second operation.");
+
+        assert_eq!(index.count("group", "group"), 2);
+        assert_eq!(index.count("group", "set"), 2);
+        assert_eq!(index.count("ring", "set"), 0);
+        assert_eq!(index.count("nonexistent", "a"), 0);
+
+        let candidates = |index: &TextIndex, query: &str| {
+            index
+                .candidates(query)
+                .map(|candidates| candidates.into_iter().sorted().collect_vec())
+        };
+
+        assert_eq!(
+            candidates(&index, "GROUP oper"),
+            Some(vec!["group".to_owned(), "ring".to_owned()])
+        );
+        assert_eq!(
+            candidates(&index, "a second"),
+            Some(vec!["ring".to_owned()])
+        );
+        assert_eq!(candidates(&index, "sets ring"), Some(vec![]));
+        assert_eq!(candidates(&index, " - "), None);
+
+        // phrases may start and end within terms, but their inner terms have to appear as they are
+        let phrase_candidates = |phrase: &str, whole_words: bool| {
+            index
+                .phrase_candidates(phrase, whole_words)
+                .map(|candidates| candidates.into_iter().sorted().collect_vec())
+        };
+        assert_eq!(
+            phrase_candidates("ing is a gro", false),
+            Some(vec!["ring".to_owned()])
+        );
+        assert_eq!(phrase_candidates("ing is a gro", true), Some(vec![]));
+        assert_eq!(
+            phrase_candidates("a ring is", true),
+            Some(vec!["ring".to_owned()])
+        );
+        assert_eq!(phrase_candidates("a rin is", false), Some(vec![]));
+        assert_eq!(
+            phrase_candidates("roup", false),
+            Some(vec!["group".to_owned(), "ring".to_owned()])
+        );
+        assert_eq!(phrase_candidates("roup", true), Some(vec![]));
+        assert_eq!(phrase_candidates(" - ", false), None);
+
+        // replacing a note removes its old terms
+        let version = index.version();
+        index.insert("group", "Monoids");
+        assert_ne!(index.version(), version);
+        assert_eq!(index.count("group", "group"), 0);
+        assert_eq!(candidates(&index, "mono"), Some(vec!["group".to_owned()]));
+
+        index.remove("ring");
+        assert_eq!(candidates(&index, "ring"), Some(vec![]));
+        assert!(!index.postings.contains_key("second"));
+
+        // the index can be restored from the term counts of its notes
+        let mut restored = TextIndex::default();
+        for (id, counts) in index.documents().clone() {
+            restored.insert_counts(id, counts);
+        }
+        assert_eq!(
+            candidates(&restored, "monoids"),
+            Some(vec!["group".to_owned()])
+        );
+        assert_eq!(restored.postings, index.postings);
+    }
+
+    #[test]
+    fn test_bm25() {
+        let mut index = TextIndex::default();
+        index.insert(
+            "group",
+            "A group is a set with an operation. Groups act on sets.",
+        );
+        index.insert("ring", "A ring is a group with a second operation.");
+        index.insert("field", "A field is a ring in which division is possible.");
+        index.insert("lattice", "A lattice is a partially ordered set.");
+
+        // rare terms weigh more than common ones
+        assert!(index.idf("field") > index.idf("group"));
+        assert!(index.idf("group") > index.idf("a"));
+        assert!(index.idf("nonexistent") > index.idf("field"));
+
+        let score = |id: &str, query: &str| {
+            let prefixes = TextIndex::tokenize(query)
+                .map(|prefix| {
+                    let idf = index.idf(&prefix);
+                    (prefix, idf)
+                })
+                .collect::<Vec<_>>();
+            index.bm25(id, &prefixes)
+        };
+
+        // more occurrences of the query give a higher score
+        assert!(score("group", "group") > score("ring", "group"));
+        assert!(score("ring", "ring") > 0.0);
+        assert_eq!(score("lattice", "group"), 0.0);
+        assert_eq!(score("nonexistent", "group"), 0.0);
+        assert_eq!(score("group", ""), 0.0);
+
+        // shorter notes are more relevant for the same number of occurrences
+        assert!(score("lattice", "set") > score("ring", "operation"));
+
+        // removing and replacing notes keeps the lengths consistent
+        index.remove("group");
+        index.insert("ring", "Rings");
+        assert_eq!(index.total_length, index.lengths.values().sum::<usize>());
+        assert_eq!(index.lengths.len(), 3);
+    }
+}
@@ -9,8 +9,20 @@ pub enum RucolaError {
     IO(#[from] std::io::Error),
     #[error("Failed to find this note at the expected location: {0}.")]
     NoteNotFound(String),
+    #[error("Invalid filter: {0}")]
+    FilterParseError(String),
+    #[error("Failed to find a task in line {1} of {0}.")]
+    TaskNotFound(std::path::PathBuf, usize),
+    #[error("Failed to find the tag {0} in the text of {1}, it might be set in the frontmatter.")]
+    TagNotFound(String, std::path::PathBuf),
+    #[error("{0} of {1} notes failed: {2}")]
+    BulkError(usize, usize, String),
+    #[error("Link to {0} in {1} is ambiguous, using {2}. Add folders to the link to disambiguate.")]
+    AmbiguousLink(String, String, String),
     #[error("Could not read file name of note at {0}.")]
     NoteNameCannotBeRead(std::path::PathBuf),
+    #[error("A file already exists at {0}.")]
+    FileExists(std::path::PathBuf),
     #[error("Failed to load config file, defaulting: {0}")]
     ConfigLoad(#[from] confy::ConfyError),
     #[error("Could not find a default application for this file type.")]
@@ -33,6 +45,12 @@ pub enum RucolaError {
     YamlError(#[from] yaml_rust::ScanError),
     #[error("Parsed YAML frontmatter but found not content.")]
     YamlDocsError(String),
+    #[error("Failed to write the index cache: {0}")]
+    CacheError(#[from] toml::ser::Error),
+    #[error("Failed to read or write the filter history: {0}")]
+    HistoryError(String),
+    #[error("Invalid key binding: {0}")]
+    KeyBinding(String),
     #[error("Failed to find Git Repository.")]
     GitError(#[from] git2::Error),
 }
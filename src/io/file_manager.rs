@@ -128,20 +128,16 @@ impl FileManager {
         fs::rename(&note.path, &new_path)?;
 
         // === RENAMING ===
-        // Create a regex that find links to the old name or id
-        let mut regex_builder = String::new();
-        regex_builder.push_str("(\\[\\[)(");
-        regex_builder.push_str(&note.name); // this is still the old name
-        regex_builder.push('|');
-        regex_builder.push_str(id);
-        regex_builder.push_str(")(\\|?[^\\|^\\]^\\]]*\\]\\])");
-
-        let mut replacement_builder = String::new();
-        replacement_builder.push_str("${1}");
-        replacement_builder.push_str(&new_name);
-        replacement_builder.push_str("${3}");
-
-        let reg = regex::Regex::new(&regex_builder)?;
+        // The given id may have been a link, so take the full id of the note itself
+        let id = &index_b.path_to_id(&note.path);
+        // Create a regex that find links to the old name or id, possibly qualified by folders
+        let name_id = id.rsplit('/').next().unwrap_or(id);
+        let reg = regex::Regex::new(&format!(
+            r"(\[\[(?:[^\]\|#\n]*/)?)({}|{})(\|?[^\|^\]^\]]*\]\])",
+            regex::escape(&note.name), // this is still the old name
+            regex::escape(name_id),
+        ))?;
+
         for other_note in index_b
             // search for references to the old id.
             .blinks_vec(id)
@@ -151,7 +147,15 @@ impl FileManager {
             // open the file once to read its old content
             let old_content = std::fs::read_to_string(&other_note.path)?;
 
-            let res = reg.replace_all(&old_content, &replacement_builder);
+            // only replace links that actually resolve to the renamed note
+            let res = reg.replace_all(&old_content, |caps: &regex::Captures| {
+                let link = format!("{}{}", caps[1].trim_start_matches("[["), &caps[2]);
+                if index_b.resolve(&data::link_to_id(&link)) == Some(id) {
+                    format!("{}{}{}", &caps[1], new_name, &caps[3])
+                } else {
+                    caps[0].to_owned()
+                }
+            });
 
             // open the file again
             let mut file = std::fs::OpenOptions::new()
@@ -188,6 +192,14 @@ impl FileManager {
         // Ensure file extension just to be safe
         self.ensure_file_extension(&mut new_path);
 
+        // Never replace another file, e.g. a note of the same name moved there before
+        if new_path.exists() {
+            if new_path.canonicalize()? == note.path {
+                return Ok(());
+            }
+            return Err(error::RucolaError::FileExists(new_path));
+        }
+
         // Ensure parent directory exists
         if let Some(parent) = new_path.parent() {
             if !parent.exists() {
@@ -219,6 +231,146 @@ impl FileManager {
         Ok(())
     }
 
+    /// Adds the given tag (with or without `#`) to the note of the given id.
+    /// The tag is appended to the last line of the note if that line consists of tags only, otherwise it is written to a new line at the end.
+    /// Notes that already have the tag are left unchanged.
+    pub fn add_tag(
+        &self,
+        index: data::NoteIndexContainer,
+        id: &str,
+        tag: &str,
+    ) -> error::Result<()> {
+        let tag = Self::normalize_tag(tag)?;
+        let index = index.borrow();
+        let note = index
+            .get(id)
+            .ok_or_else(|| error::RucolaError::NoteNotFound(id.to_owned()))?;
+
+        if note.tags.contains(&tag) {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(&note.path)?;
+        let content = content.trim_end();
+
+        // continue a trailing line of tags, otherwise start a new paragraph
+        let separator = match content.lines().last() {
+            None => "",
+            Some(line)
+                if line
+                    .split_whitespace()
+                    .all(|word| word.len() > 1 && word.starts_with('#')) =>
+            {
+                " "
+            }
+            Some(_) => "\n\n",
+        };
+
+        fs::write(&note.path, format!("{content}{separator}{tag}\n"))?;
+
+        Ok(())
+    }
+
+    /// Removes the given tag (with or without `#`) from the text of the note of the given id.
+    /// Lines that only consisted of tags removed this way are dropped entirely.
+    /// Notes that do not have the tag are left unchanged, tags given in the frontmatter cannot be removed.
+    pub fn remove_tag(
+        &self,
+        index: data::NoteIndexContainer,
+        id: &str,
+        tag: &str,
+    ) -> error::Result<()> {
+        let tag = Self::normalize_tag(tag)?;
+        let index = index.borrow();
+        let note = index
+            .get(id)
+            .ok_or_else(|| error::RucolaError::NoteNotFound(id.to_owned()))?;
+
+        if !note.tags.contains(&tag) {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(&note.path)?;
+
+        // Create a regex that finds the tag as a whole word, including the whitespace after it
+        let reg = regex::Regex::new(&format!(r"(^|[ \t]){}(?:[ \t]+|$)", regex::escape(&tag)))?;
+
+        let mut found = false;
+        let new_content = content
+            .split_inclusive('\n')
+            .filter_map(|line| {
+                let text = line.trim_end_matches(['\r', '\n']);
+                let replaced = reg.replace_all(text, "$1");
+                if replaced == text {
+                    return Some(line.to_owned());
+                }
+                found = true;
+                if replaced.trim().is_empty() {
+                    None
+                } else {
+                    Some(format!("{}{}", replaced.trim_end(), &line[text.len()..]))
+                }
+            })
+            .collect::<String>();
+
+        if !found {
+            return Err(error::RucolaError::TagNotFound(tag, note.path.clone()));
+        }
+
+        fs::write(&note.path, new_content)?;
+
+        Ok(())
+    }
+
+    /// Prepends a `#` to the given tag if necessary and checks that it is a single word.
+    fn normalize_tag(tag: &str) -> error::Result<String> {
+        let tag = tag.trim().trim_start_matches('#');
+        if tag.is_empty() {
+            return Err(error::RucolaError::Input("Tag cannot be empty.".to_owned()));
+        }
+        if tag.contains(char::is_whitespace) {
+            return Err(error::RucolaError::Input(
+                "Tag cannot contain spaces.".to_owned(),
+            ));
+        }
+        Ok(format!("#{tag}"))
+    }
+
+    /// Checks or unchecks the given task of the given note by rewriting its line in the note file.
+    /// The new state is always the opposite of the state stored in the task, so toggling twice based on stale data does not undo the change.
+    pub fn toggle_task(&self, note: &data::Note, task: &data::Task) -> error::Result<()> {
+        let content = fs::read_to_string(&note.path)?;
+
+        // Create a regex that finds the checkbox after the list marker, possibly in a block quote
+        let reg = regex::Regex::new(r"^(\s*(?:>\s*)*(?:[-+*]|\d+[.)])\s+\[)[ xX](\])")?;
+
+        let mut found = false;
+        let new_content = content
+            .split_inclusive('\n')
+            .enumerate()
+            .map(|(index, line)| {
+                if index + 1 == task.line && reg.is_match(line) {
+                    found = true;
+                    reg.replace(line, if task.done { "$1 $2" } else { "${1}x$2" })
+                        .into_owned()
+                } else {
+                    line.to_owned()
+                }
+            })
+            .collect::<String>();
+
+        if !found {
+            return Err(error::RucolaError::TaskNotFound(
+                note.path.clone(),
+                task.line,
+            ));
+        }
+
+        fs::write(&note.path, new_content)?;
+
+        Ok(())
+    }
+
     /// Creates a note of the given name in the file system (relative to the vault).
     /// Registration in the index is handled centrally by the file watcher of the index itself.
     /// Returns the path to the newly created note.
@@ -267,9 +419,12 @@ impl FileManager {
     ///  - the systems default programms
     ///
     /// for an applicable program.
+    ///
+    /// In the editor command of the config file, `%l` is replaced by the given line (or the first line, if none is given), e.g. `["vim", "+%l", "%p"]` or `["hx", "%p:%l"]`.
     pub fn create_edit_command(
         &self,
         path: &path::PathBuf,
+        line: Option<usize>,
     ) -> error::Result<std::process::Command> {
         // take the editor from the config file
         self.editor
@@ -285,6 +440,18 @@ impl FileManager {
                         if arg == "%p" {
                             // special argument for the user to indicate where to put the path
                             cmd.arg(path.canonicalize().as_ref().unwrap_or(path));
+                        } else if arg.contains("%l") {
+                            // special placeholder for the line to open the file at, possibly combined with the path
+                            cmd.arg(
+                                arg.replace("%l", &line.unwrap_or(1).to_string()).replace(
+                                    "%p",
+                                    &path
+                                        .canonicalize()
+                                        .as_ref()
+                                        .unwrap_or(path)
+                                        .to_string_lossy(),
+                                ),
+                            );
                         } else {
                             // all other arguments are appended in order
                             cmd.arg(arg);
@@ -337,9 +504,10 @@ impl FileManager {
 
         // generate the appropriate path
         let path = match vtype {
-            config::ViewerType::Html => {
-                super::html_builder::name_to_html_path(&note.name, &self.vault_path)
-            }
+            config::ViewerType::Html => super::html_builder::id_to_html_path(
+                &data::path_to_id(&note.path, &self.vault_path),
+                &self.vault_path,
+            ),
             config::ViewerType::Markdown => note.path.clone(),
         };
         eprintln!("{:?}", path);
@@ -398,10 +566,47 @@ mod tests {
 
         if let Ok(_editor) = editor {
             // if we can unwrap the env variable, then we should be able to create a command
-            fm.create_edit_command(&path.to_path_buf()).unwrap();
+            fm.create_edit_command(&path.to_path_buf(), None).unwrap();
         }
     }
 
+    #[test]
+    fn test_edit_at_line() {
+        let config = crate::Config {
+            vault_path: Some(std::env::current_dir().unwrap().join("tests")),
+            editor: Some(vec![
+                "vim".to_string(),
+                "+%l".to_string(),
+                "%p".to_string(),
+                "%p:%l".to_string(),
+            ]),
+            ..Default::default()
+        };
+        let fm = super::FileManager::new(&config);
+        let path = std::env::current_dir()
+            .unwrap()
+            .join("tests/common/notes/Books.md");
+        let path_string = path.canonicalize().unwrap().to_string_lossy().to_string();
+
+        let args = |line| {
+            fm.create_edit_command(&path, line)
+                .unwrap()
+                .get_args()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            args(Some(42)),
+            vec![
+                "+42".to_string(),
+                path_string.clone(),
+                format!("{path_string}:42")
+            ]
+        );
+        assert_eq!(args(None)[0], "+1");
+    }
+
     #[test]
     fn test_viewing() {
         let config = crate::Config {
@@ -594,6 +799,43 @@ mod tests {
         assert!(mn_path_ccc.exists());
     }
 
+    #[test]
+    fn test_toggle_task() {
+        let tmp = testdir::testdir!();
+
+        let config = crate::Config {
+            vault_path: Some(tmp.clone()),
+            ..Default::default()
+        };
+        let fm = super::FileManager::new(&config);
+
+        let path = tmp.join("Todo.md");
+        std::fs::write(
+            &path,
+            "# Todo\n\n- [ ] First\n  - [X] Second\n1. [ ] Third\n",
+        )
+        .unwrap();
+
+        let note = data::Note::from_path(&path).unwrap();
+        assert_eq!(note.tasks.len(), 3);
+
+        fm.toggle_task(&note, &note.tasks[0]).unwrap();
+        fm.toggle_task(&note, &note.tasks[1]).unwrap();
+        fm.toggle_task(&note, &note.tasks[2]).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# Todo\n\n- [x] First\n  - [ ] Second\n1. [x] Third\n"
+        );
+
+        // tasks that are no longer where they are expected cause an error
+        let moved = data::Task {
+            line: 1,
+            ..note.tasks[0].clone()
+        };
+        assert!(fm.toggle_task(&note, &moved).is_err());
+    }
+
     #[test]
     fn test_delete() {
         let tmp = testdir::testdir!();
@@ -796,6 +1038,111 @@ mod tests {
         assert!(!at_path.exists());
     }
 
+    #[test]
+    fn test_move_same_names() {
+        let tmp = testdir::testdir!();
+
+        let config = crate::Config {
+            vault_path: Some(tmp.clone()),
+            ..Default::default()
+        };
+        let fm = super::FileManager::new(&config);
+
+        fm.create_note_file("Work/Index", None).unwrap();
+        fm.create_note_file("Home/Index", None).unwrap();
+        std::fs::write(tmp.join("Work/Index.md"), "work").unwrap();
+        std::fs::write(tmp.join("Home/Index.md"), "home").unwrap();
+
+        let tracker = crate::io::FileTracker::new(&config).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config);
+        let index = crate::data::NoteIndex::new(tracker, builder, &config).0;
+        let index_con = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        fm.move_note_file(index_con.clone(), "work/index", String::from("Archive"))
+            .unwrap();
+
+        // the second note of the same name does not replace the first one
+        assert!(matches!(
+            fm.move_note_file(index_con.clone(), "home/index", String::from("Archive")),
+            Err(crate::error::RucolaError::FileExists(_))
+        ));
+        assert_eq!(
+            std::fs::read_to_string(tmp.join("Archive/Index.md")).unwrap(),
+            "work"
+        );
+        assert_eq!(
+            std::fs::read_to_string(tmp.join("Home/Index.md")).unwrap(),
+            "home"
+        );
+
+        // moving a note to its own folder changes nothing
+        fm.move_note_file(index_con.clone(), "home/index", String::from("Home"))
+            .unwrap();
+        assert!(tmp.join("Home/Index.md").exists());
+    }
+
+    #[test]
+    fn test_tags() {
+        let tmp = testdir::testdir!();
+        std::fs::write(tmp.join("Linux.md"), "# Linux\nA kernel.\n\n#os #unix\n").unwrap();
+        std::fs::write(tmp.join("Atlas.md"), "---\ntags: [math]\n---\n# Atlas").unwrap();
+
+        let config = crate::Config {
+            vault_path: Some(tmp.clone()),
+            ..Default::default()
+        };
+        let fm = super::FileManager::new(&config);
+
+        let tracker = crate::io::FileTracker::new(&config).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config);
+        let index = crate::data::NoteIndex::new(tracker, builder, &config).0;
+        let index_con = std::rc::Rc::new(std::cell::RefCell::new(index));
+        let content = |name: &str| std::fs::read_to_string(tmp.join(name)).unwrap();
+        let reindex = |name: &str| {
+            index_con
+                .borrow_mut()
+                .insert_note_from_path(&tmp.join(name))
+                .unwrap();
+        };
+
+        // tags are appended to a trailing line of tags, or to a new line
+        fm.add_tag(index_con.clone(), "linux", "kernel").unwrap();
+        assert_eq!(
+            content("Linux.md"),
+            "# Linux\nA kernel.\n\n#os #unix #kernel\n"
+        );
+        fm.add_tag(index_con.clone(), "atlas", "#topology").unwrap();
+        assert_eq!(
+            content("Atlas.md"),
+            "---\ntags: [math]\n---\n# Atlas\n\n#topology\n"
+        );
+
+        // existing tags are not added twice
+        reindex("Linux.md");
+        fm.add_tag(index_con.clone(), "linux", "#os").unwrap();
+        assert_eq!(
+            content("Linux.md"),
+            "# Linux\nA kernel.\n\n#os #unix #kernel\n"
+        );
+
+        // removing tags drops lines left empty
+        fm.remove_tag(index_con.clone(), "linux", "unix").unwrap();
+        assert_eq!(content("Linux.md"), "# Linux\nA kernel.\n\n#os #kernel\n");
+        reindex("Linux.md");
+        fm.remove_tag(index_con.clone(), "linux", "#kernel")
+            .unwrap();
+        fm.remove_tag(index_con.clone(), "linux", "#os").unwrap();
+        assert_eq!(content("Linux.md"), "# Linux\nA kernel.\n\n");
+
+        // tags of the frontmatter cannot be removed, missing tags are ignored
+        reindex("Atlas.md");
+        assert!(fm.remove_tag(index_con.clone(), "atlas", "math").is_err());
+        fm.remove_tag(index_con.clone(), "atlas", "physics")
+            .unwrap();
+        assert!(fm.add_tag(index_con.clone(), "atlas", "two words").is_err());
+        assert!(fm.add_tag(index_con.clone(), "atlas", "#").is_err());
+    }
+
     #[test]
     fn test_file_endings() {
         let md_ending_tar = std::env::current_dir()
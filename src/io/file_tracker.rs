@@ -288,7 +288,7 @@ mod tests {
         assert!(modifications);
         assert_eq!(
             id_changes,
-            vec![String::from("atlas"), String::from("lie-group"),]
+            vec![String::from("lie-group"), String::from("math/atlas"),]
         );
 
         assert!(index_con.borrow().get("atlas").is_none());
@@ -346,7 +346,7 @@ mod tests {
         assert!(modifications);
         assert_eq!(
             id_changes,
-            vec![String::from("atlas"), String::from("lie-group"),]
+            vec![String::from("lie-group"), String::from("math/atlas"),]
         );
 
         assert!(index_con.borrow().get("atlas").is_none());
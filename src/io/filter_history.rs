@@ -0,0 +1,232 @@
+use std::path;
+
+use crate::error;
+
+/// The name of the folder within the user data directory the filter histories of all vaults are saved to.
+const HISTORY_FOLDER: &str = "filter_history";
+
+/// The format of the history file.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct HistoryFile {
+    /// All remembered filters, oldest first.
+    filters: Vec<String>,
+}
+
+/// Remembers the filters applied on the select screen, so they can be recalled with the arrow keys, also in later sessions.
+#[derive(Debug, Clone, Default)]
+pub struct FilterHistory {
+    /// All remembered filters, oldest first and without duplicates.
+    entries: Vec<String>,
+    /// How many filters are remembered at most. Zero turns the history off.
+    capacity: usize,
+    /// The file the history is saved to, if any.
+    path: Option<path::PathBuf>,
+    /// The index of the entry currently recalled, if the user is browsing the history.
+    position: Option<usize>,
+    /// The filter typed before browsing the history, which is restored after the newest entry.
+    draft: String,
+}
+
+impl FilterHistory {
+    /// Loads the filter history of the vault given in the config, starting with an empty history if there is none yet.
+    /// The history is kept in the user data directory rather than the vault, as vaults are often shared.
+    pub fn new(config: &crate::Config) -> error::Result<Self> {
+        let path = config
+            .vault_path
+            .as_ref()
+            .filter(|_| config.filter_history_length > 0)
+            .and_then(|vault_path| history_path(vault_path));
+        Self::load(config.filter_history_length, path)
+    }
+
+    /// Loads the history from the given file, if any, remembering at most `capacity` filters.
+    fn load(capacity: usize, path: Option<path::PathBuf>) -> error::Result<Self> {
+        let mut history = Self {
+            capacity,
+            path,
+            ..Default::default()
+        };
+
+        if let Some(path) = history.path.as_ref().filter(|path| path.exists()) {
+            let file: HistoryFile = toml::from_str(&std::fs::read_to_string(path)?)
+                .map_err(|e| error::RucolaError::HistoryError(e.to_string()))?;
+            history.entries = file.filters;
+            history.truncate();
+        }
+
+        Ok(history)
+    }
+
+    /// Remembers the given filter as the newest entry, moving it to the end if it was already remembered, and saves the history.
+    /// Empty filters are ignored.
+    pub fn push(&mut self, filter: &str) -> error::Result<()> {
+        self.position = None;
+
+        let filter = filter.trim();
+        if filter.is_empty() || self.capacity == 0 {
+            return Ok(());
+        }
+        if self.entries.last().is_some_and(|last| last == filter) {
+            return Ok(());
+        }
+
+        self.entries.retain(|entry| entry != filter);
+        self.entries.push(filter.to_owned());
+        self.truncate();
+
+        self.save()
+    }
+
+    /// Recalls the entry before the currently recalled one, starting with the newest one.
+    /// The given filter currently typed is kept to be restored by [`Self::next`].
+    pub fn previous(&mut self, current: &str) -> Option<&str> {
+        let position = match self.position {
+            Some(position) => position.checked_sub(1)?,
+            None => {
+                self.draft = current.to_owned();
+                self.entries.len().checked_sub(1)?
+            }
+        };
+        self.position = Some(position);
+        self.entries.get(position).map(String::as_str)
+    }
+
+    /// Recalls the entry after the currently recalled one, or the filter typed before browsing after the newest one.
+    pub fn next(&mut self) -> Option<&str> {
+        let position = self.position? + 1;
+        if position < self.entries.len() {
+            self.position = Some(position);
+            self.entries.get(position).map(String::as_str)
+        } else {
+            self.position = None;
+            Some(&self.draft)
+        }
+    }
+
+    /// Stops browsing the history, e.g. because the recalled filter was edited.
+    pub fn reset(&mut self) {
+        self.position = None;
+    }
+
+    /// Drops the oldest entries beyond the capacity.
+    fn truncate(&mut self) {
+        let excess = self.entries.len().saturating_sub(self.capacity);
+        self.entries.drain(..excess);
+    }
+
+    /// Writes the history to its file, if it has one.
+    /// It is written to a temporary file first and then moved, so an interrupted write never leaves a broken history behind.
+    fn save(&self) -> error::Result<()> {
+        if let Some(path) = &self.path {
+            let file = HistoryFile {
+                filters: self.entries.clone(),
+            };
+            let content = toml::to_string(&file)
+                .map_err(|e| error::RucolaError::HistoryError(e.to_string()))?;
+
+            if let Some(folder) = path.parent() {
+                std::fs::create_dir_all(folder)?;
+            }
+            let tmp_path = path.with_extension("toml.tmp");
+            std::fs::write(&tmp_path, content)?;
+            std::fs::rename(&tmp_path, path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the file the filter history of the given vault is saved to, within the user data directory.
+/// The file is named after the full path of the vault, with all characters other than letters, digits and `-` escaped as `_` and their hex code, so each vault gets its own file.
+fn history_path(vault_path: &path::Path) -> Option<path::PathBuf> {
+    let dirs = directories::ProjectDirs::from("", "", "rucola")?;
+
+    let vault_path = vault_path
+        .canonicalize()
+        .unwrap_or_else(|_| vault_path.to_path_buf());
+    let name = vault_path
+        .to_string_lossy()
+        .bytes()
+        .map(|byte| match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' => (byte as char).to_string(),
+            byte => format!("_{byte:02x}"),
+        })
+        .collect::<String>();
+
+    Some(
+        dirs.data_dir()
+            .join(HISTORY_FOLDER)
+            .join(name)
+            .with_extension("toml"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_history_navigation() {
+        let tmp = testdir::testdir!();
+        let path = tmp.join("history").join("vault.toml");
+
+        let mut history = FilterHistory::load(3, Some(path.clone())).unwrap();
+        assert_eq!(history.previous("typed"), None);
+
+        for filter in ["#os", "  ", ">linux", "#os", "#os", "|kernel"] {
+            history.push(filter).unwrap();
+        }
+
+        // duplicates are moved to the end, empty filters ignored
+        assert_eq!(history.entries, vec![">linux", "#os", "|kernel"]);
+
+        assert_eq!(history.previous("typed"), Some("|kernel"));
+        assert_eq!(history.previous("|kernel"), Some("#os"));
+        assert_eq!(history.previous("#os"), Some(">linux"));
+        assert_eq!(history.previous(">linux"), None);
+        assert_eq!(history.next(), Some("#os"));
+        assert_eq!(history.next(), Some("|kernel"));
+        assert_eq!(history.next(), Some("typed"));
+        assert_eq!(history.next(), None);
+
+        // the capacity is respected and the history is kept for the next session
+        history.push("#unix").unwrap();
+        let mut reloaded = FilterHistory::load(3, Some(path.clone())).unwrap();
+        assert_eq!(reloaded.entries, vec!["#os", "|kernel", "#unix"]);
+        assert_eq!(reloaded.previous(""), Some("#unix"));
+        assert!(!path.with_extension("toml.tmp").exists());
+
+        // a capacity of zero turns the history off
+        let mut disabled = FilterHistory::new(&crate::Config {
+            vault_path: Some(tmp.clone()),
+            filter_history_length: 0,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(disabled.path, None);
+        disabled.push("#os").unwrap();
+        assert_eq!(disabled.previous(""), None);
+        assert!(FilterHistory::load(100, Some(path))
+            .unwrap()
+            .entries
+            .contains(&"#unix".to_string()));
+
+        // nothing is written into the vault itself
+        assert!(!tmp.join("rucola_filter_history.toml").exists());
+    }
+
+    #[test]
+    fn test_history_path() {
+        let Some(notes) = history_path(path::Path::new("/notes/my notes")) else {
+            // no user data directory in this environment
+            return;
+        };
+        let dirs = directories::ProjectDirs::from("", "", "rucola").unwrap();
+        assert!(notes.starts_with(dirs.data_dir().join(HISTORY_FOLDER)));
+        assert_eq!(notes.file_name().unwrap(), "_2fnotes_2fmy_20notes.toml");
+        assert_ne!(
+            history_path(path::Path::new("/notes/my_notes")),
+            Some(notes)
+        );
+    }
+}
@@ -61,7 +61,14 @@ impl HtmlBuilder {
         }
     }
 
-    pub fn create_html(&self, note: &data::Note, force: bool) -> error::Result<()> {
+    /// Creates the HTML file of the given note, resolving its links through the given index.
+    /// Does nothing if HTML files are disabled in the config, unless forced.
+    pub fn create_html(
+        &self,
+        note: &data::Note,
+        index: &data::NoteIndex,
+        force: bool,
+    ) -> error::Result<()> {
         if !self.enable_html && !force {
             return Ok(());
         }
@@ -78,6 +85,7 @@ impl HtmlBuilder {
                 extension: comrak::ExtensionOptions::builder()
                     .wikilinks_title_after_pipe(true)
                     .math_dollars(true)
+                    .tasklist(true)
                     .build(),
                 ..Default::default()
             },
@@ -86,15 +94,23 @@ impl HtmlBuilder {
         let mut contains_math = false;
         let mut contains_code = false;
 
+        // The id of the note determines where its HTML file is stored, and thereby how links leave its folder.
+        let id = data::path_to_id(&note.path, &self.vault_path);
+        let root_prefix = "../".repeat(id.matches('/').count());
+
         for node in root.descendants() {
             // correct id urls for wiki links
             match node.data.borrow_mut().value {
                 comrak::nodes::NodeValue::WikiLink(ref mut link) => {
-                    link.url = format!("{}.html", data::name_to_id(&link.url));
+                    link.url = link_to_html_url(&link.url, index, &root_prefix);
                 }
                 comrak::nodes::NodeValue::Link(ref mut link) => {
-                    if !link.url.contains('/') && !link.url.contains('.') {
-                        link.url = format!("{}.html", data::name_to_id(&link.url));
+                    if let Some(url) =
+                        markdown_link_to_html_url(&link.url, &id, index, &root_prefix)
+                    {
+                        link.url = url;
+                    } else if !link.url.contains('/') && !link.url.contains('.') {
+                        link.url = link_to_html_url(&link.url, index, &root_prefix);
                     }
                 }
                 comrak::nodes::NodeValue::Math(ref mut math) => {
@@ -116,7 +132,7 @@ impl HtmlBuilder {
             }
         }
 
-        let tar_path = name_to_html_path(&note.name, &self.vault_path);
+        let tar_path = id_to_html_path(&id, &self.vault_path);
 
         // ensure parent exists
         if let Some(parent) = tar_path.parent() {
@@ -141,6 +157,9 @@ impl HtmlBuilder {
                 extension: comrak::ExtensionOptions::builder()
                     .wikilinks_title_after_pipe(true)
                     .math_dollars(true)
+                    .tasklist(true)
+                    // give every heading an anchor, so links to headings work
+                    .header_ids(String::new())
                     .build(),
                 ..Default::default()
             },
@@ -150,6 +169,42 @@ impl HtmlBuilder {
         Ok(())
     }
 
+    /// Removes all HTML files directly in the `.html` folder that belong to no note in the root of the vault.
+    /// Earlier versions put the HTML files of all notes there, while notes in subfolders now get theirs in the same subfolders of the `.html` folder.
+    pub fn remove_stale_html(&self, index: &data::NoteIndex) -> error::Result<()> {
+        let html_folder = self.vault_path.join(".html");
+        if !html_folder.is_dir() {
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(html_folder)? {
+            let path = entry?.path();
+            // only notes in the root of the vault have ids without folders, which resolve to themselves
+            let is_stale = path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension == "html")
+                && path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(|id| index.resolve(id) != Some(id));
+            if is_stale {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether the HTML file of the given note exists.
+    pub fn html_exists(&self, note: &data::Note) -> bool {
+        id_to_html_path(
+            &data::path_to_id(&note.path, &self.vault_path),
+            &self.vault_path,
+        )
+        .exists()
+    }
+
     /// Prepends relevant data to a generated html file
     pub fn add_preamble(
         &self,
@@ -221,14 +276,57 @@ impl HtmlBuilder {
     }
 }
 
+/// Converts the url of a link to a note, possibly followed by a heading fragment, to the url of the corresponding HTML file and anchor.
+/// The target note is resolved through the paths and aliases of the given index, the resulting url is relative to the HTML root folder prefixed by `root_prefix`.
+/// Links consisting only of a fragment stay within the current file.
+fn link_to_html_url(url: &str, index: &data::NoteIndex, root_prefix: &str) -> String {
+    let (target, fragment) = url.split_once('#').unwrap_or((url, ""));
+
+    let mut html_url = if target.is_empty() {
+        String::new()
+    } else {
+        let id = data::link_to_id(target);
+        format!("{}{}.html", root_prefix, index.resolve(&id).unwrap_or(&id))
+    };
+
+    if !fragment.is_empty() {
+        html_url.push('#');
+        html_url.push_str(&data::heading_to_slug(fragment));
+    }
+
+    html_url
+}
+
+/// Converts the url of a markdown link in the note with the given id to the url of the HTML file of the note it points to, relative to the HTML root folder prefixed by `root_prefix`.
+/// Returns `None` if the link does not point to an existing note, e.g. for links to websites or images.
+fn markdown_link_to_html_url(
+    url: &str,
+    source_id: &str,
+    index: &data::NoteIndex,
+    root_prefix: &str,
+) -> Option<String> {
+    let link_id = data::markdown_link_to_id(url).filter(|id| !id.is_empty())?;
+    let target_id = index.resolve_from(source_id, &link_id)?;
+
+    let mut html_url = format!("{}{}.html", root_prefix, target_id);
+
+    if let Some((_, fragment)) = url.split_once('#').filter(|(_, f)| !f.is_empty()) {
+        html_url.push('#');
+        html_url.push_str(&data::heading_to_slug(
+            &percent_encoding::percent_decode_str(fragment).decode_utf8_lossy(),
+        ));
+    }
+
+    Some(html_url)
+}
+
 /// For a given note id, returns the path its HTML representation _would_ be stored at.
+/// Notes in subfolders of the vault get their HTML files in the same subfolders of the `.html` folder.
 /// Makes no guarantees if that representation currently exists.
-pub fn name_to_html_path(name: &str, vault_path: &path::Path) -> path::PathBuf {
+pub fn id_to_html_path(id: &str, vault_path: &path::Path) -> path::PathBuf {
     // calculate target path
-    let mut tar_path = vault_path.to_path_buf();
-    tar_path.push(".html/");
-
-    tar_path.set_file_name(format!(".html/{}", &data::name_to_id(name)));
+    let mut tar_path = vault_path.join(".html");
+    tar_path.extend(id.split('/'));
     tar_path.set_extension("html");
     tar_path
 }
@@ -246,6 +344,8 @@ mod tests {
             ..Default::default()
         };
         let hb = super::HtmlBuilder::new(&config);
+        let tracker = crate::io::FileTracker::new(&config).unwrap();
+        let index = crate::data::NoteIndex::new(tracker, hb.clone(), &config).0;
 
         let os = crate::data::Note::from_path(
             &std::env::current_dir()
@@ -254,7 +354,7 @@ mod tests {
         )
         .unwrap();
 
-        hb.create_html(&os, true).unwrap();
+        hb.create_html(&os, &index, true).unwrap();
     }
 
     #[test]
@@ -264,6 +364,8 @@ mod tests {
             ..Default::default()
         };
         let hb = super::HtmlBuilder::new(&config);
+        let tracker = crate::io::FileTracker::new(&config).unwrap();
+        let index = crate::data::NoteIndex::new(tracker, hb.clone(), &config).0;
 
         // with math
         let smooth_map = crate::data::Note::from_path(
@@ -273,28 +375,190 @@ mod tests {
         )
         .unwrap();
 
-        hb.create_html(&smooth_map, true).unwrap();
+        hb.create_html(&smooth_map, &index, true).unwrap();
+    }
+
+    #[test]
+    fn test_link_to_html_url() {
+        let tmp = testdir::testdir!();
+        std::fs::write(
+            tmp.join("Special Orthogonal Group.md"),
+            "---\naliases: [SO(3)]\n---\n",
+        )
+        .unwrap();
+
+        let config = crate::Config {
+            vault_path: Some(tmp),
+            ..Default::default()
+        };
+        let tracker = crate::io::FileTracker::new(&config).unwrap();
+        let index =
+            crate::data::NoteIndex::new(tracker, super::HtmlBuilder::new(&config), &config).0;
+
+        assert_eq!(
+            super::link_to_html_url("Manifold", &index, ""),
+            "manifold.html"
+        );
+        assert_eq!(
+            super::link_to_html_url("Smooth Map#Another Heading", &index, ""),
+            "smooth-map.html#another-heading"
+        );
+        assert_eq!(
+            super::link_to_html_url("#Examples", &index, "../"),
+            "#examples"
+        );
+        assert_eq!(
+            super::link_to_html_url("SO(3)", &index, ""),
+            "special-orthogonal-group.html"
+        );
+        assert_eq!(
+            super::link_to_html_url("so(3)#Definition", &index, "../"),
+            "../special-orthogonal-group.html#definition"
+        );
+    }
+
+    #[test]
+    fn test_create_html_heading_anchors() {
+        let tmp = testdir::testdir!();
+        let config = crate::Config {
+            vault_path: Some(tmp.clone()),
+            ..Default::default()
+        };
+        let hb = super::HtmlBuilder::new(&config);
+        let tracker = crate::io::FileTracker::new(&config).unwrap();
+        let index = crate::data::NoteIndex::new(tracker, hb.clone(), &config).0;
+
+        let path = tmp.join("Lie Group.md");
+        std::fs::write(&path, "See [[Manifold#Definition]].\n\n## Nice Examples\n").unwrap();
+        let note = crate::data::Note::from_path(&path).unwrap();
+
+        hb.create_html(&note, &index, true).unwrap();
+
+        let html = std::fs::read_to_string(super::id_to_html_path("lie-group", &tmp)).unwrap();
+        assert!(html.contains("href=\"manifold.html#definition\""));
+        assert!(html.contains("id=\"nice-examples\""));
+    }
+
+    #[test]
+    fn test_markdown_link_to_html_url() {
+        let tmp = testdir::testdir!();
+        std::fs::create_dir_all(tmp.join("math")).unwrap();
+        std::fs::create_dir_all(tmp.join("physics")).unwrap();
+        std::fs::write(tmp.join("math/Chart.md"), "").unwrap();
+        std::fs::write(tmp.join("math/Lie Group.md"), "").unwrap();
+
+        let config = crate::Config {
+            vault_path: Some(tmp),
+            ..Default::default()
+        };
+        let tracker = crate::io::FileTracker::new(&config).unwrap();
+        let index =
+            crate::data::NoteIndex::new(tracker, super::HtmlBuilder::new(&config), &config).0;
+
+        assert_eq!(
+            super::markdown_link_to_html_url("../math/Chart.md", "physics/motion", &index, "../"),
+            Some("../math/chart.html".to_owned())
+        );
+        assert_eq!(
+            super::markdown_link_to_html_url(
+                "Lie%20Group.md#Nice%20Examples",
+                "math/chart",
+                &index,
+                "../"
+            ),
+            Some("../math/lie-group.html#nice-examples".to_owned())
+        );
+        assert_eq!(
+            super::markdown_link_to_html_url("../images/chart.png", "math/chart", &index, "../"),
+            None
+        );
+        assert_eq!(
+            super::markdown_link_to_html_url("https://example.com", "math/chart", &index, "../"),
+            None
+        );
+        // files sharing the name of a note are no links to it
+        assert_eq!(
+            super::markdown_link_to_html_url("Chart.csv", "math/lie-group", &index, "../"),
+            None
+        );
     }
 
     #[test]
-    fn test_name_to_html_path() {
+    fn test_create_html_subfolders() {
+        let tmp = testdir::testdir!();
+        std::fs::create_dir_all(tmp.join("projects")).unwrap();
+        std::fs::create_dir_all(tmp.join("archive")).unwrap();
+        std::fs::write(tmp.join("projects/Index.md"), "See [[archive/Index]].\n").unwrap();
+        std::fs::write(tmp.join("archive/Index.md"), "").unwrap();
+
+        let config = crate::Config {
+            vault_path: Some(tmp.clone()),
+            ..Default::default()
+        };
+        let hb = super::HtmlBuilder::new(&config);
+        let tracker = crate::io::FileTracker::new(&config).unwrap();
+        let index = crate::data::NoteIndex::new(tracker, hb.clone(), &config).0;
+
+        let note = crate::data::Note::from_path(&tmp.join("projects/Index.md")).unwrap();
+        hb.create_html(&note, &index, true).unwrap();
+
+        let html = std::fs::read_to_string(super::id_to_html_path("projects/index", &tmp)).unwrap();
+        assert!(html.contains("href=\"../archive/index.html\""));
+    }
+
+    #[test]
+    fn test_remove_stale_html() {
+        let tmp = testdir::testdir!();
+        std::fs::create_dir_all(tmp.join("math")).unwrap();
+        std::fs::write(tmp.join("Root.md"), "").unwrap();
+        std::fs::write(tmp.join("math/Lie Group.md"), "").unwrap();
+
+        // HTML files as created by earlier versions, next to current ones
+        std::fs::create_dir_all(tmp.join(".html/math")).unwrap();
+        for file in [
+            "root.html",
+            "lie-group.html",
+            "math/lie-group.html",
+            "notes.txt",
+        ] {
+            std::fs::write(tmp.join(".html").join(file), "").unwrap();
+        }
+
+        let config = crate::Config {
+            vault_path: Some(tmp.clone()),
+            ..Default::default()
+        };
+        let hb = super::HtmlBuilder::new(&config);
+        let tracker = crate::io::FileTracker::new(&config).unwrap();
+        let (_index, errors) = crate::data::NoteIndex::new(tracker, hb, &config);
+        assert!(errors.is_empty());
+
+        // only the flat file of the note in a subfolder is removed
+        assert!(tmp.join(".html/root.html").exists());
+        assert!(!tmp.join(".html/lie-group.html").exists());
+        assert!(tmp.join(".html/math/lie-group.html").exists());
+        assert!(tmp.join(".html/notes.txt").exists());
+    }
+
+    #[test]
+    fn test_id_to_html_path() {
         // let config = crate::Config::default();
         let vault_path = std::env::current_dir().unwrap().join("tests");
 
         assert_eq!(
-            super::name_to_html_path("Lie Group", &vault_path),
+            super::id_to_html_path("lie-group", &vault_path),
             std::env::current_dir()
                 .unwrap()
                 .join("tests/.html/lie-group.html")
         );
         assert_eq!(
-            super::name_to_html_path("lie-group", &vault_path),
+            super::id_to_html_path("common/notes/math/lie-group", &vault_path),
             std::env::current_dir()
                 .unwrap()
-                .join("tests/.html/lie-group.html")
+                .join("tests/.html/common/notes/math/lie-group.html")
         );
         assert_eq!(
-            super::name_to_html_path("books", &vault_path),
+            super::id_to_html_path("books", &vault_path),
             std::env::current_dir()
                 .unwrap()
                 .join("tests/.html/books.html")
@@ -305,9 +569,11 @@ mod tests {
     fn test_create_html_creates_files() {
         let mut config = crate::Config::default();
         let vault_path = std::env::current_dir().unwrap().join("tests");
-        let b_path = super::name_to_html_path("Books", &vault_path);
+        let b_path = super::id_to_html_path("common/notes/books", &vault_path);
         config.vault_path = Some(vault_path);
         let hb = super::HtmlBuilder::new(&config);
+        let tracker = crate::io::FileTracker::new(&config).unwrap();
+        let index = crate::data::NoteIndex::new(tracker, hb.clone(), &config).0;
 
         let books = crate::data::Note::from_path(
             &std::env::current_dir()
@@ -322,7 +588,7 @@ mod tests {
 
         // assert!(!b_path.exists());
 
-        hb.create_html(&books, true).unwrap();
+        hb.create_html(&books, &index, true).unwrap();
 
         assert!(b_path.exists());
     }
@@ -331,9 +597,11 @@ mod tests {
     fn test_create_html_creates_files_with_math() {
         let mut config = crate::Config::default();
         let vault_path = std::env::current_dir().unwrap().join("tests");
-        let lg_path = super::name_to_html_path("Lie Group", &vault_path);
+        let lg_path = super::id_to_html_path("common/notes/math/lie-group", &vault_path);
         config.vault_path = Some(vault_path);
         let hb = super::HtmlBuilder::new(&config);
+        let tracker = crate::io::FileTracker::new(&config).unwrap();
+        let index = crate::data::NoteIndex::new(tracker, hb.clone(), &config).0;
 
         // with math
         let liegroup = crate::data::Note::from_path(
@@ -349,7 +617,7 @@ mod tests {
 
         // assert!(!lg_path.exists());
 
-        hb.create_html(&liegroup, true).unwrap();
+        hb.create_html(&liegroup, &index, true).unwrap();
 
         assert!(lg_path.exists());
     }
@@ -9,3 +9,6 @@ pub use html_builder::HtmlBuilder;
 
 mod git_manager;
 pub use git_manager::GitManager;
+
+mod filter_history;
+pub use filter_history::FilterHistory;
@@ -55,6 +55,10 @@ fn main() {
     init_hooks().expect("Error in hook initialization.");
     let mut terminal = init_terminal().expect("Error in terminal initialization.");
 
+    // Termination signals only set a flag, so the main loop can end regularly and the index cache is still saved.
+    // They are registered before indexing, so a signal received during it still lets the index finish and be saved.
+    let terminate = init_signals().expect("Error in signal handler initialization.");
+
     // create a call back for the loading screen
     // Create the app state
     let (mut app, errors) =
@@ -65,6 +69,10 @@ fn main() {
 
     // Main loop
     'main: loop {
+        if terminate.load(std::sync::atomic::Ordering::Relaxed) {
+            break 'main;
+        }
+
         // Draw the current screen.
         terminal
             .draw(|frame: &mut Frame| {
@@ -151,6 +159,17 @@ fn init_hooks() -> error::Result<()> {
     Ok(())
 }
 
+/// Registers handlers for termination signals, returning a flag that is set once one of them is received.
+/// SIGINT and SIGQUIT are left out: raw mode turns Ctrl-C and Ctrl-\ into key events, and an editor started by rucola shares its process group, so these signals would end rucola after the editor returned.
+fn init_signals() -> std::io::Result<std::sync::Arc<std::sync::atomic::AtomicBool>> {
+    let terminate = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    #[cfg(unix)]
+    for signal in [signal_hook::consts::SIGTERM, signal_hook::consts::SIGHUP] {
+        signal_hook::flag::register(signal, terminate.clone())?;
+    }
+    Ok(terminate)
+}
+
 /// Ratatui boilerplate to put the terminal into a TUI state
 fn init_terminal() -> std::io::Result<Terminal<impl ratatui::backend::Backend>> {
     std::io::stdout().execute(terminal::EnterAlternateScreen)?;
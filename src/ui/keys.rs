@@ -0,0 +1,648 @@
+use std::collections::{HashMap, HashSet};
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::text::Span;
+
+use crate::{error, ui};
+
+/// Everything the user can do with a single key press outside of submenus and text boxes.
+/// The keys for each action can be changed in the `[keys]` table of the config, using the snake case names of the variants.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// Quit rucola.
+    Quit,
+    /// Move the selection up.
+    Up,
+    /// Move the selection down.
+    Down,
+    /// Move the selection up by a page.
+    PageUp,
+    /// Move the selection down by a page.
+    PageDown,
+    /// Open the selected note or link.
+    Open,
+    /// Open the note in the editor.
+    Edit,
+    /// Open the note in the viewer.
+    View,
+    /// Open the note in the secondary viewer.
+    ViewSecondary,
+    /// Show or hide the preview of the note.
+    TogglePreview,
+    /// Open the file management submenu of the select screen.
+    FileMenu,
+    /// Open the sorting submenu of the select screen.
+    SortMenu,
+    /// Start typing into the filter area.
+    Filter,
+    /// Show the help for the filter syntax.
+    FilterHelp,
+    /// Clear the filter.
+    ClearFilter,
+    /// Switch between requiring all or any of the filter conditions.
+    ToggleAnyAll,
+    /// Show the list of all tags.
+    TagList,
+    /// Show the list of saved filters.
+    SavedFilters,
+    /// Show the lines matching the full-text search.
+    Results,
+    /// Show the tasks of all notes.
+    Tasks,
+    /// Select the first note of the list.
+    Top,
+    /// Open today's diary note.
+    Diary,
+    /// Mark or unmark the selected note.
+    Mark,
+    /// Mark all notes matching the filter.
+    MarkAll,
+    /// Unmark all notes.
+    UnmarkAll,
+    /// Return from the display screen to the select screen.
+    Home,
+    /// Return to the previous note.
+    Back,
+    /// Focus the next table of the display screen.
+    NextTable,
+    /// Focus the previous table of the display screen.
+    PreviousTable,
+    /// Rename the displayed note.
+    Rename,
+    /// Move the displayed note.
+    Move,
+    /// Delete the displayed note.
+    Delete,
+    /// Copy the displayed note.
+    Copy,
+    /// Select the next link in the preview.
+    NextLink,
+    /// Select the previous link in the preview.
+    PreviousLink,
+    /// Open the command palette to search all actions of the screen.
+    Palette,
+    /// Open the quick switcher to jump to any note.
+    QuickSwitcher,
+}
+
+impl Action {
+    /// All actions available on the select screen.
+    pub const SELECT: &[Action] = &[
+        Action::Quit,
+        Action::Up,
+        Action::Down,
+        Action::PageUp,
+        Action::PageDown,
+        Action::Open,
+        Action::Edit,
+        Action::View,
+        Action::ViewSecondary,
+        Action::TogglePreview,
+        Action::FileMenu,
+        Action::SortMenu,
+        Action::Filter,
+        Action::FilterHelp,
+        Action::ClearFilter,
+        Action::ToggleAnyAll,
+        Action::TagList,
+        Action::SavedFilters,
+        Action::Results,
+        Action::Tasks,
+        Action::Top,
+        Action::Diary,
+        Action::Mark,
+        Action::MarkAll,
+        Action::UnmarkAll,
+        Action::Palette,
+        Action::QuickSwitcher,
+    ];
+
+    /// All actions available on the display screen.
+    pub const DISPLAY: &[Action] = &[
+        Action::Quit,
+        Action::Up,
+        Action::Down,
+        Action::PageUp,
+        Action::PageDown,
+        Action::Open,
+        Action::Edit,
+        Action::View,
+        Action::ViewSecondary,
+        Action::TogglePreview,
+        Action::Home,
+        Action::Back,
+        Action::NextTable,
+        Action::PreviousTable,
+        Action::Rename,
+        Action::Move,
+        Action::Delete,
+        Action::Copy,
+        Action::NextLink,
+        Action::PreviousLink,
+        Action::Palette,
+        Action::QuickSwitcher,
+    ];
+
+    /// The keys bound to this action unless the config says otherwise.
+    /// The first key is the one shown in hints.
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q", "Q"],
+            Action::Up => &["k", "K", "up"],
+            Action::Down => &["j", "J", "down"],
+            Action::PageUp => &["pageup"],
+            Action::PageDown => &["pagedown"],
+            Action::Open => &["l", "L", "right", "enter"],
+            Action::Edit => &["e", "E"],
+            Action::View => &["v"],
+            Action::ViewSecondary => &["V"],
+            Action::TogglePreview => &["i", "I"],
+            Action::FileMenu => &["m", "M"],
+            Action::SortMenu => &["s", "S"],
+            Action::Filter => &["f", "F", "/"],
+            Action::FilterHelp => &["h", "H", "?"],
+            Action::ClearFilter => &["c", "C"],
+            Action::ToggleAnyAll => &["a", "A"],
+            Action::TagList => &["t", "T"],
+            Action::SavedFilters => &["p", "P"],
+            Action::Results => &["r", "R"],
+            Action::Tasks => &["x", "X"],
+            Action::Top => &["0"],
+            Action::Diary => &["d", "D"],
+            Action::Mark => &["space"],
+            Action::MarkAll => &["*"],
+            Action::UnmarkAll => &["u", "U"],
+            Action::Home => &["f", "F"],
+            Action::Back => &["h", "H", "left"],
+            Action::NextTable => &["tab"],
+            Action::PreviousTable => &["shift+tab"],
+            Action::Rename => &["r", "R"],
+            Action::Move => &["m", "M"],
+            Action::Delete => &["d", "D"],
+            Action::Copy => &["c", "C"],
+            Action::NextLink => &["n"],
+            Action::PreviousLink => &["N"],
+            Action::Palette => &["ctrl+p", ":"],
+            Action::QuickSwitcher => &["ctrl+o"],
+        }
+    }
+
+    /// Returns a short description of this action, as listed in the command palette.
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Up => "Move up",
+            Action::Down => "Move down",
+            Action::PageUp => "Move up by a page",
+            Action::PageDown => "Move down by a page",
+            Action::Open => "Open selected note",
+            Action::Edit => "Edit note",
+            Action::View => "View note",
+            Action::ViewSecondary => "View note in secondary viewer",
+            Action::TogglePreview => "Show or hide preview",
+            Action::FileMenu => "Manage files",
+            Action::SortMenu => "Sorting",
+            Action::Filter => "Type filter",
+            Action::FilterHelp => "Show filter help",
+            Action::ClearFilter => "Clear filter",
+            Action::ToggleAnyAll => "Switch between any and all conditions",
+            Action::TagList => "Show tag list",
+            Action::SavedFilters => "Show saved filters",
+            Action::Results => "Show full-text results",
+            Action::Tasks => "Show tasks",
+            Action::Top => "Go to first note",
+            Action::Diary => "Open today's diary note",
+            Action::Mark => "Mark or unmark selected note",
+            Action::MarkAll => "Mark all notes",
+            Action::UnmarkAll => "Unmark all notes",
+            Action::Home => "Return to note list",
+            Action::Back => "Go back to previous note",
+            Action::NextTable => "Focus next table",
+            Action::PreviousTable => "Focus previous table",
+            Action::Rename => "Rename note",
+            Action::Move => "Move note",
+            Action::Delete => "Delete note",
+            Action::Copy => "Copy note",
+            Action::NextLink => "Select next link",
+            Action::PreviousLink => "Select previous link",
+            Action::Palette => "Open command palette",
+            Action::QuickSwitcher => "Jump to note",
+        }
+    }
+
+    /// Checks if this action only moves within the screen, so listing it in the command palette would not help.
+    pub fn is_navigation(self) -> bool {
+        matches!(
+            self,
+            Action::Up
+                | Action::Down
+                | Action::PageUp
+                | Action::PageDown
+                | Action::Open
+                | Action::Top
+                | Action::NextTable
+                | Action::PreviousTable
+                | Action::NextLink
+                | Action::PreviousLink
+                | Action::Palette
+        )
+    }
+}
+
+/// A key together with the modifiers that have to be held, e.g. `ctrl+p`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    /// The key itself.
+    code: KeyCode,
+    /// The modifiers apart from shift, which is part of the character for letters.
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    /// Parses a key as written in the config, e.g. `q`, `Q`, `enter`, `shift+tab` or `ctrl+alt+p`.
+    pub fn parse(chord: &str) -> error::Result<Self> {
+        let invalid = || error::RucolaError::KeyBinding(format!("Unknown key '{chord}'."));
+
+        // the last part is the key, the parts before are modifiers - which allows `+` and `ctrl++`
+        let (modifier_names, key) = match chord.strip_suffix("++") {
+            Some(modifier_names) => (modifier_names, "+"),
+            None if chord == "+" => ("", "+"),
+            None => chord.rsplit_once('+').unwrap_or(("", chord)),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut shift = false;
+        for name in modifier_names.split('+').filter(|name| !name.is_empty()) {
+            match name.to_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                "shift" => shift = true,
+                _ => return Err(invalid()),
+            }
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) if shift => KeyCode::Char(c.to_ascii_uppercase()),
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_lowercase().as_str() {
+                "tab" if shift => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                name => name
+                    .strip_prefix('f')
+                    .and_then(|number| number.parse::<u8>().ok())
+                    .filter(|number| (1..=12).contains(number))
+                    .map(KeyCode::F)
+                    .ok_or_else(invalid)?,
+            },
+        };
+
+        Ok(Self { code, modifiers })
+    }
+
+    /// Checks if the given key event presses this chord.
+    /// Shift is ignored for characters and `BackTab`, as it is already part of them.
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let mut modifiers = key.modifiers;
+        if matches!(key.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        self.code == key.code && self.modifiers == modifiers
+    }
+
+    /// Returns how this chord is shown in hints, with letters in upper case like all other hotkeys.
+    /// Upper case letters are typed with shift and therefore shown like `Shift+V`.
+    pub fn name(&self) -> String {
+        let key = match self.code {
+            KeyCode::Char(' ') => "Space".to_owned(),
+            KeyCode::Char(c) if c.is_uppercase() => format!("Shift+{c}"),
+            KeyCode::Char(c) => c.to_uppercase().to_string(),
+            KeyCode::Enter => "↵".to_owned(),
+            KeyCode::Left => "←".to_owned(),
+            KeyCode::Right => "→".to_owned(),
+            KeyCode::Up => "↑".to_owned(),
+            KeyCode::Down => "↓".to_owned(),
+            KeyCode::Tab => "Tab".to_owned(),
+            KeyCode::BackTab => "Shift+Tab".to_owned(),
+            KeyCode::PageUp => "PgUp".to_owned(),
+            KeyCode::PageDown => "PgDn".to_owned(),
+            KeyCode::Esc => "Esc".to_owned(),
+            KeyCode::F(number) => format!("F{number}"),
+            code => format!("{code:?}"),
+        };
+
+        let mut name = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            name.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            name.push_str("Alt+");
+        }
+        name.push_str(&key);
+        name
+    }
+}
+
+/// The keys bound to each action, taken from the config or else the defaults.
+#[derive(Debug, Clone)]
+pub struct KeyBindings {
+    /// The keys of each action, the first one being shown in hints.
+    keys: HashMap<Action, Vec<KeyChord>>,
+    /// The actions whose keys were set in the config, which take precedence over the defaults of other actions.
+    configured: HashSet<Action>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self::new(&crate::Config::default()).expect("The default keys should be valid.")
+    }
+}
+
+impl KeyBindings {
+    /// Creates the key bindings from the `[keys]` table of the config, falling back to the defaults for all actions not in it.
+    pub fn new(config: &crate::Config) -> error::Result<Self> {
+        let mut keys = HashMap::new();
+
+        for action in Action::SELECT.iter().chain(Action::DISPLAY) {
+            let chords = match config.keys.get(action) {
+                Some(chords) => chords
+                    .iter()
+                    .map(|chord| KeyChord::parse(chord))
+                    .collect::<error::Result<Vec<_>>>()?,
+                None => action
+                    .default_keys()
+                    .iter()
+                    .map(|chord| KeyChord::parse(chord))
+                    .collect::<error::Result<Vec<_>>>()?,
+            };
+            keys.insert(*action, chords);
+        }
+
+        Ok(Self {
+            keys,
+            configured: config.keys.keys().copied().collect(),
+        })
+    }
+
+    /// Returns the action of the given candidates the given key is bound to, if any.
+    /// Keys set in the config take precedence over the default keys of other actions.
+    pub fn action(&self, key: &KeyEvent, candidates: &[Action]) -> Option<Action> {
+        let bound = |action: &&Action| {
+            self.keys
+                .get(action)
+                .is_some_and(|chords| chords.iter().any(|chord| chord.matches(key)))
+        };
+
+        candidates
+            .iter()
+            .filter(|action| self.configured.contains(action))
+            .find(bound)
+            .or_else(|| candidates.iter().find(bound))
+            .copied()
+    }
+
+    /// Returns the names of all keys bound to the given action, without duplicates.
+    /// A shifted letter is left out if the letter itself is bound as well, so `j` and `J` are shown as just `J`.
+    pub fn names(&self, action: Action) -> Vec<String> {
+        let mut names = Vec::new();
+        for chord in self.keys.get(&action).into_iter().flatten() {
+            let name = chord.name();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+            .iter()
+            .filter(|name| {
+                name.strip_prefix("Shift+")
+                    .is_none_or(|unshifted| !names.iter().any(|other| other == unshifted))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the name of the first key bound to the given action, as shown in hints.
+    pub fn name(&self, action: Action) -> String {
+        self.names(action).into_iter().next().unwrap_or_default()
+    }
+
+    /// Returns the spans of a hint for the given action, showing its first key.
+    /// If that key is the first letter of the label, it is highlighted within the label like `E`dit, otherwise put in front like `X: Tasks`.
+    /// Actions without keys have no hint.
+    pub fn hint(&self, action: Action, label: &str, styles: &ui::UiStyles) -> Vec<Span<'static>> {
+        let name = self.name(action);
+        self.hint_from_names(
+            if name.is_empty() { vec![] } else { vec![name] },
+            label,
+            styles,
+        )
+    }
+
+    /// Returns the spans of a hint for the given action like `hint`, but showing all of its keys, e.g. `J/↓: Down`.
+    pub fn hint_all(
+        &self,
+        action: Action,
+        label: &str,
+        styles: &ui::UiStyles,
+    ) -> Vec<Span<'static>> {
+        self.hint_from_names(self.names(action), label, styles)
+    }
+
+    /// Creates the spans of a hint for the given key names.
+    fn hint_from_names(
+        &self,
+        names: Vec<String>,
+        label: &str,
+        styles: &ui::UiStyles,
+    ) -> Vec<Span<'static>> {
+        let mut chars = label.chars();
+        match (names.as_slice(), chars.next()) {
+            ([], _) => vec![],
+            ([name], Some(first)) if *name == first.to_uppercase().to_string() => vec![
+                Span::styled(name.clone(), styles.hotkey_style),
+                Span::styled(chars.as_str().to_owned(), styles.text_style),
+            ],
+            _ => {
+                let mut spans = Vec::new();
+                for (position, name) in names.into_iter().enumerate() {
+                    if position > 0 {
+                        spans.push(Span::styled("/", styles.text_style));
+                    }
+                    spans.push(Span::styled(name, styles.hotkey_style));
+                }
+                spans.push(Span::styled(format!(": {label}"), styles.text_style));
+                spans
+            }
+        }
+    }
+}
+
+/// Joins the given hints into the spans of a single line, separated by `──`. Empty hints are left out.
+pub fn join_hints(
+    hints: impl IntoIterator<Item = Vec<Span<'static>>>,
+    styles: &ui::UiStyles,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    for hint in hints.into_iter().filter(|hint| !hint.is_empty()) {
+        if !spans.is_empty() {
+            spans.push(Span::styled("──", styles.text_style));
+        }
+        spans.extend(hint);
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_key_chords() {
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+
+        let chord = KeyChord::parse("ctrl+p").unwrap();
+        assert!(chord.matches(&key(KeyCode::Char('p'), KeyModifiers::CONTROL)));
+        assert!(!chord.matches(&key(KeyCode::Char('p'), KeyModifiers::NONE)));
+        assert_eq!(chord.name(), "Ctrl+P");
+
+        // shift is part of the character
+        let chord = KeyChord::parse("V").unwrap();
+        assert!(chord.matches(&key(KeyCode::Char('V'), KeyModifiers::SHIFT)));
+        assert!(!chord.matches(&key(KeyCode::Char('v'), KeyModifiers::NONE)));
+        assert_eq!(KeyChord::parse("shift+v").unwrap(), chord);
+        assert_eq!(chord.name(), "Shift+V");
+        assert_eq!(KeyChord::parse("v").unwrap().name(), "V");
+
+        let chord = KeyChord::parse("shift+tab").unwrap();
+        assert!(chord.matches(&key(KeyCode::BackTab, KeyModifiers::SHIFT)));
+        assert_eq!(chord.name(), "Shift+Tab");
+
+        assert_eq!(
+            KeyChord::parse("alt++").unwrap(),
+            KeyChord {
+                code: KeyCode::Char('+'),
+                modifiers: KeyModifiers::ALT
+            }
+        );
+        let chord = KeyChord::parse("+").unwrap();
+        assert!(chord.matches(&key(KeyCode::Char('+'), KeyModifiers::SHIFT)));
+        assert_eq!(chord.name(), "+");
+        assert_eq!(KeyChord::parse("F5").unwrap().name(), "F5");
+        assert_eq!(KeyChord::parse("space").unwrap().name(), "Space");
+
+        for chord in ["", "ctrl+", "hyper+p", "f13", "pgdn"] {
+            assert!(KeyChord::parse(chord).is_err(), "{chord}");
+        }
+
+        // all default keys are valid
+        for action in Action::SELECT.iter().chain(Action::DISPLAY) {
+            for chord in action.default_keys() {
+                assert!(KeyChord::parse(chord).is_ok(), "{chord}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_key_bindings() {
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        let defaults = KeyBindings::default();
+        assert_eq!(
+            defaults.action(&key('d'), Action::SELECT),
+            Some(Action::Diary)
+        );
+        assert_eq!(
+            defaults.action(&key('d'), Action::DISPLAY),
+            Some(Action::Delete)
+        );
+        assert_eq!(defaults.action(&key('w'), Action::SELECT), None);
+
+        let config = crate::Config {
+            keys: [
+                (Action::Tasks, vec!["d".to_owned(), "ctrl+t".to_owned()]),
+                (Action::Quit, vec![]),
+            ]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+        let keys = KeyBindings::new(&config).unwrap();
+
+        // configured keys take precedence, but only replace the defaults of their own action
+        assert_eq!(keys.action(&key('d'), Action::SELECT), Some(Action::Tasks));
+        assert_eq!(keys.action(&key('x'), Action::SELECT), None);
+        assert_eq!(
+            keys.action(&key('d'), Action::DISPLAY),
+            Some(Action::Delete)
+        );
+        assert_eq!(keys.action(&key('q'), Action::SELECT), None);
+
+        // hints show the configured keys
+        let styles = ui::UiStyles::default();
+        let text = |spans: Vec<Span>| {
+            spans
+                .iter()
+                .map(|span| span.content.to_string())
+                .collect::<String>()
+        };
+        assert_eq!(text(keys.hint(Action::Tasks, "Tasks", &styles)), "D: Tasks");
+        assert_eq!(text(keys.hint(Action::Edit, "Edit", &styles)), "Edit");
+        assert_eq!(keys.hint(Action::Edit, "Edit", &styles)[0].content, "E");
+        assert_eq!(text(keys.hint(Action::Quit, "Quit", &styles)), "");
+        assert_eq!(
+            text(keys.hint_all(Action::Down, "Down", &styles)),
+            "J/↓: Down"
+        );
+        assert_eq!(
+            text(defaults.hint(Action::ViewSecondary, "View", &styles)),
+            "Shift+V: View"
+        );
+        assert_eq!(
+            text(defaults.hint_all(Action::PreviousLink, "Previous", &styles)),
+            "Shift+N: Previous"
+        );
+        assert_ne!(
+            defaults.name(Action::View),
+            defaults.name(Action::ViewSecondary)
+        );
+
+        // keys are read from the config file by the names of the actions
+        let config: crate::Config =
+            toml::from_str("[keys]\nquit = [\"ctrl+c\"]\nnext_link = [\"tab\"]").unwrap();
+        let keys = KeyBindings::new(&config).unwrap();
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(keys.action(&ctrl_c, Action::SELECT), Some(Action::Quit));
+        let tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(keys.action(&tab, Action::DISPLAY), Some(Action::NextLink));
+
+        let config = crate::Config {
+            keys: [(Action::Quit, vec!["hyper+q".to_owned()])]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        assert_eq!(
+            KeyBindings::new(&config).unwrap_err().to_string(),
+            "Invalid key binding: Unknown key 'hyper+q'."
+        );
+    }
+}
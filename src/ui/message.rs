@@ -11,6 +11,12 @@ pub enum Message {
     DisplayStackPop,
     /// Pushes the given id to the top of the display stack.
     DisplayStackPush(String),
+    /// Shows the task screen instead of the select screen.
+    TaskScreenOpen,
+    /// Returns from the task screen to the select screen.
+    TaskScreenClose,
+    /// Opens the quick switcher to jump to any note.
+    QuickSwitcherOpen,
     /// Restore the terminal, execute the given command and re-enter
     OpenExternalCommand(Box<std::process::Command>),
 }
@@ -32,7 +38,10 @@ impl From<Message> for TerminalMessage {
             Message::None
             | Message::DisplayStackClear
             | Message::DisplayStackPop
-            | Message::DisplayStackPush(_) => Self::None,
+            | Message::DisplayStackPush(_)
+            | Message::TaskScreenOpen
+            | Message::TaskScreenClose
+            | Message::QuickSwitcherOpen => Self::None,
             Message::Quit => Self::Quit,
             Message::OpenExternalCommand(cmd) => Self::OpenExternalCommand(cmd),
         }
@@ -8,3 +8,16 @@ pub use screen::Screen;
 
 mod uistyles;
 pub use uistyles::UiStyles;
+
+mod preview;
+pub use preview::Preview;
+
+mod keys;
+pub use keys::Action;
+pub use keys::join_hints;
+pub use keys::KeyBindings;
+
+mod palette;
+pub use palette::Palette;
+pub use palette::PaletteChoice;
+pub use palette::PaletteEntry;
@@ -0,0 +1,404 @@
+use fuzzy_matcher::FuzzyMatcher;
+use itertools::Itertools;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{prelude::*, widgets::*};
+use tui_textarea::TextArea;
+
+use crate::{data, ui};
+
+/// An entry of a palette, e.g. a command with the keys that run it.
+#[derive(Debug, Clone)]
+pub struct PaletteEntry<T> {
+    /// The name the typed query is matched against.
+    pub name: String,
+    /// Shown next to the name, e.g. the keys of a command.
+    pub detail: String,
+    /// Further texts the query is matched against, e.g. the aliases of a note.
+    pub keywords: Vec<String>,
+    /// Returned when the entry is chosen.
+    pub value: T,
+}
+
+impl<T> PaletteEntry<T> {
+    /// Creates a new entry with the given name, detail and value.
+    pub fn new(name: impl Into<String>, detail: impl Into<String>, value: T) -> Self {
+        Self {
+            name: name.into(),
+            detail: detail.into(),
+            keywords: Vec::new(),
+            value,
+        }
+    }
+
+    /// Sets the further texts the query is matched against.
+    pub fn with_keywords(mut self, keywords: Vec<String>) -> Self {
+        self.keywords = keywords;
+        self
+    }
+}
+
+/// What happened to a palette after a key press.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteChoice<T> {
+    /// The palette stays open.
+    Pending,
+    /// The palette was closed without choosing an entry.
+    Cancelled,
+    /// The given entry was chosen, closing the palette.
+    Chosen(T),
+}
+
+/// A popup that filters a list of entries by fuzzy matching their names with a typed query, to choose one of them.
+#[derive(Debug, Clone)]
+pub struct Palette<T> {
+    /// All entries to choose from, in the order they are shown without a query.
+    entries: Vec<PaletteEntry<T>>,
+    /// The positions of the entries matching the query, best match first, with the positions of the matched characters of their names.
+    matches: Vec<(usize, Vec<usize>)>,
+    /// The position of the selected entry within the matches.
+    selected: usize,
+    /// The text area to type the query in.
+    query: TextArea<'static>,
+    /// The used styles.
+    styles: ui::UiStyles,
+}
+
+impl Palette<String> {
+    /// Creates a quick switcher to jump to any note, choosing its id.
+    /// The notes are matched by their names, aliases and paths within the vault. The given recently shown notes are listed first, the others by name.
+    pub fn notes(index: &data::NoteIndex, recent: &[String], styles: ui::UiStyles) -> Self {
+        let recent = recent
+            .iter()
+            .filter_map(|id| index.resolve(id))
+            .unique()
+            .collect_vec();
+
+        let others = index
+            .ids()
+            .map(String::as_str)
+            .filter(|id| !recent.contains(id))
+            .filter_map(|id| index.get(id).map(|note| (id, note)))
+            .sorted_by_key(|(id, note)| (note.display_name.to_lowercase(), *id))
+            .map(|(id, _)| id);
+
+        let entries = recent
+            .iter()
+            .copied()
+            .chain(others)
+            .filter_map(|id| {
+                let note = index.get(id)?;
+                let folder = index.folder(id);
+                let path = if folder.is_empty() {
+                    note.name.clone()
+                } else {
+                    format!("{folder}/{}", note.name)
+                };
+                let mut keywords = note.aliases.clone();
+                keywords.push(path);
+                Some(
+                    PaletteEntry::new(note.display_name.as_str(), folder, id.to_owned())
+                        .with_keywords(keywords),
+                )
+            })
+            .collect();
+
+        Self::new("Jump to note", entries, styles)
+    }
+}
+
+impl<T: Clone> Palette<T> {
+    /// Creates a new palette with the given title above the query, showing all the given entries until a query is typed.
+    pub fn new(title: &str, entries: Vec<PaletteEntry<T>>, styles: ui::UiStyles) -> Self {
+        let mut query = TextArea::default();
+        query.set_style(styles.input_style);
+        query.set_cursor_line_style(styles.input_style);
+        query.set_block(Block::bordered().title(Line::from(vec![Span::styled(
+            title.to_owned(),
+            styles.title_style,
+        )])));
+
+        let mut palette = Self {
+            entries,
+            matches: Vec::new(),
+            selected: 0,
+            query,
+            styles,
+        };
+        palette.match_query();
+        palette
+    }
+
+    /// Matches the names and keywords of all entries with the current query and selects the best match.
+    /// Only characters of names are highlighted, so entries matching better by a keyword are shown without highlights.
+    fn match_query(&mut self) {
+        let query = self.query.lines().concat();
+        let matcher = fuzzy_matcher::skim::SkimMatcherV2::default().ignore_case();
+
+        self.matches = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(position, entry)| {
+                if query.is_empty() {
+                    return Some((position, 0, Vec::new()));
+                }
+                let keyword_score = entry
+                    .keywords
+                    .iter()
+                    .filter_map(|keyword| matcher.fuzzy_match(keyword, &query))
+                    .max();
+                match matcher.fuzzy_indices(&entry.name, &query) {
+                    Some((score, indices))
+                        if keyword_score.is_none_or(|keyword_score| keyword_score <= score) =>
+                    {
+                        Some((position, score, indices))
+                    }
+                    name_match => keyword_score
+                        .or(name_match.map(|(score, _)| score))
+                        .map(|score| (position, score, Vec::new())),
+                }
+            })
+            // stable, so entries matching equally well stay in their order
+            .sorted_by_key(|(_, score, _)| std::cmp::Reverse(*score))
+            .map(|(position, _, indices)| (position, indices))
+            .collect();
+        self.selected = 0;
+    }
+
+    /// Handles a key press, moving the selection, typing into the query, choosing an entry or closing the palette.
+    pub fn update(&mut self, key: KeyEvent) -> PaletteChoice<T> {
+        let total = self.matches.len().max(1);
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return PaletteChoice::Cancelled,
+            KeyCode::Enter => {
+                return match self
+                    .matches
+                    .get(self.selected)
+                    .and_then(|(position, _)| self.entries.get(*position))
+                {
+                    Some(entry) => PaletteChoice::Chosen(entry.value.clone()),
+                    None => PaletteChoice::Cancelled,
+                };
+            }
+            KeyCode::Down | KeyCode::Tab => self.selected = (self.selected + 1) % total,
+            KeyCode::Char('n') if ctrl => self.selected = (self.selected + 1) % total,
+            KeyCode::Up | KeyCode::BackTab => self.selected = (self.selected + total - 1) % total,
+            KeyCode::Char('p') if ctrl => self.selected = (self.selected + total - 1) % total,
+            KeyCode::PageDown => {
+                self.selected = (self.selected + 10).min(self.matches.len().saturating_sub(1))
+            }
+            KeyCode::PageUp => self.selected = self.selected.saturating_sub(10),
+            _ => {
+                if self.query.input(key) {
+                    self.match_query();
+                }
+            }
+        }
+        PaletteChoice::Pending
+    }
+
+    /// Draws the palette as a popup in the upper part of the given area.
+    pub fn draw(&self, area: Rect, buf: &mut Buffer) {
+        let width = (area.width * 3 / 5).max(40).min(area.width);
+        let height = (self.entries.len() as u16 + 5)
+            .min(area.height * 3 / 5)
+            .max(6)
+            .min(area.height);
+        let popup_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 4,
+            width,
+            height,
+        };
+
+        let [query_area, list_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(popup_area);
+
+        let rows = self
+            .matches
+            .iter()
+            .filter_map(|(position, indices)| {
+                self.entries.get(*position).map(|entry| (entry, indices))
+            })
+            .map(|(entry, indices)| {
+                // highlight the characters matched by the query
+                let mut spans = Vec::new();
+                for (highlighted, chunk) in &entry
+                    .name
+                    .chars()
+                    .enumerate()
+                    .chunk_by(|(position, _)| indices.contains(position))
+                {
+                    let text = chunk.map(|(_, c)| c).collect::<String>();
+                    spans.push(if highlighted {
+                        Span::styled(text, self.styles.hotkey_style)
+                    } else {
+                        Span::styled(text, self.styles.text_style)
+                    });
+                }
+                Row::new(vec![
+                    Cell::from(Line::from(spans)),
+                    Cell::from(
+                        Line::from(Span::styled(
+                            entry.detail.as_str(),
+                            self.styles.subtitle_style,
+                        ))
+                        .right_aligned(),
+                    ),
+                ])
+            })
+            .collect_vec();
+
+        let count = Line::from(vec![Span::styled(
+            format!("{} / {}", self.matches.len(), self.entries.len()),
+            self.styles.text_style,
+        )])
+        .right_aligned();
+
+        let instructions = Line::from(vec![
+            Span::styled("↑", self.styles.hotkey_style),
+            Span::styled("/", self.styles.text_style),
+            Span::styled("↓", self.styles.hotkey_style),
+            Span::styled(": Choose──", self.styles.text_style),
+            Span::styled("↵", self.styles.hotkey_style),
+            Span::styled(": Confirm──", self.styles.text_style),
+            Span::styled("Esc", self.styles.hotkey_style),
+            Span::styled(": Cancel", self.styles.text_style),
+        ])
+        .right_aligned();
+
+        let mut state = TableState::new().with_selected(Some(self.selected));
+
+        let table = Table::new(rows, [Constraint::Fill(2), Constraint::Fill(1)])
+            .column_spacing(1)
+            .row_highlight_style(self.styles.selected_style)
+            .block(
+                Block::bordered()
+                    .title_top(count)
+                    .title_bottom(instructions),
+            );
+
+        Widget::render(Clear, popup_area, buf);
+        Widget::render(&self.query, query_area, buf);
+        StatefulWidget::render(table, list_area, buf, &mut state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_palette() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let entries = vec![
+            PaletteEntry::new("Edit note", "E", 1),
+            PaletteEntry::new("Sort by words", "S W", 2),
+            PaletteEntry::new("Show tag list", "T", 3),
+            PaletteEntry::new("Delete note", "D", 4),
+        ];
+
+        let mut palette = Palette::new("Commands", entries, ui::UiStyles::default());
+        let names = |palette: &Palette<i32>| {
+            palette
+                .matches
+                .iter()
+                .map(|(position, _)| palette.entries[*position].name.clone())
+                .collect_vec()
+        };
+        assert_eq!(names(&palette).len(), 4);
+
+        // fuzzy matching, case-insensitive
+        for c in "NoTe".chars() {
+            assert_eq!(
+                palette.update(key(KeyCode::Char(c))),
+                PaletteChoice::Pending
+            );
+        }
+        assert_eq!(names(&palette), vec!["Edit note", "Delete note"]);
+
+        // the selection wraps around
+        palette.update(key(KeyCode::Down));
+        palette.update(key(KeyCode::Down));
+        palette.update(key(KeyCode::Up));
+        assert_eq!(
+            palette.update(key(KeyCode::Enter)),
+            PaletteChoice::Chosen(4)
+        );
+
+        for c in "xyz".chars() {
+            palette.update(key(KeyCode::Char(c)));
+        }
+        assert!(names(&palette).is_empty());
+        assert_eq!(
+            palette.update(key(KeyCode::Enter)),
+            PaletteChoice::Cancelled
+        );
+
+        let mut palette = Palette::new(
+            "Commands",
+            vec![PaletteEntry::new("Quit", "Q", 0)],
+            ui::UiStyles::default(),
+        );
+        assert_eq!(palette.update(key(KeyCode::Esc)), PaletteChoice::Cancelled);
+    }
+
+    #[test]
+    fn test_quick_switcher() {
+        let tmp = testdir::testdir!();
+        std::fs::create_dir(tmp.join("topics")).unwrap();
+        std::fs::write(tmp.join("topics/Algebra.md"), "# Algebra\n").unwrap();
+        std::fs::write(
+            tmp.join("Lie Group.md"),
+            "---\naliases: [SO(3)]\n---\n# Lie Group\n",
+        )
+        .unwrap();
+        std::fs::write(tmp.join("Rotation.md"), "[[Lie Group]]\n").unwrap();
+        std::fs::write(tmp.join("Basis.md"), "# Basis\n").unwrap();
+
+        let config = crate::Config {
+            vault_path: Some(tmp),
+            ..Default::default()
+        };
+        let tracker = crate::io::FileTracker::new(&config).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config);
+        let index = data::NoteIndex::new(tracker, builder, &config).0;
+
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let ids = |palette: &Palette<String>| {
+            palette
+                .matches
+                .iter()
+                .map(|(position, _)| palette.entries[*position].value.clone())
+                .collect_vec()
+        };
+
+        // recent notes first, unknown ones skipped, then all others by name
+        let recent = ["rotation".to_string(), "missing".to_string()];
+        let mut switcher = Palette::notes(&index, &recent, ui::UiStyles::default());
+        assert_eq!(
+            ids(&switcher),
+            vec!["rotation", "topics/algebra", "basis", "lie-group"]
+        );
+        assert_eq!(switcher.entries[1].detail, "topics");
+
+        // matching aliases
+        for c in "so(3)".chars() {
+            switcher.update(key(KeyCode::Char(c)));
+        }
+        assert_eq!(ids(&switcher), vec!["lie-group"]);
+        assert_eq!(
+            switcher.update(key(KeyCode::Enter)),
+            PaletteChoice::Chosen("lie-group".to_string())
+        );
+
+        // matching paths
+        let mut switcher = Palette::notes(&index, &[], ui::UiStyles::default());
+        for c in "topics/".chars() {
+            switcher.update(key(KeyCode::Char(c)));
+        }
+        assert_eq!(ids(&switcher), vec!["topics/algebra"]);
+    }
+}
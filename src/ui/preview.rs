@@ -0,0 +1,563 @@
+use comrak::nodes::{AstNode, ListDelimType, ListType, NodeValue};
+use itertools::Itertools;
+use ratatui::prelude::*;
+
+use crate::{data, ui};
+
+/// A piece of text in a single style, possibly belonging to a link.
+#[derive(Debug, Clone)]
+struct Segment {
+    /// The text itself.
+    text: String,
+    /// The style the text is drawn in, unless its link is selected.
+    style: Style,
+    /// The position of the link this text belongs to in the list of links, if any.
+    link: Option<usize>,
+}
+
+/// A line of the rendered note, before it is wrapped to the width of the screen.
+#[derive(Debug, Clone, Default)]
+struct PreviewLine {
+    /// The markers and indentation of the surrounding lists and quotes in front of the line.
+    prefix: Vec<Span<'static>>,
+    /// The indentation in front of the lines continuing this one after wrapping.
+    continuation: Vec<Span<'static>>,
+    /// The content of the line.
+    segments: Vec<Segment>,
+    /// Whether the line is wrapped at the width of the screen or cut off, like the lines of code blocks.
+    wrap: bool,
+    /// Whether the line is a horizontal rule across the width of the screen.
+    rule: bool,
+}
+
+impl PreviewLine {
+    /// Checks if this line is an empty line separating two blocks.
+    fn is_blank(&self) -> bool {
+        self.wrap && !self.rule && self.segments.is_empty()
+    }
+}
+
+/// The marker and indentation a list item or quote adds in front of its lines.
+#[derive(Debug, Clone)]
+struct Prefix {
+    /// Shown in front of the first line, e.g. the bullet of a list item.
+    first: String,
+    /// Shown in front of all following lines.
+    rest: String,
+    /// The style of the prefix.
+    style: Style,
+    /// Whether the first line was already written.
+    used: bool,
+}
+
+/// The markdown content of a note, rendered into styled lines to be shown within the terminal.
+/// Wikilinks and links to other notes are remembered in order of appearance, so they can be selected and followed.
+#[derive(Debug, Clone, Default)]
+pub struct Preview {
+    /// The rendered lines, not yet wrapped.
+    lines: Vec<PreviewLine>,
+    /// The ids of the targets of all links to other notes, in order of appearance.
+    links: Vec<String>,
+    /// The style of a selected link.
+    selected_style: Style,
+    /// The style of horizontal rules.
+    rule_style: Style,
+}
+
+impl Preview {
+    /// Parses the given markdown content and renders it with the given styles.
+    pub fn new(content: &str, styles: &ui::UiStyles) -> Self {
+        let arena = comrak::Arena::new();
+        let root = comrak::parse_document(
+            &arena,
+            content,
+            &comrak::Options {
+                extension: comrak::ExtensionOptions::builder()
+                    .front_matter_delimiter("---".to_owned())
+                    .wikilinks_title_after_pipe(true)
+                    .tasklist(true)
+                    .strikethrough(true)
+                    .table(true)
+                    .build(),
+                ..Default::default()
+            },
+        );
+
+        let mut renderer = Renderer {
+            styles,
+            lines: Vec::new(),
+            links: Vec::new(),
+            prefixes: Vec::new(),
+            tight: false,
+        };
+        renderer.children(root);
+
+        Self {
+            lines: renderer.lines,
+            links: renderer.links,
+            selected_style: styles.selected_style,
+            rule_style: styles.subtitle_style,
+        }
+    }
+
+    /// Returns the ids of the targets of all links to other notes, in order of appearance.
+    pub fn links(&self) -> &[String] {
+        &self.links
+    }
+
+    /// Wraps the rendered lines to the given width, highlighting the link at the given position.
+    /// Also returns the line the highlighted link starts in, so it can be scrolled into view.
+    pub fn to_lines(
+        &self,
+        width: usize,
+        selected_link: Option<usize>,
+    ) -> (Vec<Line<'static>>, Option<usize>) {
+        let mut lines = Vec::new();
+        let mut link_line = None;
+
+        for line in &self.lines {
+            let mut current = line.prefix.clone();
+            let prefix_width = current.iter().map(Span::width).sum::<usize>();
+
+            if line.rule {
+                current.push(Span::styled(
+                    "─".repeat(width.saturating_sub(prefix_width)),
+                    self.rule_style,
+                ));
+                lines.push(Line::from(current));
+                continue;
+            }
+
+            let available = width.saturating_sub(prefix_width).max(1);
+            let mut used = 0;
+            let mut wrapped = false;
+
+            for segment in &line.segments {
+                let selected = segment.link.is_some() && segment.link == selected_link;
+                let style = if selected {
+                    self.selected_style
+                } else {
+                    segment.style
+                };
+
+                // split into words that keep their trailing spaces, and those into pieces fitting into a line
+                let words = if line.wrap {
+                    segment
+                        .text
+                        .split_inclusive(' ')
+                        .flat_map(|word| {
+                            word.chars()
+                                .chunks(available)
+                                .into_iter()
+                                .map(|chunk| chunk.collect::<String>())
+                                .collect_vec()
+                        })
+                        .collect_vec()
+                } else {
+                    vec![segment.text.clone()]
+                };
+
+                for word in words {
+                    let word_width = Span::raw(word.trim_end()).width();
+                    if line.wrap && used > 0 && used + word_width > available {
+                        lines.push(Line::from(std::mem::replace(
+                            &mut current,
+                            line.continuation.clone(),
+                        )));
+                        used = 0;
+                        wrapped = true;
+                    }
+                    // no spaces at the beginning of continued lines
+                    if wrapped && used == 0 && word.trim().is_empty() {
+                        continue;
+                    }
+                    if selected && link_line.is_none() {
+                        link_line = Some(lines.len());
+                    }
+                    used += Span::raw(word.as_str()).width();
+                    current.push(Span::styled(word, style));
+                }
+            }
+
+            lines.push(Line::from(current));
+        }
+
+        (lines, link_line)
+    }
+}
+
+/// Walks the markdown AST of a note and collects its rendered lines and links.
+struct Renderer<'s> {
+    /// The styles to render with.
+    styles: &'s ui::UiStyles,
+    /// The lines rendered so far.
+    lines: Vec<PreviewLine>,
+    /// The ids of the targets of all links rendered so far.
+    links: Vec<String>,
+    /// The markers and indentation of all lists and quotes the current block is contained in.
+    prefixes: Vec<Prefix>,
+    /// Whether the current block is an item of a tight list, which are not separated by empty lines.
+    tight: bool,
+}
+
+impl Renderer<'_> {
+    /// Renders all children of the given node as blocks.
+    fn children<'a>(&mut self, node: &'a AstNode<'a>) {
+        for child in node.children() {
+            self.block(child);
+        }
+    }
+
+    /// Renders the given block node, separating it from the blocks before.
+    fn block<'a>(&mut self, node: &'a AstNode<'a>) {
+        let value = node.data.borrow().value.clone();
+        match value {
+            NodeValue::FrontMatter(_) => {}
+            NodeValue::Heading(heading) => {
+                self.separate();
+                let style = if heading.level <= 2 {
+                    self.styles.title_style
+                } else {
+                    self.styles.subtitle_style
+                };
+                let mut lines = vec![vec![Segment {
+                    text: format!("{} ", "#".repeat(heading.level as usize)),
+                    style,
+                    link: None,
+                }]];
+                self.inlines(node, style, None, &mut lines);
+                for segments in lines {
+                    self.push_line(segments, true);
+                }
+            }
+            NodeValue::Paragraph => {
+                self.separate();
+                let mut lines = vec![Vec::new()];
+                self.inlines(node, self.styles.text_style, None, &mut lines);
+                for segments in lines {
+                    self.push_line(segments, true);
+                }
+            }
+            NodeValue::CodeBlock(code) => {
+                self.separate();
+                for line in code.literal.lines() {
+                    self.push_line(
+                        vec![Segment {
+                            text: format!("  {line}"),
+                            style: self.styles.input_style,
+                            link: None,
+                        }],
+                        false,
+                    );
+                }
+            }
+            NodeValue::HtmlBlock(html) => {
+                self.separate();
+                for line in html.literal.lines() {
+                    self.push_line(
+                        vec![Segment {
+                            text: line.to_owned(),
+                            style: self.styles.subtitle_style,
+                            link: None,
+                        }],
+                        false,
+                    );
+                }
+            }
+            NodeValue::ThematicBreak => {
+                self.separate();
+                let (prefix, continuation) = self.prefix();
+                self.lines.push(PreviewLine {
+                    prefix,
+                    continuation,
+                    rule: true,
+                    ..Default::default()
+                });
+            }
+            NodeValue::BlockQuote | NodeValue::MultilineBlockQuote(_) | NodeValue::Alert(_) => {
+                self.separate();
+                self.prefixes.push(Prefix {
+                    first: "│ ".to_owned(),
+                    rest: "│ ".to_owned(),
+                    style: self.styles.subtitle_style,
+                    used: false,
+                });
+                self.children(node);
+                self.prefixes.pop();
+            }
+            NodeValue::List(list) => {
+                self.separate();
+                let tight = std::mem::replace(&mut self.tight, list.tight);
+                for (position, item) in node.children().enumerate() {
+                    let marker = match item.data.borrow().value {
+                        NodeValue::TaskItem(Some(_)) => "[x] ".to_owned(),
+                        NodeValue::TaskItem(None) => "[ ] ".to_owned(),
+                        _ => match list.list_type {
+                            ListType::Bullet => "• ".to_owned(),
+                            ListType::Ordered => format!(
+                                "{}{} ",
+                                list.start + position,
+                                match list.delimiter {
+                                    ListDelimType::Period => '.',
+                                    ListDelimType::Paren => ')',
+                                }
+                            ),
+                        },
+                    };
+                    self.prefixes.push(Prefix {
+                        rest: " ".repeat(marker.chars().count()),
+                        first: marker,
+                        style: self
+                            .styles
+                            .hotkey_style
+                            .remove_modifier(Modifier::UNDERLINED),
+                        used: false,
+                    });
+                    self.children(item);
+                    self.prefixes.pop();
+                }
+                self.tight = tight;
+            }
+            NodeValue::Table(_) => {
+                self.separate();
+                // render the cells of all rows first to align the columns
+                let rows = node
+                    .children()
+                    .map(|row| {
+                        let header = matches!(row.data.borrow().value, NodeValue::TableRow(true));
+                        let style = if header {
+                            self.styles.subtitle_style
+                        } else {
+                            self.styles.text_style
+                        };
+                        row.children()
+                            .map(|cell| {
+                                let mut lines = vec![Vec::new()];
+                                self.inlines(cell, style, None, &mut lines);
+                                lines.concat()
+                            })
+                            .collect_vec()
+                    })
+                    .collect_vec();
+
+                let cell_width = |cell: &[Segment]| {
+                    cell.iter()
+                        .map(|segment| Span::raw(segment.text.as_str()).width())
+                        .sum::<usize>()
+                };
+                let widths = rows.iter().fold(Vec::new(), |mut widths, row| {
+                    widths.resize(widths.len().max(row.len()), 0);
+                    for (width, cell) in widths.iter_mut().zip(row) {
+                        *width = (*width).max(cell_width(cell));
+                    }
+                    widths
+                });
+
+                for row in rows {
+                    let mut segments = Vec::new();
+                    for (column, mut cell) in row.into_iter().enumerate() {
+                        if column > 0 {
+                            segments.push(Segment {
+                                text: " │ ".to_owned(),
+                                style: self.styles.subtitle_style,
+                                link: None,
+                            });
+                        }
+                        let padding = widths[column].saturating_sub(cell_width(&cell));
+                        segments.append(&mut cell);
+                        segments.push(Segment {
+                            text: " ".repeat(padding),
+                            style: self.styles.text_style,
+                            link: None,
+                        });
+                    }
+                    self.push_line(segments, false);
+                }
+            }
+            _ => self.children(node),
+        }
+    }
+
+    /// Renders the inline children of the given node in the given style into the given lines, starting new lines at hard line breaks.
+    fn inlines<'a>(
+        &mut self,
+        node: &'a AstNode<'a>,
+        style: Style,
+        link: Option<usize>,
+        lines: &mut Vec<Vec<Segment>>,
+    ) {
+        for child in node.children() {
+            let value = child.data.borrow().value.clone();
+            let text = match value {
+                NodeValue::Text(text) => Some((text, style)),
+                NodeValue::Code(code) => Some((code.literal, self.styles.input_style)),
+                NodeValue::HtmlInline(html) => Some((html, self.styles.subtitle_style)),
+                NodeValue::SoftBreak => Some((" ".to_owned(), style)),
+                NodeValue::LineBreak => {
+                    lines.push(Vec::new());
+                    None
+                }
+                NodeValue::Emph => {
+                    self.inlines(child, style.add_modifier(Modifier::ITALIC), link, lines);
+                    None
+                }
+                NodeValue::Strong => {
+                    self.inlines(child, style.add_modifier(Modifier::BOLD), link, lines);
+                    None
+                }
+                NodeValue::Strikethrough => {
+                    self.inlines(
+                        child,
+                        style.add_modifier(Modifier::CROSSED_OUT),
+                        link,
+                        lines,
+                    );
+                    None
+                }
+                NodeValue::WikiLink(wikilink) => {
+                    let link = self.add_link(data::link_to_id(&wikilink.url));
+                    self.inlines(child, self.styles.hotkey_style, link, lines);
+                    None
+                }
+                NodeValue::Link(markdown_link) => {
+                    match data::markdown_link_to_id(&markdown_link.url) {
+                        Some(id) => {
+                            let link = self.add_link(id);
+                            self.inlines(child, self.styles.hotkey_style, link, lines);
+                        }
+                        // links to websites and other files than notes cannot be followed
+                        None => self.inlines(child, self.styles.subtitle_style, link, lines),
+                    }
+                    None
+                }
+                NodeValue::Image(_) => {
+                    self.inlines(child, self.styles.subtitle_style, link, lines);
+                    None
+                }
+                _ => {
+                    self.inlines(child, style, link, lines);
+                    None
+                }
+            };
+
+            if let (Some((text, style)), Some(line)) = (text, lines.last_mut()) {
+                line.push(Segment { text, style, link });
+            }
+        }
+    }
+
+    /// Remembers a link to the note of the given id and returns its position.
+    /// Links to headings within the same note have no target and are not remembered.
+    fn add_link(&mut self, id: String) -> Option<usize> {
+        if id.is_empty() {
+            return None;
+        }
+        self.links.push(id);
+        Some(self.links.len() - 1)
+    }
+
+    /// Returns the prefix of the next line and of the lines continuing it after wrapping.
+    fn prefix(&mut self) -> (Vec<Span<'static>>, Vec<Span<'static>>) {
+        let mut first = Vec::new();
+        let mut rest = Vec::new();
+        for prefix in &mut self.prefixes {
+            let text = if prefix.used {
+                prefix.rest.clone()
+            } else {
+                prefix.first.clone()
+            };
+            prefix.used = true;
+            first.push(Span::styled(text, prefix.style));
+            rest.push(Span::styled(prefix.rest.clone(), prefix.style));
+        }
+        (first, rest)
+    }
+
+    /// Adds a line with the given content.
+    fn push_line(&mut self, segments: Vec<Segment>, wrap: bool) {
+        let (prefix, continuation) = self.prefix();
+        self.lines.push(PreviewLine {
+            prefix,
+            continuation,
+            segments,
+            wrap,
+            rule: false,
+        });
+    }
+
+    /// Adds an empty line before the next block, unless it is the first one or an item of a tight list.
+    fn separate(&mut self) {
+        if self.tight || self.lines.last().is_none_or(PreviewLine::is_blank) {
+            return;
+        }
+        let prefix = self
+            .prefixes
+            .iter()
+            .map(|prefix| Span::styled(prefix.rest.trim_end().to_owned(), prefix.style))
+            .collect_vec();
+        self.lines.push(PreviewLine {
+            prefix: prefix.clone(),
+            continuation: prefix,
+            wrap: true,
+            ..Default::default()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_preview() {
+        let content = "---\ntitle: Lie Groups\n---\n# Lie Groups\n\nA *smooth* [[Manifold]] with a [group](../Algebra/Group.md) structure, see [the wiki](https://en.wikipedia.org).\n\n- [[Atlas|Atlases]]\n- [ ] chart\n\n> quoted\n> text\n\n```\nlet x = 1;\n```\n\n| a | bb |\n|---|----|\n| ccc | d |\n";
+
+        let preview = Preview::new(content, &ui::UiStyles::default());
+        assert_eq!(preview.links(), ["manifold", "../algebra/group", "atlas"]);
+
+        let (lines, link_line) = preview.to_lines(24, Some(1));
+        let text = lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect_vec();
+
+        assert_eq!(
+            text,
+            vec![
+                "# Lie Groups",
+                "",
+                "A smooth Manifold with a ",
+                "group structure, see the ",
+                "wiki.",
+                "",
+                "• Atlases",
+                "[ ] chart",
+                "",
+                "│ quoted text",
+                "",
+                "  let x = 1;",
+                "",
+                "a   │ bb",
+                "ccc │ d ",
+            ]
+        );
+        assert_eq!(link_line, Some(3));
+
+        // the selected link is highlighted
+        let styles = ui::UiStyles::default();
+        assert!(lines[3]
+            .spans
+            .iter()
+            .any(|span| span.content == "group" && span.style == styles.selected_style));
+        assert!(lines[2]
+            .spans
+            .iter()
+            .any(|span| span.content == "Manifold" && span.style == styles.hotkey_style));
+    }
+}
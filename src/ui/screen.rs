@@ -7,6 +7,9 @@ pub use select_screen::StatsShow;
 mod display_screen;
 pub use display_screen::DisplayScreen;
 
+mod task_screen;
+pub use task_screen::TaskScreen;
+
 use crate::{error, ui};
 
 /// A trait that is implemented by different screens within the application.
@@ -1,5 +1,7 @@
 use crate::{data, error, io, ui};
 
+use std::cell::Cell;
+
 use itertools::Itertools;
 use ratatui::crossterm::event::KeyCode;
 use ratatui::{prelude::*, widgets::*};
@@ -16,8 +18,13 @@ enum DisplayMode {
     Move,
     /// Confirming delete
     Delete,
+    /// Searching the command palette.
+    Palette,
 }
 
+/// The value of `foc_table` while the preview is focused instead of one of the link tables.
+const PREVIEW_FOCUS: usize = 4;
+
 /// The display screen displays a single note to the user.
 pub struct DisplayScreen {
     // === CONFIG ===
@@ -27,8 +34,12 @@ pub struct DisplayScreen {
     builder: io::HtmlBuilder,
     /// The used styles.
     styles: ui::UiStyles,
+    /// The keys bound to the actions of this screen.
+    keys: ui::KeyBindings,
 
     // === DATA ===
+    /// The id of the displayed note.
+    id: String,
     /// The internal stats of the displayed note.
     note: data::Note,
     /// A reference to the index of all notes
@@ -39,16 +50,32 @@ pub struct DisplayScreen {
     /// - l2 backlinks
     /// - l2 links
     links: [Vec<(String, String)>; 4],
+    /// The links of this note pointing to headings, as display name of the target note, the heading text and whether that heading exists.
+    heading_links: Vec<(String, String, bool)>,
+    /// The content of the note, rendered for the preview.
+    preview: ui::Preview,
 
     // === UI ===
     /// The text area used to create new notes.
     name_area: tui_textarea::TextArea<'static>,
+    /// The command palette listing the actions of this screen.
+    palette: ui::Palette<ui::Action>,
     /// The index of the note selected in each table
     selected: [usize; 4],
-    /// The index of the primary table currently focused
+    /// The index of the primary table currently focused, or `PREVIEW_FOCUS` for the preview.
     foc_table: usize,
     /// Current input mode
     mode: DisplayMode,
+    /// Whether the preview is shown in place of the level 2 link tables.
+    show_preview: bool,
+    /// The position of the link selected in the preview, if any.
+    selected_link: Option<usize>,
+    /// The first line of the preview shown, kept within the rendered lines when drawing.
+    preview_scroll: Cell<usize>,
+    /// The number of lines of the preview shown at once when it was last drawn, to scroll by pages.
+    preview_height: Cell<usize>,
+    /// Whether to scroll the selected link into view when drawing the next time.
+    reveal_link: Cell<bool>,
 }
 
 impl DisplayScreen {
@@ -59,8 +86,14 @@ impl DisplayScreen {
         manager: io::FileManager,
         builder: io::HtmlBuilder,
         styles: ui::UiStyles,
+        keys: ui::KeyBindings,
+        show_preview: bool,
     ) -> error::Result<Self> {
         let index_b = index.borrow();
+        // Resolve the id, in case the note was opened by a link
+        let note_id = index_b
+            .resolve(note_id)
+            .ok_or_else(|| error::RucolaError::NoteNotFound(note_id.to_owned()))?;
         // Cache the note
         let note = index_b
             .get(note_id)
@@ -86,6 +119,28 @@ impl DisplayScreen {
             .unique()
             .collect();
 
+        // Resolve links to headings against the outline of their target
+        let heading_links = note
+            .heading_links
+            .iter()
+            .map(|(id, fragment)| {
+                // links within the note itself have no target
+                let id = if id.is_empty() {
+                    note_id
+                } else {
+                    index_b.resolve_from(note_id, id).unwrap_or(id)
+                };
+                let name = index_b
+                    .get(id)
+                    .map(|target| target.display_name.clone())
+                    .unwrap_or_else(|| id.to_owned());
+                match index_b.resolve_heading(id, fragment) {
+                    Some(heading) => (name, heading.text.clone(), true),
+                    None => (name, fragment.to_owned(), false),
+                }
+            })
+            .collect();
+
         // Create input area and style it
 
         let mut name_area = tui_textarea::TextArea::default();
@@ -96,19 +151,34 @@ impl DisplayScreen {
         let title_top = Line::from(vec![Span::styled("Enter note name...", styles.title_style)]);
         name_area.set_block(Block::bordered().title(title_top));
 
+        let id = note_id.to_owned();
+
         drop(index_b);
 
+        // Render the content for the preview
+        let preview = ui::Preview::new(&std::fs::read_to_string(&note.path)?, &styles);
+
         Ok(Self {
+            id,
             links: [l1blinks, l1links, l2blinks, l2links],
+            heading_links,
+            preview,
             note,
             index,
             manager,
             builder,
             styles,
+            keys,
             name_area,
+            palette: ui::Palette::new("Commands", Vec::new(), styles),
             selected: [0; 4],
-            foc_table: 0,
+            foc_table: if show_preview { PREVIEW_FOCUS } else { 0 },
             mode: DisplayMode::Display,
+            show_preview,
+            selected_link: None,
+            preview_scroll: Cell::new(0),
+            preview_height: Cell::new(1),
+            reveal_link: Cell::new(false),
         })
     }
 
@@ -125,6 +195,205 @@ impl DisplayScreen {
             self.name_area.insert_str(content);
         }
     }
+
+    /// Returns whether the preview is shown, so following screens can keep showing it.
+    pub fn shows_preview(&self) -> bool {
+        self.show_preview
+    }
+
+    /// Returns the tables and the preview that can be focused, in the order Tab cycles through them.
+    fn focus_order(&self) -> &'static [usize] {
+        if self.show_preview {
+            &[0, 1, PREVIEW_FOCUS]
+        } else {
+            &[0, 1, 2, 3]
+        }
+    }
+
+    /// Moves the focus to the next or previous of the tables and the preview.
+    fn move_focus(&mut self, forward: bool) {
+        let order = self.focus_order();
+        let position = order
+            .iter()
+            .position(|focus| *focus == self.foc_table)
+            .unwrap_or_default();
+        let position = if forward {
+            (position + 1) % order.len()
+        } else {
+            (position + order.len() - 1) % order.len()
+        };
+        self.foc_table = order[position];
+    }
+
+    /// Handles the actions scrolling the preview and following its links, while it is focused.
+    /// Returns `None` for all other actions.
+    fn update_preview(&mut self, action: ui::Action) -> error::Result<Option<ui::Message>> {
+        let scroll = self.preview_scroll.get();
+        let page = self.preview_height.get();
+        let links = self.preview.links().len();
+
+        match action {
+            ui::Action::Down => self.preview_scroll.set(scroll + 1),
+            ui::Action::Up => self.preview_scroll.set(scroll.saturating_sub(1)),
+            ui::Action::PageDown => self.preview_scroll.set(scroll + page),
+            ui::Action::PageUp => self.preview_scroll.set(scroll.saturating_sub(page)),
+            // Select the next or previous link
+            ui::Action::NextLink if links > 0 => {
+                self.selected_link = Some(self.selected_link.map_or(0, |link| (link + 1) % links));
+                self.reveal_link.set(true);
+            }
+            ui::Action::PreviousLink if links > 0 => {
+                self.selected_link = Some(
+                    self.selected_link
+                        .map_or(links - 1, |link| (link + links - 1) % links),
+                );
+                self.reveal_link.set(true);
+            }
+            // Follow the selected link
+            ui::Action::Open => {
+                let Some(target) = self
+                    .selected_link
+                    .and_then(|link| self.preview.links().get(link))
+                else {
+                    return Ok(Some(ui::Message::None));
+                };
+                let index = self.index.borrow();
+                let id = index
+                    .resolve_from(&self.id, target)
+                    .ok_or_else(|| error::RucolaError::NoteNotFound(target.to_owned()))?;
+                return Ok(Some(ui::Message::DisplayStackPush(id.to_owned())));
+            }
+            _ => return Ok(None),
+        }
+
+        Ok(Some(ui::Message::None))
+    }
+
+    /// Runs the given action of the main mode, as bound to a key or chosen from the command palette.
+    fn run_action(&mut self, action: ui::Action) -> error::Result<ui::Message> {
+        // While the preview is focused, it handles scrolling and following links first
+        if self.foc_table == PREVIEW_FOCUS {
+            if let Some(message) = self.update_preview(action)? {
+                return Ok(message);
+            }
+        }
+
+        match action {
+            ui::Action::Quit => {
+                return Ok(ui::Message::Quit);
+            }
+            // Jump to any note
+            ui::Action::QuickSwitcher => return Ok(ui::Message::QuickSwitcherOpen),
+            // Go back to selection
+            ui::Action::Home => {
+                return Ok(ui::Message::DisplayStackClear);
+            }
+            // Return to selection or previous note
+            ui::Action::Back => {
+                return Ok(ui::Message::DisplayStackPop);
+            }
+            // Go up in the current list
+            ui::Action::Up => {
+                if let Some(selected) = self.selected.get_mut(self.foc_table) {
+                    *selected = selected.saturating_sub(1);
+                }
+            }
+            // Go down in the current list
+            ui::Action::Down => {
+                if let Some(selected) = self.selected.get_mut(self.foc_table) {
+                    *selected = selected.saturating_add(1).min(
+                        self.links
+                            .get(self.foc_table)
+                            .map(|list| list.len().saturating_sub(1))
+                            .unwrap_or_default(),
+                    );
+                }
+            }
+            // Change list
+            ui::Action::NextTable => {
+                self.move_focus(true);
+            }
+            // Change list back
+            ui::Action::PreviousTable => {
+                self.move_focus(false);
+            }
+            // Search all actions of this screen
+            ui::Action::Palette => {
+                self.palette = ui::Palette::new("Commands", self.palette_entries(), self.styles);
+                self.mode = DisplayMode::Palette;
+            }
+            // Show or hide the preview, focusing it when shown
+            ui::Action::TogglePreview => {
+                self.show_preview = !self.show_preview;
+                self.foc_table = if self.show_preview { PREVIEW_FOCUS } else { 0 };
+            }
+            // Switch to the selected note
+            ui::Action::Open => {
+                return Ok(self
+                    .links
+                    // get the correct table
+                    .get(self.foc_table)
+                    // unwrap the current index
+                    .and_then(|table| table.get(self.selected[self.foc_table]))
+                    // and extract the id
+                    .map(|(id, _name)| ui::Message::DisplayStackPush(id.to_owned()))
+                    .unwrap_or(ui::Message::None));
+            }
+            // Open selected item in editor
+            ui::Action::Edit => {
+                return Ok(ui::Message::OpenExternalCommand(Box::new(
+                    self.manager.create_edit_command(&self.note.path, None)?,
+                )));
+            }
+            // Open selected item in viewer
+            action @ (ui::Action::View | ui::Action::ViewSecondary) => {
+                self.builder
+                    .create_html(&self.note, &self.index.borrow(), true)?;
+                return Ok(ui::Message::OpenExternalCommand(Box::new(
+                    self.manager
+                        .create_view_command(&self.note, action == ui::Action::View)?,
+                )));
+            }
+            // Rename note
+            ui::Action::Rename => {
+                self.mode = DisplayMode::Rename;
+                self.set_name_area("Enter new name of note...", Some(self.note.name.clone()));
+            }
+            // Move note
+            ui::Action::Move => {
+                self.mode = DisplayMode::Move;
+                self.set_name_area("Enter new location relative to vault...", None);
+            }
+            // Delete note
+            ui::Action::Delete => {
+                self.mode = DisplayMode::Delete;
+            }
+            // Copy note
+            ui::Action::Copy => {
+                self.manager.copy_note_file(self.index.clone(), &self.id)?;
+                self.index.borrow().poll_file_system();
+            }
+
+            _ => {}
+        }
+
+        Ok(ui::Message::None)
+    }
+
+    /// Returns all actions of this screen for the command palette, apart from those moving within the screen.
+    fn palette_entries(&self) -> Vec<ui::PaletteEntry<ui::Action>> {
+        ui::Action::DISPLAY
+            .iter()
+            .filter(|action| !action.is_navigation())
+            .map(|action| {
+                ui::PaletteEntry::new(
+                    action.description(),
+                    self.keys.names(*action).join(", "),
+                    *action,
+                )
+            })
+            .collect()
+    }
 }
 
 impl super::Screen for DisplayScreen {
@@ -138,7 +407,8 @@ impl super::Screen for DisplayScreen {
             Constraint::Length(1),
             Constraint::Length(5),
             Constraint::Fill(1),
-            Constraint::Fill(1),
+            // the preview takes the place of the level 2 links
+            Constraint::Fill(if self.show_preview { 2 } else { 1 }),
         ]);
 
         let [title_area, stats_area, links1_area, links2_area] = vertical.areas(area);
@@ -156,20 +426,31 @@ impl super::Screen for DisplayScreen {
         )])
         .alignment(Alignment::Right);
 
-        let instructions_bot_right = Line::from(vec![
-            Span::styled("V", self.styles.hotkey_style),
-            Span::styled("iew──", self.styles.text_style),
-            Span::styled("E", self.styles.hotkey_style),
-            Span::styled("dit──", self.styles.text_style),
-            Span::styled("R", self.styles.hotkey_style),
-            Span::styled("ename──", self.styles.text_style),
-            Span::styled("M", self.styles.hotkey_style),
-            Span::styled("ove──", self.styles.text_style),
-            Span::styled("C", self.styles.hotkey_style),
-            Span::styled("opy──", self.styles.text_style),
-            Span::styled("D", self.styles.hotkey_style),
-            Span::styled("elete", self.styles.text_style),
-        ])
+        let palette_hint = Line::from(ui::join_hints(
+            [
+                self.keys
+                    .hint(ui::Action::Palette, "Commands", &self.styles),
+                self.keys
+                    .hint(ui::Action::QuickSwitcher, "Jump to note", &self.styles),
+            ],
+            &self.styles,
+        ))
+        .alignment(Alignment::Left);
+
+        let instructions_bot_right = Line::from(ui::join_hints(
+            [
+                (ui::Action::TogglePreview, "Preview"),
+                (ui::Action::View, "View"),
+                (ui::Action::Edit, "Edit"),
+                (ui::Action::Rename, "Rename"),
+                (ui::Action::Move, "Move"),
+                (ui::Action::Copy, "Copy"),
+                (ui::Action::Delete, "Delete"),
+            ]
+            .into_iter()
+            .map(|(action, label)| self.keys.hint(action, label, &self.styles)),
+            &self.styles,
+        ))
         .right_aligned();
 
         let stats = self.note.to_stats_table(&self.styles).block(
@@ -183,19 +464,40 @@ impl super::Screen for DisplayScreen {
 
         // === All the links ===
 
+        let [links1_area, outline_area] =
+            Layout::horizontal([Constraint::Fill(2), Constraint::Fill(1)]).areas(links1_area);
+
         let horizontal = Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]);
 
         let [blinks1, links1] = horizontal.areas(links1_area);
-        let [blinks2, links2] = horizontal.areas(links2_area);
 
         Widget::render(title, title_area, buf);
         Widget::render(version, title_area, buf);
+        Widget::render(palette_hint, title_area, buf);
         Widget::render(stats, stats_area, buf);
 
         self.draw_link_table(0, "Backlinks", blinks1, buf);
         self.draw_link_table(1, "Links", links1, buf);
-        self.draw_link_table(2, "Level 2 Backlinks", blinks2, buf);
-        self.draw_link_table(3, "Level 2 Links", links2, buf);
+
+        if self.show_preview {
+            self.draw_outline(outline_area, buf);
+            self.draw_preview(links2_area, buf);
+        } else {
+            let [links2_area, _] =
+                Layout::horizontal([Constraint::Fill(2), Constraint::Fill(1)]).areas(links2_area);
+            let [blinks2, links2] = horizontal.areas(links2_area);
+
+            // extend the outline over both rows of link tables
+            let outline_area = outline_area.union(Rect {
+                y: links2_area.y,
+                height: links2_area.height,
+                ..outline_area
+            });
+
+            self.draw_link_table(2, "Level 2 Backlinks", blinks2, buf);
+            self.draw_link_table(3, "Level 2 Links", links2, buf);
+            self.draw_outline(outline_area, buf);
+        }
 
         if self.mode == DisplayMode::Rename || self.mode == DisplayMode::Move {
             let popup_areas = Layout::vertical([
@@ -217,6 +519,9 @@ impl super::Screen for DisplayScreen {
 
             Widget::render(&self.name_area, center_area, buf);
         }
+        if self.mode == DisplayMode::Palette {
+            self.palette.draw(area, buf);
+        }
         if self.mode == DisplayMode::Delete {
             let delete_confirmation = Paragraph::new(Text::styled(
                 format!("Delete current note \"{}\"?", self.note.display_name),
@@ -263,92 +568,19 @@ impl super::Screen for DisplayScreen {
 
     fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
         match self.mode {
-            DisplayMode::Display => match key.code {
-                // Quit with Q
-                KeyCode::Char('Q' | 'q') => {
-                    return Ok(ui::Message::Quit);
-                }
-                // Go back to selection with f
-                KeyCode::Char('F' | 'f') => {
-                    return Ok(ui::Message::DisplayStackClear);
-                }
-                // Return to selection or previous note with left or H
-                KeyCode::Left | KeyCode::Char('H' | 'h') => {
-                    return Ok(ui::Message::DisplayStackPop);
-                }
-                // Go up in the current list with k
-                KeyCode::Up | KeyCode::Char('K' | 'k') => {
-                    if let Some(selected) = self.selected.get_mut(self.foc_table) {
-                        *selected = selected.saturating_sub(1);
-                    }
-                }
-                // Go down in the current list with j
-                KeyCode::Down | KeyCode::Char('J' | 'j') => {
-                    if let Some(selected) = self.selected.get_mut(self.foc_table) {
-                        *selected = selected.saturating_add(1).min(
-                            self.links
-                                .get(self.foc_table)
-                                .map(|list| list.len().saturating_sub(1))
-                                .unwrap_or_default(),
-                        );
-                    }
-                }
-                // Change list with Tab
-                KeyCode::Tab => {
-                    self.foc_table = (self.foc_table.wrapping_add(1)) % 4;
-                }
-                // Change list back with Shift+Tab or H
-                KeyCode::BackTab => {
-                    self.foc_table = (self.foc_table.wrapping_sub(1)) % 4;
-                }
-                // If enter, switch to that note
-                KeyCode::Enter | KeyCode::Right | KeyCode::Char('L' | 'l') => {
-                    return Ok(self
-                        .links
-                        // get the correct table
-                        .get(self.foc_table)
-                        // unwrap the current index
-                        .and_then(|table| table.get(self.selected[self.foc_table]))
-                        // and extract the id
-                        .map(|(id, _name)| ui::Message::DisplayStackPush(id.to_owned()))
-                        .unwrap_or(ui::Message::None));
-                }
-                // Open selected item in editor
-                KeyCode::Char('e' | 'E') => {
-                    return Ok(ui::Message::OpenExternalCommand(Box::new(
-                        self.manager.create_edit_command(&self.note.path)?,
-                    )));
-                }
-                // Open selected item in viewer
-                KeyCode::Char('v' | 'V') => {
-                    self.builder.create_html(&self.note, true)?;
-                    return Ok(ui::Message::OpenExternalCommand(Box::new(
-                        self.manager
-                            .create_view_command(&self.note, key.code == KeyCode::Char('v'))?,
-                    )));
-                }
-                // R: Rename note
-                KeyCode::Char('r' | 'R') => {
-                    self.mode = DisplayMode::Rename;
-                    self.set_name_area("Enter new name of note...", Some(self.note.name.clone()));
-                }
-                // M: Move note
-                KeyCode::Char('m' | 'M') => {
-                    self.mode = DisplayMode::Move;
-                    self.set_name_area("Enter new location relative to vault...", None);
-                }
-                // D: Move note
-                KeyCode::Char('d' | 'D') => {
-                    self.mode = DisplayMode::Delete;
+            DisplayMode::Display => {
+                if let Some(action) = self.keys.action(&key, ui::Action::DISPLAY) {
+                    return self.run_action(action);
                 }
-                // C: Copy note
-                KeyCode::Char('c' | 'C') => {
-                    self.manager
-                        .copy_note_file(self.index.clone(), &data::name_to_id(&self.note.name))?;
-                    self.index.borrow().poll_file_system();
+            }
+            // Palette mode: Search and run an action
+            DisplayMode::Palette => match self.palette.update(key) {
+                ui::PaletteChoice::Pending => {}
+                ui::PaletteChoice::Cancelled => self.mode = DisplayMode::Display,
+                ui::PaletteChoice::Chosen(action) => {
+                    self.mode = DisplayMode::Display;
+                    return self.run_action(action);
                 }
-
-                _ => {}
             },
             DisplayMode::Rename => match key.code {
                 KeyCode::Esc => {
@@ -359,7 +591,7 @@ impl super::Screen for DisplayScreen {
                     self.mode = DisplayMode::Display;
                     self.manager.rename_note_file(
                         self.index.clone(),
-                        &data::name_to_id(&self.note.name),
+                        &self.id,
                         super::extract_string_and_clear(&mut self.name_area).ok_or_else(|| {
                             error::RucolaError::Input("New name is empty.".to_string())
                         })?,
@@ -379,7 +611,7 @@ impl super::Screen for DisplayScreen {
                     self.mode = DisplayMode::Display;
                     self.manager.move_note_file(
                         self.index.clone(),
-                        &data::name_to_id(&self.note.name),
+                        &self.id,
                         super::extract_string_and_clear(&mut self.name_area).ok_or_else(|| {
                             error::RucolaError::Input("Move location is empty.".to_string())
                         })?,
@@ -395,7 +627,7 @@ impl super::Screen for DisplayScreen {
                 KeyCode::Enter => {
                     // delete it from index & filesystem
                     self.manager
-                        .delete_note_file(self.index.clone(), &data::name_to_id(&self.note.name))?;
+                        .delete_note_file(self.index.clone(), &self.id)?;
                     self.index.borrow().poll_file_system();
                     return Ok(ui::Message::DisplayStackPop);
                 }
@@ -410,6 +642,130 @@ impl super::Screen for DisplayScreen {
 }
 
 impl DisplayScreen {
+    /// Draws the rendered content of the note, scrolled to the current position or to the selected link.
+    fn draw_preview(&self, area: Rect, buf: &mut Buffer) {
+        let focused = self.foc_table == PREVIEW_FOCUS;
+
+        let (lines, link_line) = self.preview.to_lines(
+            area.width.saturating_sub(2) as usize,
+            self.selected_link.filter(|_| focused),
+        );
+
+        // keep the selected link in view, then stop scrolling at the end of the note
+        let height = area.height.saturating_sub(2) as usize;
+        let mut scroll = self.preview_scroll.get();
+        if let Some(line) = link_line.filter(|_| self.reveal_link.take()) {
+            if line < scroll || line >= scroll + height {
+                scroll = line.saturating_sub(height / 3);
+            }
+        }
+        scroll = scroll.min(lines.len().saturating_sub(height));
+        self.preview_scroll.set(scroll);
+        self.preview_height.set(height.max(1));
+
+        let title = Line::from(vec![Span::styled("Preview", self.styles.title_style)]);
+
+        let count = self.preview.links().len();
+        let count = Line::from(vec![Span::styled(
+            format!("{} Link{}", count, if count == 1 { "" } else { "s" }),
+            self.styles.text_style,
+        )])
+        .right_aligned();
+
+        let instructions_bot_left = Line::from(ui::join_hints(
+            [
+                vec![
+                    Span::styled(self.keys.name(ui::Action::Down), self.styles.hotkey_style),
+                    Span::styled("/", self.styles.text_style),
+                    Span::styled(self.keys.name(ui::Action::Up), self.styles.hotkey_style),
+                    Span::styled(": Scroll", self.styles.text_style),
+                ],
+                self.keys
+                    .hint(ui::Action::NextLink, "Next Link", &self.styles),
+                self.keys.hint(ui::Action::Open, "Follow", &self.styles),
+                self.keys.hint_all(ui::Action::Back, "Back", &self.styles),
+                self.keys.hint(ui::Action::Home, "Home", &self.styles),
+            ],
+            &self.styles,
+        ))
+        .left_aligned();
+
+        let instructions_bot_right = Line::from(self.keys.hint(
+            ui::Action::NextTable,
+            "Next Table",
+            &self.styles,
+        ))
+        .right_aligned();
+
+        Widget::render(
+            Paragraph::new(lines).scroll((scroll as u16, 0)).block(
+                Block::bordered()
+                    .border_style(if focused {
+                        self.styles.title_style
+                    } else {
+                        Style::default()
+                    })
+                    .title_top(title)
+                    .title_top(count)
+                    .title_bottom(instructions_bot_left)
+                    .title_bottom(instructions_bot_right),
+            ),
+            area,
+            buf,
+        );
+    }
+
+    /// Draws the heading outline of the note, followed by the links of this note to headings of other notes.
+    fn draw_outline(&self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(vec![Span::styled("Outline", self.styles.title_style)]);
+
+        let count = self.note.headings.len();
+        let count = Line::from(vec![Span::styled(
+            format!("{} Heading{}", count, if count == 1 { "" } else { "s" }),
+            self.styles.text_style,
+        )])
+        .right_aligned();
+
+        let mut lines = self
+            .note
+            .headings
+            .iter()
+            .map(|heading| {
+                Line::from(vec![
+                    Span::raw("  ".repeat(heading.level.saturating_sub(1) as usize)),
+                    Span::styled(heading.text.as_str(), self.styles.text_style),
+                ])
+            })
+            .collect_vec();
+
+        if !self.heading_links.is_empty() {
+            lines.push(Line::default());
+            lines.push(Line::from(Span::styled(
+                "Linked headings",
+                self.styles.subtitle_style,
+            )));
+            lines.extend(self.heading_links.iter().map(|(name, heading, exists)| {
+                Line::from(vec![
+                    Span::styled(format!("{name} › "), self.styles.text_style),
+                    Span::styled(
+                        heading.as_str(),
+                        if *exists {
+                            self.styles.text_style
+                        } else {
+                            self.styles.subtitle_style.crossed_out()
+                        },
+                    ),
+                ])
+            }));
+        }
+
+        Widget::render(
+            Paragraph::new(lines).block(Block::bordered().title_top(title).title_top(count)),
+            area,
+            buf,
+        );
+    }
+
     fn draw_link_table(&self, index: usize, title: &str, area: Rect, buf: &mut Buffer) {
         // Title
         let title = Line::from(vec![Span::styled(title, self.styles.title_style)]).left_aligned();
@@ -483,37 +839,28 @@ impl DisplayScreen {
         // in some places, add instructions
         let block = match index {
             2 => block.title_bottom(
-                Line::from(vec![
-                    Span::styled("J", self.styles.hotkey_style),
-                    Span::styled("/", self.styles.text_style),
-                    Span::styled("↓", self.styles.hotkey_style),
-                    Span::styled(": Down──", self.styles.text_style),
-                    Span::styled("K", self.styles.hotkey_style),
-                    Span::styled("/", self.styles.text_style),
-                    Span::styled("↑", self.styles.hotkey_style),
-                    Span::styled(": Up──", self.styles.text_style),
-                    Span::styled("L", self.styles.hotkey_style),
-                    Span::styled("/", self.styles.text_style),
-                    Span::styled("→", self.styles.hotkey_style),
-                    Span::styled("/", self.styles.text_style),
-                    Span::styled("↵", self.styles.hotkey_style),
-                    Span::styled(": Open──", self.styles.text_style),
-                    Span::styled("H", self.styles.hotkey_style),
-                    Span::styled("/", self.styles.text_style),
-                    Span::styled("←", self.styles.hotkey_style),
-                    Span::styled(": Back──", self.styles.text_style),
-                    Span::styled("F", self.styles.hotkey_style),
-                    Span::styled(": Home", self.styles.text_style),
-                ])
+                Line::from(ui::join_hints(
+                    [
+                        self.keys.hint_all(ui::Action::Down, "Down", &self.styles),
+                        self.keys.hint_all(ui::Action::Up, "Up", &self.styles),
+                        self.keys.hint_all(ui::Action::Open, "Open", &self.styles),
+                        self.keys.hint_all(ui::Action::Back, "Back", &self.styles),
+                        self.keys.hint(ui::Action::Home, "Home", &self.styles),
+                    ],
+                    &self.styles,
+                ))
                 .left_aligned(),
             ),
             3 => block.title_bottom(
-                Line::from(vec![
-                    Span::styled("Tab", self.styles.hotkey_style),
-                    Span::styled(": Next Table──", self.styles.text_style),
-                    Span::styled("Shift+Tab", self.styles.hotkey_style),
-                    Span::styled(": Previous Table", self.styles.text_style),
-                ])
+                Line::from(ui::join_hints(
+                    [
+                        self.keys
+                            .hint(ui::Action::NextTable, "Next Table", &self.styles),
+                        self.keys
+                            .hint(ui::Action::PreviousTable, "Previous Table", &self.styles),
+                    ],
+                    &self.styles,
+                ))
                 .right_aligned(),
             ),
             _ => block,
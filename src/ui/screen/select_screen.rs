@@ -1,9 +1,10 @@
-use crate::config::DiaryConfig;
+use crate::config::{DiaryConfig, SavedFilter};
 use crate::{data, error, io, ui};
 use chrono::Local;
 use itertools::Itertools;
-use ratatui::crossterm::event::KeyCode;
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{prelude::*, widgets::*};
+use std::collections::HashSet;
 use tui_textarea::TextArea;
 
 /// Describes the current mode of the UI.
@@ -16,20 +17,76 @@ enum SelectMode {
     SubmenuFile,
     /// Sorting submenu
     SubmenuSorting,
+    /// Sorting submenu after `+`, adding a column to sort by after the current ones.
+    SubmenuSortingThen,
     /// Typing into the filter box.
     Filter,
+    /// Choosing one of the completions of the word before the cursor in the filter box.
+    Completion(usize),
     /// Show the help screen for the filter box.
     FilterHelp,
     /// Show a list of all tags in the vault.
     TagList(usize),
+    /// Show a list of all filters saved in the config.
+    SavedFilters(usize),
+    /// Show the lines matching the full-text search of the filter instead of the notes.
+    Results(usize),
     /// Typing into the create box.
     Create,
     /// Typing into the create box to rename a note.
     Rename,
     /// Typing into the create box to move a note.
     Move,
+    /// Typing into the create box to add a tag to a note.
+    AddTag,
+    /// Typing into the create box to remove a tag from a note.
+    RemoveTag,
     /// Confirmation for deletion
     Delete,
+    /// Confirmation for the pending file operation on all marked notes.
+    ConfirmBulk,
+    /// Searching the command palette.
+    Palette,
+}
+
+/// The actions available while the full-text results are shown. `Results` and `Esc` go back to the note list, `Enter` edits like `Edit`.
+const RESULTS_ACTIONS: &[ui::Action] = &[
+    ui::Action::Quit,
+    ui::Action::Results,
+    ui::Action::Filter,
+    ui::Action::Down,
+    ui::Action::Up,
+    ui::Action::PageDown,
+    ui::Action::PageUp,
+    ui::Action::Top,
+    ui::Action::Edit,
+    ui::Action::Open,
+];
+
+/// A command that can be chosen from the command palette.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Command {
+    /// Run the given action, just like its key does.
+    Action(ui::Action),
+    /// Press the given key within the given submenu.
+    Submenu(SelectMode, char),
+    /// Apply the saved filter at the given position.
+    SavedFilter(usize),
+}
+
+/// A file operation that can be applied to several notes at once.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum FileOperation {
+    /// Delete the notes.
+    Delete,
+    /// Copy the notes next to themselves.
+    Copy,
+    /// Move the notes to the given folder relative to the vault.
+    Move(String),
+    /// Add the given tag to the notes.
+    AddTag(String),
+    /// Remove the given tag from the notes.
+    RemoveTag(String),
 }
 
 /// Describes when to show a which stats area.
@@ -63,16 +120,34 @@ pub struct SelectScreen {
     builder: io::HtmlBuilder,
     /// The used styles.
     styles: ui::UiStyles,
+    /// The keys bound to the actions of this screen.
+    keys: ui::KeyBindings,
     /// Configuration for the daily diary feature.
     diary_config: DiaryConfig,
 
     // === UI ===
     /// The text area to type in filters.
     filter_area: TextArea<'static>,
+    /// The error message of the current filter, if it could not be parsed. Shown below the filter area.
+    filter_error: Option<String>,
+    /// The filters applied before, which can be recalled in the filter area.
+    filter_history: io::FilterHistory,
+    /// The completions of the word before the cursor in the filter area, shown in a popup below it.
+    completions: Vec<String>,
+    /// Whether the current filter searches the full text, so its matching lines can be shown.
+    has_full_text: bool,
+    /// The lines of the filtered notes matching the full-text search of the filter, as pairs of note id and line.
+    text_results: Vec<(String, data::TextMatch)>,
     /// The text area used to create new notes.
     name_area: TextArea<'static>,
     /// Current input mode
     mode: SelectMode,
+    /// The ids of all notes marked to apply file operations to at once.
+    marked: HashSet<String>,
+    /// The file operation waiting for confirmation to be applied to all marked notes.
+    pending_operation: Option<FileOperation>,
+    /// The command palette, filled with the commands available when it was opened.
+    palette: ui::Palette<Command>,
     /// Current state of the list
     ///
     /// This is saved as a simple usize from which the ListState to use with ratatui is constructed in immediate mode.
@@ -84,18 +159,28 @@ pub struct SelectScreen {
     any_conditions: bool,
     /// UI mode whether to match tags by prefix or exactly.
     tag_match: data::TagMatch,
-    /// Default sorting of the note list.
-    default_sorting: data::NoteColumn,
-    /// Default sorting direction of the note list.
-    default_sorting_asc: bool,
-    /// Default sorting of the note list.
-    sorting: data::NoteColumn,
-    /// Default sorting direction of the note list.
-    sorting_asc: bool,
+    /// Default columns and directions to sort the note list by.
+    default_sorting: Vec<data::SortKey>,
+    /// Current columns and directions the note list is sorted by, later ones only deciding between notes equal in all earlier ones.
+    sorting: Vec<data::SortKey>,
     /// How to display the two stats blocks.
     stats_show: StatsShow,
     /// What columns to display
     column_config: Vec<(String, data::NoteColumn)>,
+
+    // === Saved filters ===
+    /// The filters saved in the config, sorted by name.
+    saved_filters: Vec<(String, SavedFilter)>,
+    /// How many notes match each of the saved filters, only counted while they are shown.
+    saved_filter_counts: Vec<usize>,
+    /// Whether to show the saved filters next to the note list.
+    show_saved_filters: bool,
+
+    // === Preview ===
+    /// Whether to show the rendered content of the selected note next to the note list.
+    show_preview: bool,
+    /// The rendered content of the note with the given id, kept while it stays selected so it is not read again on every frame.
+    preview: std::cell::RefCell<Option<(String, ui::Preview)>>,
 }
 
 impl SelectScreen {
@@ -105,6 +190,7 @@ impl SelectScreen {
         manager: io::FileManager,
         git_manager: Option<io::GitManager>,
         builder: io::HtmlBuilder,
+        filter_history: io::FilterHistory,
         styles: ui::UiStyles,
         config: &crate::Config,
     ) -> Self {
@@ -113,26 +199,40 @@ impl SelectScreen {
             global_stats: data::EnvironmentStats::new_with_filter(&index, data::Filter::default()),
             index: index.clone(),
             styles,
+            // invalid key bindings are reported when starting the app
+            keys: ui::KeyBindings::new(config).unwrap_or_default(),
             builder,
             manager,
             git_manager,
             filter_area: TextArea::default(),
+            filter_error: None,
+            filter_history,
+            completions: Vec::new(),
+            has_full_text: false,
+            text_results: Vec::new(),
             name_area: TextArea::default(),
             mode: SelectMode::Select,
+            marked: HashSet::new(),
+            pending_operation: None,
+            palette: ui::Palette::new("Commands", Vec::new(), styles),
             any_conditions: false,
             tag_match: config.tag_match,
-            default_sorting: config.default_sorting,
-            default_sorting_asc: config.default_sorting_asc,
-            sorting: config.default_sorting,
-            sorting_asc: config.default_sorting_asc,
+            default_sorting: config.default_sort_keys(),
+            sorting: config.default_sort_keys(),
             selected: 0,
             stats_show: config.stats_show,
             column_config: config.select_columns.clone(),
             diary_config: config.diary.clone(),
+            saved_filters: config.saved_filters.clone().into_iter().collect(),
+            saved_filter_counts: Vec::new(),
+            show_saved_filters: config.show_saved_filters,
+            show_preview: config.select_preview,
+            preview: std::cell::RefCell::new(None),
         };
 
-        res.local_stats
-            .sort(index, config.default_sorting, config.default_sorting_asc);
+        res.count_saved_filters();
+
+        res.local_stats.sort(index, &config.default_sort_keys());
 
         res.style_text_area();
 
@@ -142,35 +242,50 @@ impl SelectScreen {
     /// Styling of TextArea extracted from constructor to keep it clean.
     fn style_text_area(&mut self) {
         // === Filter ===
+        self.style_filter_area();
 
-        // The actual title
-        let title_top = Line::from(vec![
-            Span::styled("F", self.styles.hotkey_style),
-            Span::styled("ilter", self.styles.title_style),
-        ])
-        .left_aligned();
+        // === Create ===
+        self.style_name_area();
+    }
+
+    /// Styling of the filter area, extracted to be repeated when the any/all mode or the parse error of the filter changes.
+    fn style_filter_area(&mut self) {
+        // The actual title, with the label in the title style
+        let mut title_top = self.keys.hint(ui::Action::Filter, "Filter", &self.styles);
+        match title_top.last_mut() {
+            Some(label) => label.style = self.styles.title_style,
+            None => title_top.push(Span::styled("Filter", self.styles.title_style)),
+        }
+        let title_top = Line::from(title_top).left_aligned();
 
         // The hotkey instructions at the bottom.
-        let instructions = Line::from(vec![
-            Span::styled("T", self.styles.hotkey_style),
-            Span::styled("ag List", self.styles.text_style),
-            Span::styled("──", self.styles.text_style),
-            Span::styled("C", self.styles.hotkey_style),
-            Span::styled("lear filter", self.styles.text_style),
-        ])
+        let instructions = Line::from(ui::join_hints(
+            [
+                (ui::Action::SavedFilters, "Saved Filters"),
+                (ui::Action::TagList, "Tag List"),
+                (ui::Action::ClearFilter, "Clear filter"),
+            ]
+            .into_iter()
+            .map(|(action, label)| self.keys.hint(action, label, &self.styles)),
+            &self.styles,
+        ))
         .right_aligned();
 
-        let instructions_bot = Line::from(vec![
-            Span::styled("A", self.styles.hotkey_style),
-            Span::styled(
-                if self.any_conditions { "ny" } else { "ll" },
-                self.styles.text_style,
-            ),
-            Span::styled(" Conditions", self.styles.text_style),
-            Span::styled("──", self.styles.text_style),
-            Span::styled("H", self.styles.hotkey_style),
-            Span::styled("elp", self.styles.text_style),
-        ])
+        let instructions_bot = Line::from(ui::join_hints(
+            [
+                self.keys.hint(
+                    ui::Action::ToggleAnyAll,
+                    if self.any_conditions {
+                        "Any Conditions"
+                    } else {
+                        "All Conditions"
+                    },
+                    &self.styles,
+                ),
+                self.keys.hint(ui::Action::FilterHelp, "Help", &self.styles),
+            ],
+            &self.styles,
+        ))
         .right_aligned();
 
         // Apply default self.styles to the filter area
@@ -179,14 +294,23 @@ impl SelectScreen {
         self.filter_area
             .set_cursor_line_style(self.styles.input_style);
 
-        self.filter_area.set_block(
-            Block::bordered()
-                .title_top(title_top)
-                .title_top(instructions)
-                .title_bottom(instructions_bot),
-        );
+        let mut block = Block::bordered()
+            .title_top(title_top)
+            .title_top(instructions)
+            .title_bottom(instructions_bot);
 
-        // === Create ===
+        // Show parse errors of the filter inline
+        if let Some(error) = &self.filter_error {
+            block = block.title_bottom(
+                Line::from(Span::styled(error.clone(), self.styles.hotkey_style)).left_aligned(),
+            );
+        }
+
+        self.filter_area.set_block(block);
+    }
+
+    /// Styling of the create area.
+    fn style_name_area(&mut self) {
         // The title
         let title_top = Line::from(vec![Span::styled(
             "Enter note name...",
@@ -232,15 +356,28 @@ impl SelectScreen {
         }
     }
 
-    /// Sorts the display according to the current mode
-    fn set_mode_sort(&mut self, sorting: data::NoteColumn, sorting_asc: bool) {
+    /// Sorts the display by the given columns and directions.
+    fn set_sorting(&mut self, sorting: Vec<data::SortKey>) {
         self.sorting = sorting;
-        self.sorting_asc = sorting_asc;
-        self.local_stats
-            .sort(self.index.clone(), self.sorting, self.sorting_asc);
+        self.local_stats.sort(self.index.clone(), &self.sorting);
         self.selected = 0;
     }
 
+    /// Sorts the display by the given column, either only by it or after all columns it is currently sorted by.
+    /// In the latter case, the column is moved to the end if the display was already sorted by it.
+    fn sort_by_column(&mut self, column: data::NoteColumn, ascending: bool, then: bool) {
+        let key = data::SortKey::new(column, ascending);
+        let sorting = if then {
+            let mut sorting = self.sorting.clone();
+            sorting.retain(|other| other.column != column);
+            sorting.push(key);
+            sorting
+        } else {
+            vec![key]
+        };
+        self.set_sorting(sorting);
+    }
+
     /// Creates a filter from the current content of the filter area.
     fn filter_from_input(&self) -> data::Filter {
         self.filter_area
@@ -253,168 +390,780 @@ impl SelectScreen {
     /// Reloads the displayed statistics, showing stats for only those elements of the index matching the specified filter.
     /// Every filtering neccessarily triggers a non-stable resort.
     fn filter(&mut self, filter: data::Filter) {
+        // show parse errors in the filter area
+        if self.filter_error != filter.error {
+            self.filter_error = filter.error.clone();
+            self.style_filter_area();
+        }
+        // keep the previous results while the filter cannot be parsed
+        if self.filter_error.is_some() {
+            return;
+        }
+        self.has_full_text = !filter.full_text_queries().is_empty();
         // actual filtering
         self.local_stats = data::EnvironmentStats::new_with_filter(&self.index, filter);
         // reset sorting
-        self.set_mode_sort(data::NoteColumn::Score, false);
+        self.set_sorting(vec![data::SortKey::new(data::NoteColumn::Score, false)]);
+    }
+
+    /// Replaces the content of the filter area, places the cursor at the given character and applies the new filter.
+    fn set_filter_text(&mut self, filter: &str, cursor: usize) {
+        self.filter_area
+            .set_lines(vec![filter.to_owned()], (0, cursor));
+        self.filter(self.filter_from_input());
+    }
+
+    /// Returns the word before the cursor in the filter area, which is completed by Tab.
+    fn filter_word(&self) -> String {
+        let (_row, column) = self.filter_area.cursor();
+        let line = self
+            .filter_area
+            .lines()
+            .first()
+            .cloned()
+            .unwrap_or_default();
+        let before = line.chars().take(column).collect::<String>();
+        before
+            .rsplit(|c: char| c.is_whitespace() || c == '(')
+            .next()
+            .unwrap_or_default()
+            .to_owned()
+    }
+
+    /// Replaces the word before the cursor in the filter area with the completion of the given index.
+    fn complete_filter_word(&mut self, selected: usize) {
+        let Some(completion) = self.completions.get(selected).cloned() else {
+            return;
+        };
+        let (_row, column) = self.filter_area.cursor();
+        let start = column - self.filter_word().chars().count();
+        let line = self
+            .filter_area
+            .lines()
+            .first()
+            .cloned()
+            .unwrap_or_default();
+
+        let filter = line
+            .chars()
+            .take(start)
+            .chain(completion.chars())
+            .chain(line.chars().skip(column))
+            .collect::<String>();
+        self.set_filter_text(&filter, start + completion.chars().count());
+    }
+
+    /// Collects the lines of all filtered notes that match the full-text search of the filter, in the order of the note list.
+    fn collect_text_results(&mut self) {
+        let filter = self.filter_from_input();
+        let queries = filter.full_text_queries();
+        let index = self.index.borrow();
+
+        self.text_results = (0..self.local_stats.len())
+            .filter_map(|position| self.local_stats.get_selected(position))
+            .filter_map(|env_stats| {
+                let note = index.get(&env_stats.id)?;
+                Some((
+                    env_stats.id.clone(),
+                    std::fs::read_to_string(&note.path).ok()?,
+                ))
+            })
+            .flat_map(|(id, content)| {
+                data::TextMatch::find_all(&queries, &content)
+                    .into_iter()
+                    .map(move |text_match| (id.clone(), text_match))
+            })
+            .collect();
+    }
+
+    /// Counts the notes matching each of the saved filters, if they are shown in the sidebar or the saved filter picker.
+    fn count_saved_filters(&mut self) {
+        if !self.show_saved_filters && !matches!(self.mode, SelectMode::SavedFilters(_)) {
+            self.saved_filter_counts.clear();
+            return;
+        }
+        self.saved_filter_counts = self
+            .saved_filters
+            .iter()
+            .map(|(_name, saved)| {
+                data::EnvironmentStats::new_with_filter(
+                    &self.index,
+                    data::Filter::new(&saved.filter, self.any_conditions, self.tag_match),
+                )
+                .len()
+            })
+            .collect();
+    }
+
+    /// Replaces the filter with the saved filter at the given position and sorts its results as configured.
+    fn apply_saved_filter(&mut self, selected: usize) {
+        let Some((_name, saved)) = self.saved_filters.get(selected).cloned() else {
+            return;
+        };
+
+        let _ = super::extract_string_and_clear(&mut self.filter_area);
+        self.filter_area.insert_str(&saved.filter);
+        self.filter(self.filter_from_input());
+
+        if let Some(sorting) = saved.sorting {
+            self.set_sorting(vec![data::SortKey::new(sorting, saved.sorting_asc)]);
+        }
+    }
+
+    /// Returns the ids of all marked notes, those in the note list first and in its order.
+    fn marked_ids(&self) -> Vec<String> {
+        let listed = (0..self.local_stats.len())
+            .filter_map(|position| self.local_stats.get_selected(position))
+            .map(|env_stats| env_stats.id.clone())
+            .filter(|id| self.marked.contains(id))
+            .collect_vec();
+        let hidden = self
+            .marked
+            .iter()
+            .filter(|id| !listed.contains(id))
+            .sorted()
+            .cloned()
+            .collect_vec();
+        listed.into_iter().chain(hidden).collect()
+    }
+
+    /// Applies the given file operation to the note of the given id.
+    /// The index is not refreshed, so several operations can be applied in a row.
+    fn apply_file_operation(&self, operation: &FileOperation, id: &str) -> error::Result<()> {
+        match operation {
+            FileOperation::Delete => self.manager.delete_note_file(self.index.clone(), id),
+            FileOperation::Copy => self.manager.copy_note_file(self.index.clone(), id),
+            FileOperation::Move(path) => {
+                self.manager
+                    .move_note_file(self.index.clone(), id, path.clone())
+            }
+            FileOperation::AddTag(tag) => self.manager.add_tag(self.index.clone(), id, tag),
+            FileOperation::RemoveTag(tag) => self.manager.remove_tag(self.index.clone(), id, tag),
+        }
+    }
+
+    /// Applies the given file operation to all marked notes, or asks for confirmation first if there are any.
+    /// Without marked notes, the operation is applied to the selected note right away.
+    fn request_file_operation(&mut self, operation: FileOperation) -> error::Result<()> {
+        if !self.marked.is_empty() {
+            self.pending_operation = Some(operation);
+            self.mode = SelectMode::ConfirmBulk;
+        } else if let Some(env_stats) = self.local_stats.get_selected(self.selected) {
+            self.apply_file_operation(&operation, &env_stats.id)?;
+            // if successful, refresh the ui
+            self.index.borrow().poll_file_system();
+            self.refresh_env_stats();
+        }
+        Ok(())
+    }
+
+    /// Applies the given file operation to all marked notes and unmarks them.
+    /// Notes the operation fails for stay marked and are reported together in a single error.
+    fn apply_bulk_operation(&mut self, operation: &FileOperation) -> error::Result<()> {
+        let ids = self.marked_ids();
+
+        let mut failed = HashSet::new();
+        let mut messages = Vec::new();
+        for id in &ids {
+            if let Err(e) = self.apply_file_operation(operation, id) {
+                let name = self
+                    .index
+                    .borrow()
+                    .get(id)
+                    .map(|note| note.display_name.clone())
+                    .unwrap_or_else(|| id.clone());
+                messages.push(format!("{name}: {e}"));
+                failed.insert(id.clone());
+            }
+        }
+
+        self.marked = failed;
+        self.index.borrow().poll_file_system();
+        self.refresh_env_stats();
+
+        if messages.is_empty() {
+            Ok(())
+        } else {
+            Err(error::RucolaError::BulkError(
+                messages.len(),
+                ids.len(),
+                messages.join("; "),
+            ))
+        }
+    }
+
+    /// Describes the given operation on all marked notes, to be confirmed by the user.
+    fn describe_bulk_operation(&self, operation: &FileOperation) -> String {
+        let notes = format!(
+            "{} marked note{}",
+            self.marked.len(),
+            if self.marked.len() == 1 { "" } else { "s" }
+        );
+        let tag = |tag: &str| format!("#{}", tag.trim().trim_start_matches('#'));
+        match operation {
+            FileOperation::Delete => format!("Delete {notes}?"),
+            FileOperation::Copy => format!("Copy {notes}?"),
+            FileOperation::Move(path) => format!("Move {notes} to \"{path}\"?"),
+            FileOperation::AddTag(name) => format!("Add tag {} to {notes}?", tag(name)),
+            FileOperation::RemoveTag(name) => format!("Remove tag {} from {notes}?", tag(name)),
+        }
     }
 
     /// Re-creates the global and local stats from the index.
     /// To be performed after file management operations.
     pub fn refresh_env_stats(&mut self) {
+        // Notes might have changed, so render the preview again
+        self.preview.take();
+
+        // Forget marks of notes that no longer exist
+        {
+            let index = self.index.borrow();
+            self.marked.retain(|id| index.get(id).is_some());
+        }
+
         // Refresh global stats
         self.global_stats =
             data::EnvironmentStats::new_with_filter(&self.index, data::Filter::default());
-        // Refresh local stats
-        self.local_stats =
-            data::EnvironmentStats::new_with_filter(&self.index, self.filter_from_input());
+        // Refresh local stats, unless the filter cannot be parsed
+        let filter = self.filter_from_input();
+        if filter.error.is_none() {
+            self.local_stats = data::EnvironmentStats::new_with_filter(&self.index, filter);
+        }
 
         // Refresh sorting
-        self.local_stats
-            .sort(self.index.clone(), self.sorting, self.sorting_asc);
+        self.local_stats.sort(self.index.clone(), &self.sorting);
+
+        // Refresh the matches of the saved filters
+        self.count_saved_filters();
+
+        // Refresh the full-text results, if they are shown
+        if let SelectMode::Results(selected) = self.mode {
+            self.collect_text_results();
+            self.mode =
+                SelectMode::Results(selected.min(self.text_results.len().saturating_sub(1)));
+        }
     }
 }
 
-impl super::Screen for SelectScreen {
-    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
-        let key: ratatui::crossterm::event::KeyEvent = key;
-        // Check for mode
-        match self.mode {
-            // Main mode: Switch to modes, general command
-            SelectMode::Select => match key.code {
-                // Q: Quit application
-                KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
-                // M: Got to file management submenu
-                KeyCode::Char('m' | 'M') => {
-                    self.mode = SelectMode::SubmenuFile;
-                }
-                // S: Got to sorting submenu
-                KeyCode::Char('s' | 'S') => {
-                    self.mode = SelectMode::SubmenuSorting;
-                }
-                // F, /: Go to filter mode
-                KeyCode::Char('f' | 'F' | '/') => {
-                    self.mode = SelectMode::Filter;
-                }
-                // ?, h, H: Go to filter help mode
-                KeyCode::Char('?' | 'h' | 'H') => {
-                    self.mode = SelectMode::FilterHelp;
-                }
-                // C: Clear filter
-                KeyCode::Char('c' | 'C') => {
-                    let _ = super::extract_string_and_clear(&mut self.filter_area);
-                    self.filter(data::Filter::default());
-                    // reset filter to default
-                    self.set_mode_sort(self.default_sorting, self.default_sorting_asc);
-                }
-                // A: Change all/any words requirement
-                KeyCode::Char('a' | 'A') => {
-                    self.any_conditions = !self.any_conditions;
-                    self.filter(self.filter_from_input());
-                    self.style_text_area();
+impl SelectScreen {
+    /// Runs the given action of the main mode, as bound to a key or chosen from the command palette.
+    fn run_action(&mut self, action: ui::Action) -> error::Result<ui::Message> {
+        match action {
+            // Quit application
+            ui::Action::Quit => return Ok(ui::Message::Quit),
+            // Jump to any note
+            ui::Action::QuickSwitcher => return Ok(ui::Message::QuickSwitcherOpen),
+            // Got to file management submenu
+            ui::Action::FileMenu => {
+                self.mode = SelectMode::SubmenuFile;
+            }
+            // Got to sorting submenu
+            ui::Action::SortMenu => {
+                self.mode = SelectMode::SubmenuSorting;
+            }
+            // Search all commands of this screen
+            ui::Action::Palette => {
+                self.palette = ui::Palette::new("Commands", self.palette_entries(), self.styles);
+                self.mode = SelectMode::Palette;
+            }
+            // Go to filter mode
+            ui::Action::Filter => {
+                self.mode = SelectMode::Filter;
+            }
+            // Go to filter help mode
+            ui::Action::FilterHelp => {
+                self.mode = SelectMode::FilterHelp;
+            }
+            // Clear filter
+            ui::Action::ClearFilter => {
+                let _ = super::extract_string_and_clear(&mut self.filter_area);
+                self.filter(data::Filter::default());
+                // reset filter to default
+                self.set_sorting(self.default_sorting.clone());
+            }
+            // Change all/any words requirement
+            ui::Action::ToggleAnyAll => {
+                self.any_conditions = !self.any_conditions;
+                self.filter(self.filter_from_input());
+                self.count_saved_filters();
+                self.style_text_area();
+            }
+            // Show tags list
+            ui::Action::TagList => self.mode = SelectMode::TagList(0),
+            // Show saved filters
+            ui::Action::SavedFilters => {
+                self.mode = SelectMode::SavedFilters(0);
+                self.count_saved_filters();
+            }
+            // Show the lines matching the full-text search
+            ui::Action::Results if self.has_full_text => {
+                self.collect_text_results();
+                self.mode = SelectMode::Results(0);
+            }
+            // Show the tasks of all notes
+            ui::Action::Tasks => return Ok(ui::Message::TaskScreenOpen),
+            // Show or hide the preview of the selected note
+            ui::Action::TogglePreview => self.show_preview = !self.show_preview,
+            // Open selected item in editor
+            ui::Action::Edit => {
+                self.mode = SelectMode::Select;
+                if let Some(res) = self
+                    // get the selected item in the list for the id
+                    .local_stats
+                    .get_selected(self.selected)
+                    // use this id in the index to get the note
+                    .and_then(|env_stats| {
+                        // use the id to get the path
+                        self.index
+                            .borrow()
+                            .get(&env_stats.id)
+                            .map(|note| note.path.clone())
+                    })
+                {
+                    // use the config to create a valid opening command
+                    return Ok(ui::Message::OpenExternalCommand(Box::new(
+                        self.manager.create_edit_command(&res, None)?,
+                    )));
                 }
-                // T: Show tags list
-                KeyCode::Char('t' | 'T') => self.mode = SelectMode::TagList(0),
-                // Open selected item in editor
-                KeyCode::Char('e' | 'E') => {
-                    self.mode = SelectMode::Select;
-                    if let Some(res) = self
-                        // get the selected item in the list for the id
-                        .local_stats
-                        .get_selected(self.selected)
-                        // use this id in the index to get the note
-                        .and_then(|env_stats| {
-                            // use the id to get the path
-                            self.index
-                                .borrow()
-                                .get(&env_stats.id)
-                                .map(|note| note.path.clone())
-                        })
-                    {
-                        // use the config to create a valid opening command
+            }
+            // Open view mode
+            action @ (ui::Action::View | ui::Action::ViewSecondary) => {
+                self.mode = SelectMode::Select;
+                if let Some(env_stats) = self.local_stats.get_selected(self.selected) {
+                    if let Some(note) = self.index.borrow().get(&env_stats.id) {
+                        self.builder.create_html(note, &self.index.borrow(), true)?;
                         return Ok(ui::Message::OpenExternalCommand(Box::new(
-                            self.manager.create_edit_command(&res)?,
+                            self.manager
+                                .create_view_command(note, action == ui::Action::View)?,
                         )));
                     }
                 }
-                // Open view mode
-                KeyCode::Char('v' | 'V') => {
-                    self.mode = SelectMode::Select;
-                    if let Some(env_stats) = self.local_stats.get_selected(self.selected) {
-                        if let Some(note) = self.index.borrow().get(&env_stats.id) {
-                            self.builder.create_html(note, true)?;
-                            return Ok(ui::Message::OpenExternalCommand(Box::new(
-                                self.manager
-                                    .create_view_command(note, key.code == KeyCode::Char('v'))?,
-                            )));
-                        }
+            }
+            // Selection
+            // Down
+            ui::Action::Down => {
+                self.selected = self
+                    .selected
+                    .saturating_add(1)
+                    .min(self.local_stats.len().saturating_sub(1));
+            }
+            // Up
+            ui::Action::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            // PageDown
+            ui::Action::PageDown => {
+                self.selected = self
+                    .selected
+                    .saturating_add(10)
+                    .min(self.local_stats.len().saturating_sub(1));
+            }
+            // PageUp
+            ui::Action::PageUp => {
+                self.selected = self.selected.saturating_sub(10);
+            }
+
+            // To the start
+            ui::Action::Top => {
+                self.selected = 0;
+            }
+            // Mark or unmark the selected note, then go down
+            ui::Action::Mark => {
+                if let Some(env_stats) = self.local_stats.get_selected(self.selected) {
+                    if !self.marked.remove(&env_stats.id) {
+                        self.marked.insert(env_stats.id.clone());
                     }
-                }
-                // Selection
-                // Down
-                KeyCode::Char('j' | 'J') | KeyCode::Down => {
                     self.selected = self
                         .selected
                         .saturating_add(1)
                         .min(self.local_stats.len().saturating_sub(1));
                 }
-                // Up
-                KeyCode::Char('k' | 'K') | KeyCode::Up => {
-                    self.selected = self.selected.saturating_sub(1);
+            }
+            // Mark all notes matching the filter, or unmark them if they all are
+            ui::Action::MarkAll => {
+                let listed = (0..self.local_stats.len())
+                    .filter_map(|position| self.local_stats.get_selected(position))
+                    .map(|env_stats| env_stats.id.clone())
+                    .collect_vec();
+                if listed.iter().all(|id| self.marked.contains(id)) {
+                    for id in &listed {
+                        self.marked.remove(id);
+                    }
+                } else {
+                    self.marked.extend(listed);
                 }
-                // PageDown
-                KeyCode::PageDown => {
-                    self.selected = self
-                        .selected
-                        .saturating_add(10)
-                        .min(self.local_stats.len().saturating_sub(1));
+            }
+            // Unmark all notes
+            ui::Action::UnmarkAll => self.marked.clear(),
+            // Open selected item in display view
+            ui::Action::Open => {
+                if let Some(env_stats) = self.local_stats.get_selected(self.selected) {
+                    return Ok(ui::Message::DisplayStackPush(env_stats.id.clone()));
                 }
-                // PageUp
-                KeyCode::PageUp => {
-                    self.selected = self.selected.saturating_sub(10);
+            }
+            // Shortcut to the diary entry for the current day
+            ui::Action::Diary if self.diary_config.enabled => {
+                // Get the current date
+                let title_format = self.diary_config.title_format.as_deref().unwrap_or("%F");
+
+                // Determine the desired note id for today's diary entry
+                let diary_entry_name = format!("{}", Local::now().format(title_format));
+                let diary_entry_id = data::name_to_id(&diary_entry_name);
+
+                // Create the note if it note yet exists
+                if self.index.borrow().get(&diary_entry_id).is_none() {
+                    let created_note_path = self.manager.create_note_file(
+                        &diary_entry_name,
+                        self.diary_config.initial_content.clone(),
+                    )?;
+
+                    // Directly insert the new note into the index rather than relying on
+                    // the file watcher.
+                    self.index
+                        .borrow_mut()
+                        .insert_note_from_path(&created_note_path)?;
+                    self.refresh_env_stats();
+
+                    // Open the new note in an external editor
+                    return Ok(ui::Message::OpenExternalCommand(Box::new(
+                        self.manager.create_edit_command(&created_note_path, None)?,
+                    )));
                 }
+                return Ok(ui::Message::DisplayStackPush(diary_entry_id));
+            }
+            _ => {}
+        }
+
+        Ok(ui::Message::None)
+    }
+
+    /// Runs the given command chosen from the command palette.
+    fn run_command(&mut self, command: Command) -> error::Result<ui::Message> {
+        match command {
+            Command::Action(action) => self.run_action(action),
+            // press the key within the submenu, just like the user would
+            Command::Submenu(mode, key) => {
+                self.mode = mode;
+                super::Screen::update(self, KeyEvent::from(KeyCode::Char(key)))
+            }
+            Command::SavedFilter(position) => {
+                self.apply_saved_filter(position);
+                Ok(ui::Message::None)
+            }
+        }
+    }
 
-                // To the start
-                KeyCode::Char('0') => {
-                    self.selected = 0;
+    /// Returns all commands of this screen for the command palette: its actions, the entries of the submenus and the saved filters.
+    fn palette_entries(&self) -> Vec<ui::PaletteEntry<Command>> {
+        let mut entries = ui::Action::SELECT
+            .iter()
+            .filter(|action| !action.is_navigation())
+            .filter(|action| match action {
+                ui::Action::Diary => self.diary_config.enabled,
+                ui::Action::Results => self.has_full_text,
+                _ => true,
+            })
+            .map(|action| {
+                ui::PaletteEntry::new(
+                    action.description(),
+                    self.keys.names(*action).join(", "),
+                    Command::Action(*action),
+                )
+            })
+            .collect_vec();
+
+        for mode in [
+            SelectMode::SubmenuFile,
+            SelectMode::SubmenuSorting,
+            SelectMode::SubmenuSortingThen,
+        ] {
+            let prefix = match mode {
+                SelectMode::SubmenuFile => self.keys.name(ui::Action::FileMenu),
+                SelectMode::SubmenuSortingThen => {
+                    format!("{} +", self.keys.name(ui::Action::SortMenu))
                 }
-                // Open selected item in display view
-                KeyCode::Enter | KeyCode::Char('l' | 'L') | KeyCode::Right => {
-                    if let Some(env_stats) = self.local_stats.get_selected(self.selected) {
-                        return Ok(ui::Message::DisplayStackPush(env_stats.id.clone()));
-                    }
+                _ => self.keys.name(ui::Action::SortMenu),
+            };
+            for (key, description) in self.submenu_entries(mode) {
+                // choosing a column after + is listed on its own, shuffling ignores previous columns anyway
+                if key == "+" || (mode == SelectMode::SubmenuSortingThen && key == "S") {
+                    continue;
                 }
-                // Shortcut to the diary entry for the current day
-                KeyCode::Char('d' | 'D') if self.diary_config.enabled => {
-                    // Get the current date
-                    let title_format = self.diary_config.title_format.as_deref().unwrap_or("%F");
-
-                    // Determine the desired note id for today's diary entry
-                    let diary_entry_name = format!("{}", Local::now().format(title_format));
-                    let diary_entry_id = data::name_to_id(&diary_entry_name);
-
-                    // Create the note if it note yet exists
-                    if self.index.borrow().get(&diary_entry_id).is_none() {
-                        let created_note_path = self.manager.create_note_file(
-                            &diary_entry_name,
-                            self.diary_config.initial_content.clone(),
-                        )?;
-
-                        // Directly insert the new note into the index rather than relying on
-                        // the file watcher.
-                        self.index
-                            .borrow_mut()
-                            .insert_note_from_path(&created_note_path)?;
-                        self.refresh_env_stats();
+                if let Some(c) = key.chars().next() {
+                    entries.push(ui::PaletteEntry::new(
+                        description,
+                        format!("{prefix} {key}").trim_start(),
+                        Command::Submenu(mode, c),
+                    ));
+                }
+            }
+        }
 
-                        // Open the new note in an external editor
-                        return Ok(ui::Message::OpenExternalCommand(Box::new(
-                            self.manager.create_edit_command(&created_note_path)?,
-                        )));
-                    }
-                    return Ok(ui::Message::DisplayStackPush(diary_entry_id));
+        entries.extend(
+            self.saved_filters
+                .iter()
+                .enumerate()
+                .map(|(position, (name, saved))| {
+                    ui::PaletteEntry::new(
+                        format!("Apply saved filter {name}"),
+                        saved.filter.as_str(),
+                        Command::SavedFilter(position),
+                    )
+                }),
+        );
+
+        entries
+    }
+
+    /// Returns the keys and descriptions of the entries of the given submenu.
+    fn submenu_entries(&self, mode: SelectMode) -> Vec<(String, String)> {
+        if mode == SelectMode::SubmenuFile {
+            // with marked notes, most operations apply to all of them
+            let targets = if self.marked.is_empty() {
+                "selected note".to_string()
+            } else {
+                format!(
+                    "{} marked note{}",
+                    self.marked.len(),
+                    if self.marked.len() == 1 { "" } else { "s" }
+                )
+            };
+            [
+                ("N", "New note".to_string()),
+                ("R", "Rename selected note".to_string()),
+                ("M", format!("Move {targets}")),
+                ("C", format!("Copy {targets}")),
+                ("D", format!("Delete {targets}")),
+                ("T", format!("Add tag to {targets}")),
+                ("U", format!("Remove tag from {targets}")),
+            ]
+            .into_iter()
+            .map(|(a, b)| (a.to_string(), b))
+            .collect_vec()
+        } else {
+            // after +, the chosen column is added to the current ones
+            let then = mode == SelectMode::SubmenuSortingThen;
+            let verb = if then { "Then by" } else { "Sort by" };
+            let mut contents = vec![
+                ("S", "Shuffle".to_string()),
+                ("A", format!("{verb} name")),
+                ("F", format!("{verb} folder")),
+                ("W", format!("{verb} words")),
+                ("C", format!("{verb} characters")),
+                ("O", format!("{verb} global outlinks")),
+                ("U", format!("{verb} local outlinks")),
+                ("I", format!("{verb} global inlinks")),
+                ("N", format!("{verb} local inlinks")),
+                ("B", format!("{verb} broken links")),
+                ("M", format!("{verb} last modification")),
+                ("E", format!("{verb} score")),
+            ];
+            if !then {
+                contents.push(("+", "Then by...".to_string()));
+                if self.sorting.len() > 1 {
+                    contents.push(("-", "Remove last column".to_string()));
+                }
+                contents.push(("R", "Reverse sorting".to_string()));
+            }
+            contents
+                .into_iter()
+                .map(|(a, b)| (a.to_string(), b))
+                .collect_vec()
+        }
+    }
+
+    /// Draws the rendered content of the selected note.
+    fn draw_preview(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let index = self.index.borrow();
+        let selected = self.local_stats.get_selected(self.selected);
+        let note = selected.and_then(|env_stats| index.get(&env_stats.id));
+
+        // Read and render the note only when another note is selected
+        let mut preview = self.preview.borrow_mut();
+        match selected.zip(note) {
+            Some((env_stats, note)) => {
+                if preview.as_ref().is_none_or(|(id, _)| *id != env_stats.id) {
+                    *preview = std::fs::read_to_string(&note.path).ok().map(|content| {
+                        (
+                            env_stats.id.clone(),
+                            ui::Preview::new(&content, &self.styles),
+                        )
+                    });
+                }
+            }
+            None => *preview = None,
+        }
+
+        let lines = preview
+            .as_ref()
+            .map(|(_, preview)| {
+                preview
+                    .to_lines(area.width.saturating_sub(2) as usize, None)
+                    .0
+            })
+            .unwrap_or_default();
+
+        let title = Line::from(vec![Span::styled(
+            note.map(|note| note.display_name.as_str())
+                .unwrap_or("Preview"),
+            self.styles.title_style,
+        )]);
+
+        Widget::render(
+            Paragraph::new(lines).block(Block::bordered().title_top(title)),
+            area,
+            buf,
+        );
+    }
+
+    /// Draws the lines matching the full-text search in place of the note list, with the matching parts highlighted.
+    fn draw_text_results(&self, selected: usize, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let index = self.index.borrow();
+
+        // Generate state from selected element
+        let mut state = TableState::new()
+            .with_offset(
+                selected
+                    // try to keep element at above 1/3rd of the total height
+                    .saturating_sub(area.height as usize / 3)
+                    .min(
+                        // but when reaching the end of the list, still scroll down
+                        self.text_results
+                            .len()
+                            // correct for table edges
+                            .saturating_add(3)
+                            .saturating_sub(area.height as usize),
+                    ),
+            )
+            .with_selected(selected);
+
+        let rows = self
+            .text_results
+            .iter()
+            .map(|(id, text_match)| {
+                Row::new(vec![
+                    Cell::from(
+                        index
+                            .get(id)
+                            .map(|note| note.display_name.clone())
+                            .unwrap_or_default(),
+                    )
+                    .style(self.styles.subtitle_style),
+                    Cell::from(format!("{:5}", text_match.line)).style(self.styles.text_style),
+                    Cell::from(Line::from(
+                        text_match
+                            .snippet(20)
+                            .into_iter()
+                            .map(|(text, highlighted)| {
+                                Span::styled(
+                                    text,
+                                    if highlighted {
+                                        self.styles.hotkey_style
+                                    } else {
+                                        self.styles.text_style
+                                    },
+                                )
+                            })
+                            .collect_vec(),
+                    )),
+                ])
+            })
+            .collect_vec();
+
+        let widths = [
+            Constraint::Fill(1),
+            Constraint::Length(5),
+            Constraint::Fill(3),
+        ];
+
+        // Instructions at the bottom of the page, showing the configured keys
+        let instructions_bot_left = Line::from(ui::join_hints(
+            [
+                self.keys.hint_all(ui::Action::Down, "Down", &self.styles),
+                self.keys.hint_all(ui::Action::Up, "Up", &self.styles),
+                vec![
+                    Span::styled("↵", self.styles.hotkey_style),
+                    Span::styled(": Edit at line", self.styles.text_style),
+                ],
+                self.keys.hint(ui::Action::Open, "Open", &self.styles),
+            ],
+            &self.styles,
+        ))
+        .left_aligned();
+
+        let mut back_hint = vec![];
+        let back_key = self.keys.name(ui::Action::Results);
+        if !back_key.is_empty() {
+            back_hint.push(Span::styled(back_key, self.styles.hotkey_style));
+            back_hint.push(Span::styled("/", self.styles.text_style));
+        }
+        back_hint.push(Span::styled("Esc", self.styles.hotkey_style));
+        back_hint.push(Span::styled(": Back to notes", self.styles.text_style));
+        let instructions_bot_right = Line::from(ui::join_hints(
+            [
+                back_hint,
+                self.keys.hint(ui::Action::Quit, "Quit", &self.styles),
+            ],
+            &self.styles,
+        ))
+        .right_aligned();
+
+        let count = Line::from(vec![Span::styled(
+            format!(
+                "{} Match{}",
+                self.text_results.len(),
+                if self.text_results.len() == 1 {
+                    ""
+                } else {
+                    "es"
+                }
+            ),
+            self.styles.text_style,
+        )])
+        .right_aligned();
+
+        let table = Table::new(rows, widths)
+            .column_spacing(1)
+            .header(Row::new(
+                ["Note", " Line", "Match"]
+                    .into_iter()
+                    .map(|title| Span::styled(title, self.styles.subtitle_style)),
+            ))
+            .row_highlight_style(self.styles.selected_style)
+            .block(
+                Block::bordered()
+                    .title_top(style::Styled::set_style(
+                        "Full-Text Results",
+                        self.styles.title_style,
+                    ))
+                    .title_top(count)
+                    .title_bottom(instructions_bot_left)
+                    .title_bottom(instructions_bot_right),
+            );
+
+        StatefulWidget::render(table, area, buf, &mut state);
+    }
+}
+
+impl super::Screen for SelectScreen {
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        let key: ratatui::crossterm::event::KeyEvent = key;
+        // Check for mode
+        match self.mode {
+            // Main mode: Switch to modes, general command
+            SelectMode::Select => {
+                if let Some(action) = self.keys.action(&key, ui::Action::SELECT) {
+                    return self.run_action(action);
+                }
+            }
+            // Palette mode: Search and run a command
+            SelectMode::Palette => match self.palette.update(key) {
+                ui::PaletteChoice::Pending => {}
+                ui::PaletteChoice::Cancelled => self.mode = SelectMode::Select,
+                ui::PaletteChoice::Chosen(command) => {
+                    self.mode = SelectMode::Select;
+                    return self.run_command(command);
                 }
-                _ => {}
             },
             // Filter mode: Type in filter values
             SelectMode::Filter => {
@@ -423,15 +1172,83 @@ impl super::Screen for SelectScreen {
                     KeyCode::Esc | KeyCode::Enter => {
                         self.mode = SelectMode::Select;
                         self.filter(self.filter_from_input());
+                        // remember the filter to recall it later
+                        let filter = self
+                            .filter_area
+                            .lines()
+                            .first()
+                            .cloned()
+                            .unwrap_or_default();
+                        self.filter_history.push(&filter)?;
+                    }
+                    // Up: Recall the previous filter
+                    KeyCode::Up => {
+                        let current = self
+                            .filter_area
+                            .lines()
+                            .first()
+                            .cloned()
+                            .unwrap_or_default();
+                        if let Some(filter) = self.filter_history.previous(&current) {
+                            let filter = filter.to_owned();
+                            self.set_filter_text(&filter, filter.chars().count());
+                        }
+                    }
+                    // Down: Recall the next filter
+                    KeyCode::Down => {
+                        if let Some(filter) = self.filter_history.next() {
+                            let filter = filter.to_owned();
+                            self.set_filter_text(&filter, filter.chars().count());
+                        }
+                    }
+                    // Tab: Complete the word before the cursor
+                    KeyCode::Tab => {
+                        self.completions =
+                            data::filter_completions(&self.filter_word(), &self.index.borrow());
+                        match self.completions.len() {
+                            0 => {}
+                            // a single completion is inserted right away
+                            1 => self.complete_filter_word(0),
+                            _ => self.mode = SelectMode::Completion(0),
+                        }
                     }
                     // All other key events are passed on to the text area, then the filter is immediately applied
                     _ => {
                         // Else -> Pass on to the text area
+                        self.filter_history.reset();
                         self.filter_area.input(key);
                         self.filter(self.filter_from_input());
                     }
                 };
             }
+            SelectMode::Completion(selected) => {
+                let total = self.completions.len();
+                match key.code {
+                    // Tab, Down: Next completion
+                    KeyCode::Tab | KeyCode::Down => {
+                        self.mode = SelectMode::Completion((selected + 1) % total.max(1));
+                    }
+                    // Shift+Tab, Up: Previous completion
+                    KeyCode::BackTab | KeyCode::Up => {
+                        self.mode =
+                            SelectMode::Completion((selected + total.max(1) - 1) % total.max(1));
+                    }
+                    // Enter: Insert the selected completion
+                    KeyCode::Enter => {
+                        self.mode = SelectMode::Filter;
+                        self.complete_filter_word(selected);
+                    }
+                    // Escape: Close the completions
+                    KeyCode::Esc => {
+                        self.mode = SelectMode::Filter;
+                    }
+                    // All other keys close the completions and are typed into the filter area
+                    _ => {
+                        self.mode = SelectMode::Filter;
+                        return self.update(key);
+                    }
+                }
+            }
             SelectMode::FilterHelp => {
                 match key.code {
                     // Escape or Enter: Back to main mode
@@ -492,19 +1309,134 @@ impl super::Screen for SelectScreen {
                     KeyCode::Char('m' | 'M') => {
                         self.tag_match = self.tag_match.cycle();
                         self.filter(self.filter_from_input());
+                        self.count_saved_filters();
                         self.style_text_area();
                     }
                     // All other key events are ignored
                     _ => {}
                 };
             }
+            SelectMode::Results(selected) => {
+                let total = self.text_results.len();
+                let action = match key.code {
+                    // Escape: Back to the note list
+                    KeyCode::Esc => Some(ui::Action::Results),
+                    // Enter: Open the note in the editor at the line of the result
+                    KeyCode::Enter => Some(ui::Action::Edit),
+                    _ => self.keys.action(&key, RESULTS_ACTIONS),
+                };
+                match action {
+                    // Quit application
+                    Some(ui::Action::Quit) => return Ok(ui::Message::Quit),
+                    // Back to the note list
+                    Some(ui::Action::Results) => {
+                        self.mode = SelectMode::Select;
+                    }
+                    // Go to filter mode
+                    Some(ui::Action::Filter) => {
+                        self.mode = SelectMode::Filter;
+                    }
+                    // Navigate Down
+                    Some(ui::Action::Down) => {
+                        self.mode = SelectMode::Results(
+                            (selected.saturating_add(1)).min(total.saturating_sub(1)),
+                        );
+                    }
+                    // Navigate Up
+                    Some(ui::Action::Up) => {
+                        self.mode = SelectMode::Results(selected.saturating_sub(1));
+                    }
+                    // PageDown
+                    Some(ui::Action::PageDown) => {
+                        self.mode = SelectMode::Results(
+                            (selected.saturating_add(10)).min(total.saturating_sub(1)),
+                        );
+                    }
+                    // PageUp
+                    Some(ui::Action::PageUp) => {
+                        self.mode = SelectMode::Results(selected.saturating_sub(10));
+                    }
+                    // To the start
+                    Some(ui::Action::Top) => {
+                        self.mode = SelectMode::Results(0);
+                    }
+                    // Open the note in the editor at the line of the result
+                    Some(ui::Action::Edit) => {
+                        if let Some((path, line)) =
+                            self.text_results
+                                .get(selected)
+                                .and_then(|(id, text_match)| {
+                                    self.index
+                                        .borrow()
+                                        .get(id)
+                                        .map(|note| (note.path.clone(), text_match.line))
+                                })
+                        {
+                            return Ok(ui::Message::OpenExternalCommand(Box::new(
+                                self.manager.create_edit_command(&path, Some(line))?,
+                            )));
+                        }
+                    }
+                    // Open the note of the result in display view
+                    Some(ui::Action::Open) => {
+                        if let Some((id, _text_match)) = self.text_results.get(selected) {
+                            return Ok(ui::Message::DisplayStackPush(id.clone()));
+                        }
+                    }
+                    // All other key events are ignored
+                    _ => {}
+                };
+            }
+            SelectMode::SavedFilters(selected) => {
+                let total = self.saved_filters.len();
+                match key.code {
+                    // Escape or C: Back to main mode
+                    KeyCode::Esc | KeyCode::Char('c' | 'C') => {
+                        self.mode = SelectMode::Select;
+                    }
+                    // J: Navigate Down
+                    KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                        self.mode = SelectMode::SavedFilters(
+                            (selected.saturating_add(1)).min(total.saturating_sub(1)),
+                        );
+                    }
+                    // K: Navigate Up
+                    KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                        self.mode = SelectMode::SavedFilters(selected.saturating_sub(1));
+                    }
+                    // PageDown
+                    KeyCode::PageDown => {
+                        self.mode = SelectMode::SavedFilters(
+                            (selected.saturating_add(10)).min(total.saturating_sub(1)),
+                        );
+                    }
+                    // PageUp
+                    KeyCode::PageUp => {
+                        self.mode = SelectMode::SavedFilters(selected.saturating_sub(10));
+                    }
+                    // Enter: Apply the selected filter and go to select mode
+                    KeyCode::Enter => {
+                        self.apply_saved_filter(selected);
+                        self.mode = SelectMode::Select;
+                    }
+                    // All other key events are ignored
+                    _ => {}
+                };
+            }
             // File mode: Wait for second input
             SelectMode::SubmenuFile => {
                 match key.code {
-                    // D: Delete note
+                    // D: Delete note, or all marked ones after confirmation
+                    KeyCode::Char('d' | 'D') if !self.marked.is_empty() => {
+                        self.request_file_operation(FileOperation::Delete)?;
+                    }
                     KeyCode::Char('d' | 'D') => {
                         self.mode = SelectMode::Delete;
                     }
+                    // C: Copy all marked notes after confirmation
+                    KeyCode::Char('c' | 'C') if !self.marked.is_empty() => {
+                        self.request_file_operation(FileOperation::Copy)?;
+                    }
                     // C: Copy note
                     KeyCode::Char('c' | 'C') => {
                         if let Some(env_stats) = self
@@ -548,6 +1480,16 @@ impl super::Screen for SelectScreen {
                         self.mode = SelectMode::Move;
                         self.set_name_area("Enter new location relative to vault...", None);
                     }
+                    // T: Add tag to note
+                    KeyCode::Char('t' | 'T') => {
+                        self.mode = SelectMode::AddTag;
+                        self.set_name_area("Enter tag to add...", None);
+                    }
+                    // U: Remove tag from note
+                    KeyCode::Char('u' | 'U') => {
+                        self.mode = SelectMode::RemoveTag;
+                        self.set_name_area("Enter tag to remove...", None);
+                    }
                     // Back to select mode
                     KeyCode::Esc => {
                         self.mode = SelectMode::Select;
@@ -556,7 +1498,11 @@ impl super::Screen for SelectScreen {
                 }
             }
             // Modes that require input in the text box.
-            SelectMode::Create | SelectMode::Rename | SelectMode::Move => {
+            SelectMode::Create
+            | SelectMode::Rename
+            | SelectMode::Move
+            | SelectMode::AddTag
+            | SelectMode::RemoveTag => {
                 match key.code {
                     // Escape: Back to main mode, clear the buffer
                     KeyCode::Esc => {
@@ -606,6 +1552,16 @@ impl super::Screen for SelectScreen {
                                     self.refresh_env_stats();
                                 }
                             }
+                            SelectMode::Move if !self.marked.is_empty() => {
+                                // Ask for confirmation before moving all marked notes.
+                                let path = super::extract_string_and_clear(&mut self.name_area)
+                                    .ok_or_else(|| {
+                                        error::RucolaError::Input(
+                                            "Move target is empty.".to_string(),
+                                        )
+                                    })?;
+                                self.request_file_operation(FileOperation::Move(path))?;
+                            }
                             SelectMode::Move => {
                                 // Get the id of currently selected, then delegate to note_file::move.
                                 if let Some(env_stats) =
@@ -626,6 +1582,17 @@ impl super::Screen for SelectScreen {
                                     self.refresh_env_stats();
                                 }
                             }
+                            SelectMode::AddTag | SelectMode::RemoveTag => {
+                                let tag = super::extract_string_and_clear(&mut self.name_area)
+                                    .ok_or_else(|| {
+                                        error::RucolaError::Input("Tag is empty.".to_string())
+                                    })?;
+                                self.request_file_operation(if mode == SelectMode::AddTag {
+                                    FileOperation::AddTag(tag)
+                                } else {
+                                    FileOperation::RemoveTag(tag)
+                                })?;
+                            }
                             _ => {
                                 //This should NOT happen
                             }
@@ -659,48 +1626,65 @@ impl super::Screen for SelectScreen {
                     self.mode = SelectMode::Select;
                 }
             },
-            // Sorting submenu: Wait for second input
-            SelectMode::SubmenuSorting => {
+            // Bulk confirmation: Enter applies the pending operation to all marked notes, all others cancel.
+            SelectMode::ConfirmBulk => {
                 self.mode = SelectMode::Select;
-                match key.code {
-                    KeyCode::Char('s' | 'S') => {
-                        self.set_mode_sort(data::NoteColumn::Shuffle, true);
-                    }
-                    KeyCode::Char('a' | 'A') => {
-                        self.set_mode_sort(data::NoteColumn::Name, true);
-                    }
-                    KeyCode::Char('w' | 'W') => {
-                        self.set_mode_sort(data::NoteColumn::Words, false);
-                    }
-                    KeyCode::Char('c' | 'C') => {
-                        self.set_mode_sort(data::NoteColumn::Chars, false);
+                if let Some(operation) = self.pending_operation.take() {
+                    if key.code == KeyCode::Enter {
+                        self.apply_bulk_operation(&operation)?;
                     }
-                    KeyCode::Char('o' | 'O') => {
-                        self.set_mode_sort(data::NoteColumn::GlobalOutLinks, false);
-                    }
-                    KeyCode::Char('u' | 'U') => {
-                        self.set_mode_sort(data::NoteColumn::LocalOutLinks, false);
-                    }
-                    KeyCode::Char('i' | 'I') => {
-                        self.set_mode_sort(data::NoteColumn::GlobalInLinks, false);
-                    }
-                    KeyCode::Char('n' | 'N') => {
-                        self.set_mode_sort(data::NoteColumn::LocalInLinks, false);
+                }
+            }
+            // Sorting submenu: Wait for second input
+            SelectMode::SubmenuSorting | SelectMode::SubmenuSortingThen => {
+                let then = self.mode == SelectMode::SubmenuSortingThen;
+                self.mode = SelectMode::Select;
+                let column = match key.code {
+                    KeyCode::Char('s' | 'S') => Some((data::NoteColumn::Shuffle, true)),
+                    KeyCode::Char('a' | 'A') => Some((data::NoteColumn::Name, true)),
+                    KeyCode::Char('f' | 'F') => Some((data::NoteColumn::Folder, true)),
+                    KeyCode::Char('w' | 'W') => Some((data::NoteColumn::Words, false)),
+                    KeyCode::Char('c' | 'C') => Some((data::NoteColumn::Chars, false)),
+                    KeyCode::Char('o' | 'O') => Some((data::NoteColumn::GlobalOutLinks, false)),
+                    KeyCode::Char('u' | 'U') => Some((data::NoteColumn::LocalOutLinks, false)),
+                    KeyCode::Char('i' | 'I') => Some((data::NoteColumn::GlobalInLinks, false)),
+                    KeyCode::Char('n' | 'N') => Some((data::NoteColumn::LocalInLinks, false)),
+                    KeyCode::Char('b' | 'B') => Some((data::NoteColumn::Broken, false)),
+                    KeyCode::Char('m' | 'M') => Some((data::NoteColumn::LastModified, false)),
+                    KeyCode::Char('e' | 'E') => Some((data::NoteColumn::Score, false)),
+                    _ => None,
+                };
+                match (column, key.code) {
+                    (Some((column, ascending)), _) => {
+                        self.sort_by_column(column, ascending, then);
                     }
-                    KeyCode::Char('b' | 'B') => {
-                        self.set_mode_sort(data::NoteColumn::Broken, false);
+                    // +: Choose a column to sort by after the current ones
+                    (None, KeyCode::Char('+')) if !then => {
+                        self.mode = SelectMode::SubmenuSortingThen;
                     }
-                    KeyCode::Char('m' | 'M') => {
-                        self.set_mode_sort(data::NoteColumn::LastModified, false);
+                    // -: Stop sorting by the last column
+                    (None, KeyCode::Char('-')) if !then && self.sorting.len() > 1 => {
+                        let mut sorting = self.sorting.clone();
+                        sorting.pop();
+                        self.set_sorting(sorting);
                     }
-                    KeyCode::Char('e' | 'E') => {
-                        self.set_mode_sort(data::NoteColumn::Score, false);
+                    // R: Reverse the direction of all columns
+                    (None, KeyCode::Char('r' | 'R')) if !then => {
+                        let sorting = self
+                            .sorting
+                            .iter()
+                            .map(|key| data::SortKey::new(key.column, !key.ascending))
+                            .collect();
+                        self.set_sorting(sorting);
                     }
-                    KeyCode::Char('r' | 'R') => {
-                        self.local_stats.reverse_order();
+                    (None, KeyCode::Esc) => {}
+                    _ => {
+                        self.mode = if then {
+                            SelectMode::SubmenuSortingThen
+                        } else {
+                            SelectMode::SubmenuSorting
+                        }
                     }
-                    KeyCode::Esc => {}
-                    _ => self.mode = SelectMode::SubmenuSorting,
                 }
             }
         };
@@ -724,6 +1708,33 @@ impl super::Screen for SelectScreen {
         let [title_area, global_stats_area, local_stats_area, filter_area, table_area] =
             vertical.areas(area);
 
+        // Show the saved filters next to the note list, if enabled
+        let (table_area, saved_filters_area) =
+            if self.show_saved_filters && !self.saved_filters.is_empty() {
+                let width = self
+                    .saved_filters
+                    .iter()
+                    .map(|(name, _saved)| name.chars().count() as u16 + 8)
+                    .max()
+                    .unwrap_or_default()
+                    .max(17);
+                let [table_area, saved_filters_area] =
+                    Layout::horizontal([Constraint::Min(0), Constraint::Length(width)])
+                        .areas(table_area);
+                (table_area, Some(saved_filters_area))
+            } else {
+                (table_area, None)
+            };
+
+        // Show the preview of the selected note next to the note list, if enabled
+        let (table_area, preview_area) = if self.show_preview {
+            let [table_area, preview_area] =
+                Layout::horizontal([Constraint::Fill(3), Constraint::Fill(2)]).areas(table_area);
+            (table_area, Some(preview_area))
+        } else {
+            (table_area, None)
+        };
+
         // Title
         let title = Line::from(vec![Span::styled(
             self.manager.get_vault_title(),
@@ -737,6 +1748,17 @@ impl super::Screen for SelectScreen {
         )])
         .alignment(Alignment::Right);
 
+        let palette_hint = Line::from(ui::join_hints(
+            [
+                self.keys
+                    .hint(ui::Action::Palette, "Commands", &self.styles),
+                self.keys
+                    .hint(ui::Action::QuickSwitcher, "Jump to note", &self.styles),
+            ],
+            &self.styles,
+        ))
+        .alignment(Alignment::Left);
+
         // Generate stats areas
         let global_stats =
             self.global_stats
@@ -776,33 +1798,54 @@ impl super::Screen for SelectScreen {
                 SelectMode::Select
                 | SelectMode::Rename
                 | SelectMode::Move
+                | SelectMode::AddTag
+                | SelectMode::RemoveTag
                 | SelectMode::Delete
+                | SelectMode::ConfirmBulk
+                | SelectMode::Palette
                 | SelectMode::SubmenuFile
-                | SelectMode::SubmenuSorting => Some(self.selected),
+                | SelectMode::SubmenuSorting
+                | SelectMode::SubmenuSortingThen => Some(self.selected),
                 SelectMode::Filter
+                | SelectMode::Completion(_)
                 | SelectMode::FilterHelp
                 | SelectMode::TagList(_)
+                | SelectMode::SavedFilters(_)
+                | SelectMode::Results(_)
                 | SelectMode::Create => None,
             });
 
         // Instructions at the bottom of the page
-        let instructions_bot_left = Line::from(vec![
-            Span::styled("J", self.styles.hotkey_style),
-            Span::styled("/", self.styles.text_style),
-            Span::styled("↓", self.styles.hotkey_style),
-            Span::styled(": Down──", self.styles.text_style),
-            Span::styled("K", self.styles.hotkey_style),
-            Span::styled("/", self.styles.text_style),
-            Span::styled("↑", self.styles.hotkey_style),
-            Span::styled(": Up──", self.styles.text_style),
-            Span::styled("L", self.styles.hotkey_style),
-            Span::styled("/", self.styles.text_style),
-            Span::styled("→", self.styles.hotkey_style),
-            Span::styled("/", self.styles.text_style),
-            Span::styled("↵", self.styles.hotkey_style),
-            Span::styled(": Open──", self.styles.text_style),
-        ])
-        .left_aligned();
+        let mut instructions_bot_left = ui::join_hints(
+            [
+                self.keys.hint_all(ui::Action::Down, "Down", &self.styles),
+                self.keys.hint_all(ui::Action::Up, "Up", &self.styles),
+                self.keys.hint_all(ui::Action::Open, "Open", &self.styles),
+                self.keys.hint(ui::Action::Mark, "Mark", &self.styles),
+            ],
+            &self.styles,
+        );
+        instructions_bot_left.push(Span::styled("──", self.styles.text_style));
+        let instructions_bot_left = Line::from(instructions_bot_left).left_aligned();
+
+        // Show how many notes are marked and how to (un)mark them all
+        let mut marking_hints = vec![self
+            .keys
+            .hint(ui::Action::MarkAll, "Mark all", &self.styles)];
+        if !self.marked.is_empty() {
+            marking_hints.insert(
+                0,
+                vec![Span::styled(
+                    format!("{} marked", self.marked.len()),
+                    self.styles.text_style,
+                )],
+            );
+            marking_hints.push(
+                self.keys
+                    .hint(ui::Action::UnmarkAll, "Unmark all", &self.styles),
+            );
+        }
+        let marking_info = Line::from(ui::join_hints(marking_hints, &self.styles)).right_aligned();
 
         // Display some git info:
         let git_info = if let Some(git_manager) = &self.git_manager {
@@ -826,33 +1869,44 @@ impl super::Screen for SelectScreen {
                 info.push('+');
             }
 
-            info.push_str("──");
-
             info
         } else {
             String::new()
         };
 
-        let mut keybinding_spans = vec![
-            Span::styled("E", self.styles.hotkey_style),
-            Span::styled("dit──", self.styles.text_style),
-            Span::styled("V", self.styles.hotkey_style),
-            Span::styled("iew──", self.styles.text_style),
-            Span::styled("S", self.styles.hotkey_style),
-            Span::styled("orting──", self.styles.text_style),
-            Span::styled(git_info, self.styles.text_style),
-            Span::styled("M", self.styles.hotkey_style),
-            Span::styled("anage Files──", self.styles.text_style),
-            Span::styled("Q", self.styles.hotkey_style),
-            Span::styled("uit", self.styles.text_style),
+        let mut keybinding_hints = vec![
+            self.keys.hint(ui::Action::Edit, "Edit", &self.styles),
+            self.keys.hint(ui::Action::View, "View", &self.styles),
+            self.keys
+                .hint(ui::Action::SortMenu, "Sorting", &self.styles),
+            self.keys.hint(ui::Action::Tasks, "Tasks", &self.styles),
+            self.keys
+                .hint(ui::Action::TogglePreview, "Preview", &self.styles),
+            if git_info.is_empty() {
+                vec![]
+            } else {
+                vec![Span::styled(git_info, self.styles.text_style)]
+            },
+            self.keys
+                .hint(ui::Action::FileMenu, "Manage Files", &self.styles),
+            self.keys.hint(ui::Action::Quit, "Quit", &self.styles),
         ];
         if self.diary_config.enabled {
-            keybinding_spans.insert(0, Span::styled("iary──", self.styles.text_style));
-            keybinding_spans.insert(0, Span::styled("D", self.styles.hotkey_style));
+            keybinding_hints.insert(0, self.keys.hint(ui::Action::Diary, "Diary", &self.styles));
+        }
+        if self.has_full_text {
+            keybinding_hints.insert(
+                0,
+                self.keys.hint(ui::Action::Results, "Results", &self.styles),
+            );
         }
-        let instructions_bot_right = Line::from(keybinding_spans).right_aligned();
+        let instructions_bot_right =
+            Line::from(ui::join_hints(keybinding_hints, &self.styles)).right_aligned();
 
-        let table_heading_key_style = if self.mode == SelectMode::SubmenuSorting {
+        let table_heading_key_style = if matches!(
+            self.mode,
+            SelectMode::SubmenuSorting | SelectMode::SubmenuSortingThen
+        ) {
             self.styles.hotkey_style
         } else {
             self.styles.subtitle_style
@@ -861,18 +1915,36 @@ impl super::Screen for SelectScreen {
         // Finally generate the table from the generated row and width data
         let table = self
             .local_stats
-            .to_note_table(self.index.clone(), &self.styles, &self.column_config)
-            // Add Headers
-            .header(Row::new(self.column_config.iter().map(
-                |(title, column)| {
-                    column.title_line(title, self.styles.subtitle_style, table_heading_key_style)
-                },
-            )))
+            .to_note_table(
+                self.index.clone(),
+                &self.styles,
+                &self.column_config,
+                &self.sorting,
+                &self.marked,
+            )
+            // Add Headers, marking the columns the notes are sorted by
+            .header(Row::new(
+                (!self.marked.is_empty())
+                    .then(Line::default)
+                    .into_iter()
+                    .chain(self.column_config.iter().map(|(title, column)| {
+                        let mut line = column.title_line(
+                            title,
+                            self.styles.subtitle_style,
+                            table_heading_key_style,
+                        );
+                        if let Some(marker) = data::SortKey::marker(&self.sorting, *column) {
+                            line.push_span(Span::styled(marker, self.styles.hotkey_style));
+                        }
+                        line
+                    })),
+            ))
             .row_highlight_style(self.styles.selected_style)
             // Add Instructions and a title
             .block(
                 Block::bordered()
                     .title_top(style::Styled::set_style("Notes", self.styles.title_style))
+                    .title_top(marking_info)
                     .title_bottom(instructions_bot_left)
                     .title_bottom(instructions_bot_right),
             );
@@ -880,45 +1952,78 @@ impl super::Screen for SelectScreen {
         // === Rendering ===
         Widget::render(title, title_area, buf);
         Widget::render(version, title_area, buf);
+        Widget::render(palette_hint, title_area, buf);
 
         Widget::render(&self.filter_area, filter_area, buf);
 
         Widget::render(global_stats, global_stats_area, buf);
         Widget::render(local_stats, local_stats_area, buf);
 
-        StatefulWidget::render(table, table_area, buf, &mut state);
+        if let SelectMode::Results(selected) = self.mode {
+            self.draw_text_results(selected, table_area, buf);
+        } else {
+            StatefulWidget::render(table, table_area, buf, &mut state);
+        }
 
-        // Render possible pop-ups
-        match self.mode {
-            SelectMode::SubmenuFile | SelectMode::SubmenuSorting => {
-                let contents = if self.mode == SelectMode::SubmenuFile {
-                    vec![
-                        ("N", "New note"),
-                        ("R", "Rename selected note"),
-                        ("M", "Move selected note"),
-                        ("C", "Copy selected file"),
-                        ("D", "Delete selected note"),
-                    ]
-                } else {
-                    vec![
-                        ("S", "Shuffle"),
-                        ("A", "Sort by name"),
-                        ("W", "Sort by words"),
-                        ("C", "Sort by characters"),
-                        ("O", "Sort by global outlinks"),
-                        ("U", "Sort by local outlinks"),
-                        ("I", "Sort by global inlinks"),
-                        ("N", "Sort by local inlinks"),
-                        ("B", "Sort by broken links"),
-                        ("M", "Sort by last modification"),
-                        ("E", "Sort by score"),
-                        ("R", "Reverse sorting"),
-                    ]
-                }
+        if let Some(preview_area) = preview_area {
+            self.draw_preview(preview_area, buf);
+        }
+
+        if let Some(saved_filters_area) = saved_filters_area {
+            // highlight the saved filter that is currently applied
+            let current = self
+                .filter_area
+                .lines()
+                .first()
+                .cloned()
+                .unwrap_or_default();
+            let saved_filter_rows = self
+                .saved_filters
                 .iter()
-                .map(|(a, b)| (a.to_string(), b.to_string()))
+                .zip(self.saved_filter_counts.iter())
+                .map(|((name, saved), count)| {
+                    Row::new(vec![
+                        Cell::from(name.as_str()),
+                        Cell::from(format!("{:5}", count)),
+                    ])
+                    .style(if saved.filter == current {
+                        self.styles.selected_style
+                    } else {
+                        self.styles.text_style
+                    })
+                })
                 .collect_vec();
 
+            let saved_filters_table = Table::new(
+                saved_filter_rows,
+                [Constraint::Min(0), Constraint::Length(5)],
+            )
+            .column_spacing(1)
+            .block(
+                Block::bordered()
+                    .title_top(style::Styled::set_style(
+                        "Saved Filters",
+                        self.styles.title_style,
+                    ))
+                    .title_bottom(
+                        Line::from(
+                            self.keys
+                                .hint(ui::Action::SavedFilters, "Apply", &self.styles),
+                        )
+                        .right_aligned(),
+                    ),
+            );
+
+            Widget::render(saved_filters_table, saved_filters_area, buf);
+        }
+
+        // Render possible pop-ups
+        match self.mode {
+            SelectMode::SubmenuFile
+            | SelectMode::SubmenuSorting
+            | SelectMode::SubmenuSortingThen => {
+                let contents = self.submenu_entries(self.mode);
+
                 let popup_areas = Layout::vertical([
                     Constraint::Fill(1),
                     Constraint::Length(contents.len() as u16 + 2),
@@ -960,8 +2065,52 @@ impl super::Screen for SelectScreen {
                 Widget::render(Clear, br_area, buf);
                 Widget::render(popup_table, br_area, buf);
             }
-            SelectMode::Filter | SelectMode::Select => {}
-            SelectMode::Create | SelectMode::Rename | SelectMode::Move => {
+            SelectMode::Filter | SelectMode::Select | SelectMode::Results(_) => {}
+            SelectMode::Palette => self.palette.draw(area, buf),
+            SelectMode::Completion(selected) => {
+                // below the word before the cursor, as far as the screen allows
+                let column = self.filter_area.cursor().1 - self.filter_word().chars().count();
+                let width = (self
+                    .completions
+                    .iter()
+                    .map(|completion| completion.chars().count())
+                    .max()
+                    .unwrap_or_default() as u16
+                    + 2)
+                .min(area.width);
+                let height = (self.completions.len() as u16 + 2)
+                    .min(12)
+                    .min(table_area.height);
+                let popup_area = Rect {
+                    x: (filter_area.x + 1 + column as u16).min(area.right().saturating_sub(width)),
+                    y: filter_area.bottom(),
+                    width,
+                    height,
+                };
+
+                let items = self
+                    .completions
+                    .iter()
+                    .map(|completion| {
+                        ListItem::new(completion.as_str()).style(self.styles.text_style)
+                    })
+                    .collect_vec();
+
+                let mut state = ListState::default().with_selected(Some(selected));
+
+                let list = List::new(items)
+                    .highlight_style(self.styles.selected_style)
+                    .block(Block::bordered());
+
+                // Clear the area and then render the widget on top.
+                Widget::render(Clear, popup_area, buf);
+                StatefulWidget::render(list, popup_area, buf, &mut state);
+            }
+            SelectMode::Create
+            | SelectMode::Rename
+            | SelectMode::Move
+            | SelectMode::AddTag
+            | SelectMode::RemoveTag => {
                 let popup_areas = Layout::vertical([
                     Constraint::Fill(1),
                     Constraint::Length(3),
@@ -980,39 +2129,43 @@ impl super::Screen for SelectScreen {
                 Widget::render(Clear, center_area, buf);
                 Widget::render(&self.name_area, center_area, buf);
             }
-            SelectMode::Delete => {
-                let delete_confirmation = Paragraph::new(Text::styled(
-                    format!(
-                        "Delete selected note \"{}\"?",
-                        self.local_stats
-                            .get_selected(self.selected)
-                            .and_then(|note| self
-                                .index
-                                .borrow()
-                                .get(&note.id)
-                                .map(|note| note.display_name.clone()))
-                            .unwrap_or(String::from("<Unknown Note>"))
+            SelectMode::Delete | SelectMode::ConfirmBulk => {
+                let (title, question) = match &self.pending_operation {
+                    Some(operation) if self.mode == SelectMode::ConfirmBulk => (
+                        "Confirm bulk operation",
+                        self.describe_bulk_operation(operation),
                     ),
-                    self.styles.text_style,
-                ))
-                .block(
-                    Block::bordered()
-                        .title(style::Styled::set_style(
-                            "Confirm deletion",
-                            self.styles.title_style,
-                        ))
-                        .title_bottom(
-                            Line::from(vec![
-                                Span::styled("↵", self.styles.hotkey_style),
-                                Span::styled(": Confirm──", self.styles.text_style),
-                                Span::styled("Esc", self.styles.hotkey_style),
-                                Span::styled("/", self.styles.text_style),
-                                Span::styled("Any", self.styles.hotkey_style),
-                                Span::styled(": Cancel", self.styles.text_style),
-                            ])
-                            .right_aligned(),
+                    _ => (
+                        "Confirm deletion",
+                        format!(
+                            "Delete selected note \"{}\"?",
+                            self.local_stats
+                                .get_selected(self.selected)
+                                .and_then(|note| self
+                                    .index
+                                    .borrow()
+                                    .get(&note.id)
+                                    .map(|note| note.display_name.clone()))
+                                .unwrap_or(String::from("<Unknown Note>"))
                         ),
-                );
+                    ),
+                };
+                let delete_confirmation =
+                    Paragraph::new(Text::styled(question, self.styles.text_style)).block(
+                        Block::bordered()
+                            .title(style::Styled::set_style(title, self.styles.title_style))
+                            .title_bottom(
+                                Line::from(vec![
+                                    Span::styled("↵", self.styles.hotkey_style),
+                                    Span::styled(": Confirm──", self.styles.text_style),
+                                    Span::styled("Esc", self.styles.hotkey_style),
+                                    Span::styled("/", self.styles.text_style),
+                                    Span::styled("Any", self.styles.hotkey_style),
+                                    Span::styled(": Cancel", self.styles.text_style),
+                                ])
+                                .right_aligned(),
+                            ),
+                    );
 
                 let popup_areas = Layout::vertical([
                     Constraint::Fill(1),
@@ -1033,7 +2186,7 @@ impl super::Screen for SelectScreen {
                 Widget::render(delete_confirmation, center_area, buf);
             }
             SelectMode::FilterHelp => {
-                let help_widths = [Constraint::Length(9), Constraint::Min(0)];
+                let help_widths = [Constraint::Length(12), Constraint::Min(0)];
 
                 let help_rows = [
                     Row::new(vec![
@@ -1044,6 +2197,16 @@ impl super::Screen for SelectScreen {
                         Cell::from("↵ or Esc").style(self.styles.subtitle_style),
                         Cell::from("Exit the filter text box").style(self.styles.text_style),
                     ]),
+                    Row::new(vec![
+                        Cell::from("↑ or ↓").style(self.styles.subtitle_style),
+                        Cell::from("Recall previously applied filters.")
+                            .style(self.styles.text_style),
+                    ]),
+                    Row::new(vec![
+                        Cell::from("Tab").style(self.styles.subtitle_style),
+                        Cell::from("Complete tags, note names after > or < and property keys.")
+                            .style(self.styles.text_style),
+                    ]),
                     Row::new(vec![Cell::from("").style(self.styles.subtitle_style)]),
                     Row::new(vec![
                         Cell::from("#[tag]").style(self.styles.subtitle_style),
@@ -1076,6 +2239,50 @@ impl super::Screen for SelectScreen {
                         Cell::from(" ").style(self.styles.subtitle_style),
                         Cell::from("").style(self.styles.text_style),
                     ]),
+                    Row::new(vec![
+                        Cell::from("[key]:[v]").style(self.styles.subtitle_style),
+                        Cell::from("Show notes whose property [key] has value [v], * for any.")
+                            .style(self.styles.text_style),
+                    ]),
+                    Row::new(vec![
+                        Cell::from("![key]:[v]").style(self.styles.subtitle_style),
+                        Cell::from("Show notes whose property [key] lacks value [v].")
+                            .style(self.styles.text_style),
+                    ]),
+                    Row::new(vec![
+                        Cell::from("modified:[d]").style(self.styles.subtitle_style),
+                        Cell::from("Show notes modified on day, month or year [d].")
+                            .style(self.styles.text_style),
+                    ]),
+                    Row::new(vec![
+                        Cell::from("created:>[d]").style(self.styles.subtitle_style),
+                        Cell::from("Show notes created after [d], use < for before.")
+                            .style(self.styles.text_style),
+                    ]),
+                    Row::new(vec![
+                        Cell::from("").style(self.styles.subtitle_style),
+                        Cell::from("Dates like 2025-03 or ages like 7d, 2w, 3m, 1y.")
+                            .style(self.styles.text_style),
+                    ]),
+                    Row::new(vec![
+                        Cell::from("words:>[n]").style(self.styles.subtitle_style),
+                        Cell::from("Show notes with more than [n] words, also <, >=, <=.")
+                            .style(self.styles.text_style),
+                    ]),
+                    Row::new(vec![
+                        Cell::from("").style(self.styles.subtitle_style),
+                        Cell::from("Also chars, broken, inlinks and outlinks, e.g. inlinks:0.")
+                            .style(self.styles.text_style),
+                    ]),
+                    Row::new(vec![
+                        Cell::from("in:[folder]").style(self.styles.subtitle_style),
+                        Cell::from("Show notes in [folder] or its subfolders.")
+                            .style(self.styles.text_style),
+                    ]),
+                    Row::new(vec![
+                        Cell::from(" ").style(self.styles.subtitle_style),
+                        Cell::from("").style(self.styles.text_style),
+                    ]),
                     Row::new(vec![
                         Cell::from(">[note]").style(self.styles.subtitle_style),
                         Cell::from("Show notes linking to [note].").style(self.styles.text_style),
@@ -1102,8 +2309,28 @@ impl super::Screen for SelectScreen {
                     ]),
                     Row::new(vec![Cell::from("").style(self.styles.subtitle_style)]),
                     Row::new(vec![
-                        Cell::from("|").style(self.styles.subtitle_style),
-                        Cell::from("All text after | will be searched in the full text.")
+                        Cell::from("|[w]").style(self.styles.subtitle_style),
+                        Cell::from("Search words beginning with [w] in the full text.")
+                            .style(self.styles.text_style),
+                    ]),
+                    Row::new(vec![
+                        Cell::from("").style(self.styles.subtitle_style),
+                        Cell::from("E.g. |group finds groups, but not subgroups.")
+                            .style(self.styles.text_style),
+                    ]),
+                    Row::new(vec![
+                        Cell::from("|\"[p]\"").style(self.styles.subtitle_style),
+                        Cell::from("Search the exact phrase [p] in the full text.")
+                            .style(self.styles.text_style),
+                    ]),
+                    Row::new(vec![
+                        Cell::from("|/[r]/").style(self.styles.subtitle_style),
+                        Cell::from("Search the regular expression [r] in the full text.")
+                            .style(self.styles.text_style),
+                    ]),
+                    Row::new(vec![
+                        Cell::from("").style(self.styles.subtitle_style),
+                        Cell::from("Append c for case-sensitive, w for whole words.")
                             .style(self.styles.text_style),
                     ]),
                     Row::new(vec![
@@ -1111,6 +2338,17 @@ impl super::Screen for SelectScreen {
                         Cell::from("All other text will be matched against the title.")
                             .style(self.styles.text_style),
                     ]),
+                    Row::new(vec![Cell::from("").style(self.styles.subtitle_style)]),
+                    Row::new(vec![
+                        Cell::from("AND OR").style(self.styles.subtitle_style),
+                        Cell::from("Combine conditions, e.g. (#os OR #linux) AND NOT >windows.")
+                            .style(self.styles.text_style),
+                    ]),
+                    Row::new(vec![
+                        Cell::from("NOT ( )").style(self.styles.subtitle_style),
+                        Cell::from("Conditions without operator must all hold.")
+                            .style(self.styles.text_style),
+                    ]),
                 ];
 
                 // Pop-up should be as tall as the number of help dialog rows
@@ -1140,7 +2378,7 @@ impl super::Screen for SelectScreen {
 
                 let center_area = Layout::horizontal([
                     Constraint::Fill(1),
-                    Constraint::Length(64),
+                    Constraint::Length(76),
                     Constraint::Fill(1),
                 ])
                 .split(popup_areas[1])[1];
@@ -1237,6 +2475,105 @@ impl super::Screen for SelectScreen {
                 Widget::render(Clear, center_area, buf);
                 StatefulWidget::render(tag_table, center_area, buf, &mut state);
             }
+            SelectMode::SavedFilters(selected) => {
+                let name_width = self
+                    .saved_filters
+                    .iter()
+                    .map(|(name, _saved)| name.chars().count() as u16)
+                    .max()
+                    .unwrap_or_default();
+                let saved_widths = [
+                    Constraint::Length(name_width),
+                    Constraint::Min(0),
+                    Constraint::Length(5),
+                ];
+
+                let mut saved_rows = self
+                    .saved_filters
+                    .iter()
+                    .zip(self.saved_filter_counts.iter())
+                    .map(|((name, saved), count)| {
+                        Row::new(vec![
+                            Cell::from(name.as_str()).style(self.styles.subtitle_style),
+                            Cell::from(saved.filter.as_str()).style(self.styles.text_style),
+                            Cell::from(format!("{:5}", count)).style(self.styles.text_style),
+                        ])
+                    })
+                    .collect_vec();
+
+                if saved_rows.is_empty() {
+                    saved_rows.push(Row::new(vec![
+                        Cell::from(""),
+                        Cell::from("Add filters to [saved_filters] in the config.")
+                            .style(self.styles.text_style),
+                    ]));
+                }
+
+                // Pop-up should be as tall as the number of saved filters, but a maximum of 16 rows
+                // plus 2 rows for top and bottom border
+                let saved_height = (saved_rows.len() as u16 + 2).min(16);
+                let saved_areas = Layout::vertical([
+                    Constraint::Fill(1),
+                    Constraint::Length(saved_height),
+                    Constraint::Fill(1),
+                ])
+                .split(area);
+
+                // generate a table state for selection etc.
+                let mut state = TableState::new()
+                    .with_offset(
+                        selected
+                            // try to keep element at above 1/3rd of the total height
+                            .saturating_sub(saved_height as usize / 3)
+                            .min(
+                                // but when reaching the end of the list, still scroll down
+                                saved_rows
+                                    .len()
+                                    // correct for table edges
+                                    .saturating_add(2)
+                                    .saturating_sub(saved_height as usize),
+                            ),
+                    )
+                    .with_selected((!self.saved_filters.is_empty()).then_some(selected));
+
+                // Generate the table
+                let saved_table = Table::new(saved_rows, saved_widths)
+                    .column_spacing(2)
+                    .block(
+                        Block::bordered()
+                            .title(style::Styled::set_style(
+                                "Saved Filters",
+                                self.styles.title_style,
+                            ))
+                            .title_bottom(
+                                Line::from(vec![
+                                    Span::styled("C", self.styles.hotkey_style),
+                                    Span::styled("lose", self.styles.text_style),
+                                ])
+                                .right_aligned(),
+                            )
+                            .title_bottom(
+                                Line::from(vec![
+                                    Span::styled("↵", self.styles.hotkey_style),
+                                    Span::styled(": Apply filter", self.styles.text_style),
+                                ])
+                                .left_aligned(),
+                            ),
+                    )
+                    .row_highlight_style(self.styles.selected_style);
+
+                // Generate an area to clear for the list
+                let center_area = Layout::horizontal([
+                    Constraint::Fill(1),
+                    Constraint::Length(80),
+                    Constraint::Fill(1),
+                ])
+                .split(saved_areas[1])[1];
+
+                // Clear the area and then render the list on top.
+                Widget::render(Clear, center_area, buf);
+                StatefulWidget::render(saved_table, center_area, buf, &mut state);
+            }
         }
     }
 }
@@ -0,0 +1,418 @@
+use crate::{data, error, io, ui};
+use itertools::Itertools;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+use tui_textarea::TextArea;
+
+/// Describes the current mode of the UI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+enum TaskMode {
+    /// Selecting a task from the list.
+    #[default]
+    Select,
+    /// Typing into the filter box.
+    Filter,
+}
+
+/// The task screen lists the tasks of all notes in the vault and allows the user to check them off.
+pub struct TaskScreen {
+    // === DATA ===
+    /// A reference to the index of all notes
+    index: data::NoteIndexContainer,
+    /// All tasks matching the current filter, as pairs of the id of the containing note and the task itself.
+    tasks: Vec<(String, data::Task)>,
+
+    // === Config ===
+    /// The file manager this screen uses to enact the user's file system requests on the file system.
+    manager: io::FileManager,
+    /// The used styles.
+    styles: ui::UiStyles,
+
+    // === UI ===
+    /// The text area to type in filters.
+    filter_area: TextArea<'static>,
+    /// The error message of the current filter, if it could not be parsed. Shown below the filter area.
+    filter_error: Option<String>,
+    /// Current input mode
+    mode: TaskMode,
+    /// The index of the currently selected task.
+    selected: usize,
+    /// Whether to also show tasks that are already done.
+    show_done: bool,
+    /// UI mode whether the user wants the filter conditions to all apply or if any (one of them) is enough.
+    any_conditions: bool,
+    /// UI mode whether to match tags by prefix or exactly.
+    tag_match: data::TagMatch,
+}
+
+impl TaskScreen {
+    /// Creates a new task screen, with no filter applied by default.
+    pub fn new(
+        index: data::NoteIndexContainer,
+        manager: io::FileManager,
+        styles: ui::UiStyles,
+        config: &crate::Config,
+    ) -> Self {
+        let mut res = Self {
+            index,
+            tasks: Vec::new(),
+            manager,
+            styles,
+            filter_area: TextArea::default(),
+            filter_error: None,
+            mode: TaskMode::Select,
+            selected: 0,
+            show_done: false,
+            any_conditions: false,
+            tag_match: config.tag_match,
+        };
+
+        res.style_text_area();
+        res.refresh_tasks();
+
+        res
+    }
+
+    /// Styling of the filter area, extracted to be repeated when the any/all mode or the parse error of the filter changes.
+    fn style_text_area(&mut self) {
+        let title_top = Line::from(vec![
+            Span::styled("F", self.styles.hotkey_style),
+            Span::styled("ilter", self.styles.title_style),
+        ])
+        .left_aligned();
+
+        let instructions = Line::from(vec![
+            Span::styled("C", self.styles.hotkey_style),
+            Span::styled("lear filter", self.styles.text_style),
+        ])
+        .right_aligned();
+
+        let instructions_bot = Line::from(vec![
+            Span::styled("A", self.styles.hotkey_style),
+            Span::styled(
+                if self.any_conditions { "ny" } else { "ll" },
+                self.styles.text_style,
+            ),
+            Span::styled(" Conditions", self.styles.text_style),
+        ])
+        .right_aligned();
+
+        self.filter_area.set_style(self.styles.input_style);
+        self.filter_area
+            .set_cursor_line_style(self.styles.input_style);
+
+        let mut block = Block::bordered()
+            .title_top(title_top)
+            .title_top(instructions)
+            .title_bottom(instructions_bot);
+
+        // Show parse errors of the filter inline
+        if let Some(error) = &self.filter_error {
+            block = block.title_bottom(
+                Line::from(Span::styled(error.clone(), self.styles.hotkey_style)).left_aligned(),
+            );
+        }
+
+        self.filter_area.set_block(block);
+    }
+
+    /// Creates a filter from the current content of the filter area.
+    fn filter_from_input(&self) -> data::Filter {
+        self.filter_area
+            .lines()
+            .first()
+            .map(|l| data::Filter::new(l, self.any_conditions, self.tag_match))
+            .unwrap_or_default()
+    }
+
+    /// Re-collects all tasks matching the current filter from the index.
+    /// While the filter cannot be parsed, the previous tasks are kept and the error is shown instead.
+    pub fn refresh_tasks(&mut self) {
+        let filter = self.filter_from_input();
+        if self.filter_error != filter.error {
+            self.filter_error = filter.error.clone();
+            self.style_text_area();
+        }
+
+        if self.filter_error.is_none() {
+            self.tasks = self.index.borrow().tasks_vec(&filter, self.show_done);
+        }
+
+        self.selected = self.selected.min(self.tasks.len().saturating_sub(1));
+    }
+}
+
+impl super::Screen for TaskScreen {
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        match self.mode {
+            TaskMode::Select => match key.code {
+                // Q: Quit application
+                KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+                // Esc, X: Back to the select screen
+                KeyCode::Esc | KeyCode::Char('x' | 'X') => {
+                    return Ok(ui::Message::TaskScreenClose);
+                }
+                // F, /: Go to filter mode
+                KeyCode::Char('f' | 'F' | '/') => {
+                    self.mode = TaskMode::Filter;
+                }
+                // C: Clear filter
+                KeyCode::Char('c' | 'C') => {
+                    let _ = super::extract_string_and_clear(&mut self.filter_area);
+                    self.refresh_tasks();
+                }
+                // A: Change all/any words requirement
+                KeyCode::Char('a' | 'A') => {
+                    self.any_conditions = !self.any_conditions;
+                    self.style_text_area();
+                    self.refresh_tasks();
+                }
+                // D: Show or hide tasks that are done
+                KeyCode::Char('d' | 'D') => {
+                    self.show_done = !self.show_done;
+                    self.refresh_tasks();
+                }
+                // Space: Check or uncheck the selected task
+                KeyCode::Char(' ') => {
+                    if let Some((id, task)) = self.tasks.get(self.selected) {
+                        let toggled = self
+                            .index
+                            .borrow()
+                            .get(id)
+                            .map(|note| self.manager.toggle_task(note, task));
+                        if let Some(toggled) = toggled {
+                            toggled?;
+                            // Directly re-read the note rather than waiting for the file watcher.
+                            self.index.borrow_mut().reload_note(id)?;
+                            self.refresh_tasks();
+                        }
+                    }
+                }
+                // Open the note of the selected task in the editor, at the line of the task
+                KeyCode::Char('e' | 'E') => {
+                    if let Some((path, line)) =
+                        self.tasks.get(self.selected).and_then(|(id, task)| {
+                            self.index
+                                .borrow()
+                                .get(id)
+                                .map(|note| (note.path.clone(), task.line))
+                        })
+                    {
+                        return Ok(ui::Message::OpenExternalCommand(Box::new(
+                            self.manager.create_edit_command(&path, Some(line))?,
+                        )));
+                    }
+                }
+                // Down
+                KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                    self.selected = self
+                        .selected
+                        .saturating_add(1)
+                        .min(self.tasks.len().saturating_sub(1));
+                }
+                // Up
+                KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                    self.selected = self.selected.saturating_sub(1);
+                }
+                // PageDown
+                KeyCode::PageDown => {
+                    self.selected = self
+                        .selected
+                        .saturating_add(10)
+                        .min(self.tasks.len().saturating_sub(1));
+                }
+                // PageUp
+                KeyCode::PageUp => {
+                    self.selected = self.selected.saturating_sub(10);
+                }
+                // To the start
+                KeyCode::Char('0') => {
+                    self.selected = 0;
+                }
+                // Open the note of the selected task in display view
+                KeyCode::Enter | KeyCode::Char('l' | 'L') | KeyCode::Right => {
+                    if let Some((id, _task)) = self.tasks.get(self.selected) {
+                        return Ok(ui::Message::DisplayStackPush(id.clone()));
+                    }
+                }
+                _ => {}
+            },
+            // Filter mode: Type in filter values
+            TaskMode::Filter => match key.code {
+                // Escape or Enter: Back to main mode
+                KeyCode::Esc | KeyCode::Enter => {
+                    self.mode = TaskMode::Select;
+                }
+                // All other key events are passed on to the text area, then the filter is immediately applied
+                _ => {
+                    self.filter_area.input(key);
+                    self.refresh_tasks();
+                }
+            },
+        }
+
+        Ok(ui::Message::None)
+    }
+
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let vertical = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Min(6),
+        ]);
+
+        let [title_area, filter_area, table_area] = vertical.areas(area);
+
+        // Title
+        let title = Line::from(vec![Span::styled(
+            self.manager.get_vault_title().replacen("Notes", "Tasks", 1),
+            self.styles.title_style,
+        )])
+        .alignment(Alignment::Center);
+
+        let version = Line::from(vec![Span::styled(
+            format!("rucola v{}", env!("CARGO_PKG_VERSION")),
+            self.styles.subtitle_style,
+        )])
+        .alignment(Alignment::Right);
+
+        // === Table Area ===
+
+        // Generate state from selected element
+        let mut state = TableState::new()
+            .with_offset(
+                self.selected
+                    // try to keep element at above 1/3rd of the total height
+                    .saturating_sub(table_area.height as usize / 3)
+                    .min(
+                        // but when reaching the end of the list, still scroll down
+                        self.tasks
+                            .len()
+                            // correct for table edges
+                            .saturating_add(3)
+                            .saturating_sub(table_area.height as usize),
+                    ),
+            )
+            .with_selected(match self.mode {
+                TaskMode::Select => Some(self.selected),
+                TaskMode::Filter => None,
+            });
+
+        let today = chrono::Local::now().date_naive();
+
+        let index = self.index.borrow();
+
+        let rows = self
+            .tasks
+            .iter()
+            .map(|(id, task)| {
+                Row::new(vec![
+                    Cell::from(if task.done { "[x]" } else { "[ ]" }).style(self.styles.text_style),
+                    Cell::from(task.text.as_str()).style(self.styles.text_style),
+                    Cell::from(
+                        task.due
+                            .map(|due| due.format("%Y-%m-%d").to_string())
+                            .unwrap_or_default(),
+                    )
+                    .style(
+                        // highlight tasks that are overdue
+                        if !task.done && task.due.is_some_and(|due| due < today) {
+                            self.styles.hotkey_style
+                        } else {
+                            self.styles.text_style
+                        },
+                    ),
+                    Cell::from(
+                        index
+                            .get(id)
+                            .map(|note| note.display_name.clone())
+                            .unwrap_or_default(),
+                    )
+                    .style(self.styles.subtitle_style),
+                ])
+            })
+            .collect_vec();
+
+        let widths = [
+            Constraint::Length(3),
+            Constraint::Fill(3),
+            Constraint::Length(10),
+            Constraint::Fill(1),
+        ];
+
+        // Instructions at the bottom of the page
+        let instructions_bot_left = Line::from(vec![
+            Span::styled("J", self.styles.hotkey_style),
+            Span::styled("/", self.styles.text_style),
+            Span::styled("↓", self.styles.hotkey_style),
+            Span::styled(": Down──", self.styles.text_style),
+            Span::styled("K", self.styles.hotkey_style),
+            Span::styled("/", self.styles.text_style),
+            Span::styled("↑", self.styles.hotkey_style),
+            Span::styled(": Up──", self.styles.text_style),
+            Span::styled("␣", self.styles.hotkey_style),
+            Span::styled(": Toggle──", self.styles.text_style),
+            Span::styled("L", self.styles.hotkey_style),
+            Span::styled("/", self.styles.text_style),
+            Span::styled("→", self.styles.hotkey_style),
+            Span::styled("/", self.styles.text_style),
+            Span::styled("↵", self.styles.hotkey_style),
+            Span::styled(": Open──", self.styles.text_style),
+        ])
+        .left_aligned();
+
+        let instructions_bot_right = Line::from(vec![
+            Span::styled("E", self.styles.hotkey_style),
+            Span::styled("dit──", self.styles.text_style),
+            Span::styled("D", self.styles.hotkey_style),
+            Span::styled(
+                if self.show_done {
+                    "one: Shown──"
+                } else {
+                    "one: Hidden──"
+                },
+                self.styles.text_style,
+            ),
+            Span::styled("X", self.styles.hotkey_style),
+            Span::styled("/", self.styles.text_style),
+            Span::styled("Esc", self.styles.hotkey_style),
+            Span::styled(": Back──", self.styles.text_style),
+            Span::styled("Q", self.styles.hotkey_style),
+            Span::styled("uit", self.styles.text_style),
+        ])
+        .right_aligned();
+
+        let count = Line::from(vec![Span::styled(
+            format!(
+                "{} Task{}",
+                self.tasks.len(),
+                if self.tasks.len() == 1 { "" } else { "s" }
+            ),
+            self.styles.text_style,
+        )])
+        .right_aligned();
+
+        let table = Table::new(rows, widths)
+            .header(Row::new(
+                ["", "Task", "Due", "Note"]
+                    .into_iter()
+                    .map(|title| Span::styled(title, self.styles.subtitle_style)),
+            ))
+            .row_highlight_style(self.styles.selected_style)
+            .block(
+                Block::bordered()
+                    .title_top(style::Styled::set_style("Tasks", self.styles.title_style))
+                    .title_top(count)
+                    .title_bottom(instructions_bot_left)
+                    .title_bottom(instructions_bot_right),
+            );
+
+        // === Rendering ===
+        Widget::render(title, title_area, buf);
+        Widget::render(version, title_area, buf);
+
+        Widget::render(&self.filter_area, filter_area, buf);
+
+        StatefulWidget::render(table, table_area, buf, &mut state);
+    }
+}